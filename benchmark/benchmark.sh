@@ -36,3 +36,20 @@ cargo run -- --headless
 cargo bench -- tests::generate_csv -- --ram IntuitionisticLogic
 cp data.csv data_intuitionistic_ram.csv
 cargo run -- --headless
+
+# Measure CPU instructions on the book problems of first order and intuitionistic logic
+# Unlike random formulas, book problems are proved with contradiction checks
+cargo bench -- tests::generate_csv -- --cpu --book FirstOrderLogic+ConstantDomain+NecessaryIdentity+WithoutModality
+cp data.csv data_first_order_book_cpu.csv
+cargo run -- --headless
+cargo bench -- tests::generate_csv -- --cpu --book IntuitionisticLogic
+cp data.csv data_intuitionistic_book_cpu.csv
+cargo run -- --headless
+
+# Measure the wall clock time on the same book problems, where CPU instructions cannot be counted
+cargo bench -- tests::generate_csv -- --time --book FirstOrderLogic+ConstantDomain+NecessaryIdentity+WithoutModality
+cp data.csv data_first_order_book_time.csv
+cargo run -- --headless
+cargo bench -- tests::generate_csv -- --time --book IntuitionisticLogic
+cp data.csv data_intuitionistic_book_time.csv
+cargo run -- --headless
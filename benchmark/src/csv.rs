@@ -5,6 +5,7 @@ use std::path::Path;
 use std::io::{BufRead, BufReader, Write};
 use std::rc::Rc;
 use std::str::FromStr;
+use std::time::Instant;
 use anyhow::{Context, Result};
 use itertools::Itertools;
 use prover::codeloc;
@@ -12,10 +13,14 @@ use prover::formula::Formula;
 use prover::formula::to_string::FormulaFormatOptions;
 use prover::logic::Logic;
 use prover::parser::algorithm::LogicalExpressionParser;
-use prover::problem::{Problem, ProblemFlags};
+use prover::problem::{Problem, ProblemFlags, ProofLimits};
+use prover::problem::catalog::get_demo_problem_catalog;
+use prover::proof::cancellation::ProofCancellationToken;
 
 const RANDOM_FORMULAS_FILE_NAME : &str = "random_formulas.txt";
 const DATA_CSV_FILE_NAME : &str = "data.csv";
+//wall clock time is noisy, thus the fastest of a few runs is kept
+const NUMBER_OF_TIME_MEASUREMENTS : usize = 7;
 
 #[inline(always)]
 fn measure_number_of_cpu_instructions<F>(function : F) -> Result<u64> where F : FnOnce() -> ()
@@ -25,6 +30,17 @@ fn measure_number_of_cpu_instructions<F>(function : F) -> Result<u64> where F :
     return Ok(count);
 }
 
+#[inline(always)]
+fn measure_number_of_microseconds<F>(function : F) -> u128 where F : Fn() -> ()
+{
+    return (0..NUMBER_OF_TIME_MEASUREMENTS).map(|_|
+    {
+        let start_time = Instant::now();
+        function();
+        return start_time.elapsed().as_micros();
+    }).min().unwrap_or_default();
+}
+
 #[inline(always)]
 fn measure_total_number_of_allocated_bytes<F>(function : F) -> u64 where F : FnOnce() -> ()
 {
@@ -79,6 +95,7 @@ pub fn read_random_problems(logic : &Rc<dyn Logic>) -> Result<Vec<Problem>>
                 {
                     should_skip_contradiction_check: true,
                     non_rigid_designators: BTreeSet::new(),
+                    limits: ProofLimits::default(),
                 }
             });
         }
@@ -87,16 +104,25 @@ pub fn read_random_problems(logic : &Rc<dyn Logic>) -> Result<Vec<Problem>>
     return Ok(problems);
 }
 
+//unlike random problems, book problems are proved with contradiction checks
+pub fn read_book_problems(logic : &Rc<dyn Logic>) -> Result<Vec<Problem>>
+{
+    let logic_name = logic.get_name().to_string();
+
+    return get_demo_problem_catalog().context(codeloc!())?.into_iter()
+        .flat_map(|chapter| chapter.problems)
+        .filter(|problem| problem.logic == logic_name)
+        .map(|problem| problem.to_problem())
+        .collect::<Result<Vec<Problem>>>();
+}
+
 pub fn generate_csv(logic : &Rc<dyn Logic>) -> Result<()>
 {
     let program_args = env::args().collect_vec();
 
-    let problems = read_random_problems(logic).context(codeloc!())?;
-    /*let problems = get_demo_problem_catalog().unwrap().into_iter()
-        .flat_map(|chapter| chapter.problems)
-        .map(|problem| problem.to_problem().unwrap())
-        .filter(|problem| problem.logic.get_name().is_first_order_logic())
-        .collect_vec();*/
+    let problems = if program_args.contains(&String::from("--book"))
+    { read_book_problems(logic).context(codeloc!())? }
+    else { read_random_problems(logic).context(codeloc!())? };
 
     let data_file_path = Path::new(DATA_CSV_FILE_NAME);
     fs::remove_file(data_file_path).unwrap_or_default();
@@ -115,22 +141,29 @@ pub fn generate_csv(logic : &Rc<dyn Logic>) -> Result<()>
         if program_args.contains(&String::from("--cpu"))
         {
             let problem = problem.clone();
-            let lambda = move || { problem.prove(); };
+            let lambda = move || { problem.prove(ProofCancellationToken::new()); };
             let instruction_count = measure_number_of_cpu_instructions(lambda).context(codeloc!())?;
 
             writeln!(data_file, "{},{},{},{}", problem_id, logic_name, input_indicator, instruction_count).context(codeloc!())?;
         }
+        else if program_args.contains(&String::from("--time"))
+        {
+            let lambda = || { problem.clone().prove(ProofCancellationToken::new()); };
+            let number_of_microseconds = measure_number_of_microseconds(lambda);
+
+            writeln!(data_file, "{},{},{},{}", problem_id, logic_name, input_indicator, number_of_microseconds).context(codeloc!())?;
+        }
         else if program_args.contains(&String::from("--ram"))
         {
             let problem = problem.clone();
-            let lambda = move || { problem.prove(); };
+            let lambda = move || { problem.prove(ProofCancellationToken::new()); };
             let allocated_bytes_count = measure_total_number_of_allocated_bytes(lambda);
 
             writeln!(data_file, "{},{},{},{}", problem_id, logic_name, input_indicator, allocated_bytes_count).context(codeloc!())?;
         }
         else
         {
-            let proof_tree = problem.clone().prove();
+            let proof_tree = problem.clone().prove(ProofCancellationToken::new());
             let proof_tree_size = proof_tree.get_total_number_of_nodes();
 
             writeln!(data_file, "{},{},{},{}", problem_id, logic_name, input_indicator, proof_tree_size).context(codeloc!())?;
@@ -10,6 +10,7 @@ pub struct CountermodelGraph
 {
     pub nodes : BTreeSet<CountermodelGraphNode>,
     pub vertices : BTreeSet<CountermodelGraphVertex>,
+    pub star_worlds : BTreeMap<PossibleWorld, PossibleWorld>,
     pub was_built_from_modality_graph : bool,
     pub comment : String,
 }
@@ -38,6 +39,7 @@ impl CountermodelGraph
         {
             nodes: BTreeSet::new(),
             vertices: BTreeSet::new(),
+            star_worlds: BTreeMap::new(),
             was_built_from_modality_graph: false,
             comment: String::new(),
         };
@@ -57,10 +57,13 @@ impl ProofTree
 
         for number_of_graph_nodes in min_number_of_graph_nodes..=max_number_of_graph_nodes
         {
-            for graph in graph_generator.generate_graphs(number_of_graph_nodes)
+            for graph in graph_generator.generate_graphs(number_of_graph_nodes, &self.cancellation_token)
             {
                 for domain in domain_generator.generate_domains(min_domain_size, max_domain_size)
                 {
+                    //the search space grows exponentially, it must stop once the proof was cancelled
+                    if self.cancellation_token.is_cancelled() { return None };
+
                     let formulas_without_quantifiers = premises_and_non_conclusion.iter()
                         .map(|formula| formula.eliminate_quantifiers(&domain))
                         .collect::<Vec<Formula>>();
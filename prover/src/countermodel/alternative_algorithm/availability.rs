@@ -28,10 +28,17 @@ impl AlternativeCountermodelFinderAvailability
         [
             Rc::new(PropositionalLogic{}),
             Rc::new(NormalModalLogic::K()),
+            Rc::new(NormalModalLogic::D()),
             Rc::new(NormalModalLogic::T()),
             Rc::new(NormalModalLogic::B()),
+            Rc::new(NormalModalLogic::K4()),
+            Rc::new(NormalModalLogic::K5()),
+            Rc::new(NormalModalLogic::K45()),
+            Rc::new(NormalModalLogic::KD45()),
             Rc::new(NormalModalLogic::S4()),
             Rc::new(NormalModalLogic::S5()),
+            Rc::new(NormalModalLogic::GL()),
+            Rc::new(NormalModalLogic::SDL()),
         ];
     }
 
@@ -1,7 +1,7 @@
 use crate::formula::notations::OperatorNotations;
 use crate::formula::to_string::FormulaFormatOptions;
 use crate::formula::Formula;
-use crate::formula::Formula::{And, BiImply, Conditional, Exists, ForAll, Imply, InFuture, InPast, Necessary, Non, Or, Possible, StrictImply};
+use crate::formula::Formula::{AgentNecessary, AgentPossible, And, BiImply, CommonKnowledge, Conditional, DistributedKnowledge, EveryoneKnows, Exists, ForAll, Imply, InFuture, InPast, Necessary, Next, Non, Or, Possible, Since, StrictImply, Until};
 use box_macro::bx;
 use logicng::formulas::{EncodedFormula as LogicNGEncodedFormula, FormulaFactory as LogicNGFormulaFactory};
 
@@ -45,10 +45,18 @@ impl Formula
             BiImply(box p, box q, extras) => BiImply(bx!(p.eliminate_implication()), bx!(q.eliminate_implication()), extras.clone()),
             StrictImply(box p, box q, extras) => StrictImply(bx!(p.eliminate_implication()), bx!(q.eliminate_implication()), extras.clone()),
             Conditional(box p, box q, extras) => Conditional(bx!(p.eliminate_implication()), bx!(q.eliminate_implication()), extras.clone()),
+            Until(box p, box q, extras) => Until(bx!(p.eliminate_implication()), bx!(q.eliminate_implication()), extras.clone()),
+            Since(box p, box q, extras) => Since(bx!(p.eliminate_implication()), bx!(q.eliminate_implication()), extras.clone()),
             Exists(x, box p, extras) => Exists(x.clone(), bx!(p.eliminate_implication()), extras.clone()),
             ForAll(x, box p, extras) => ForAll(x.clone(), bx!(p.eliminate_implication()), extras.clone()),
             InPast(box p, extras) => InPast(bx!(p.eliminate_implication()), extras.clone()),
             InFuture(box p, extras) => InFuture(bx!(p.eliminate_implication()), extras.clone()),
+            Next(box p, extras) => Next(bx!(p.eliminate_implication()), extras.clone()),
+            AgentPossible(agent, box p, extras) => AgentPossible(agent.clone(), bx!(p.eliminate_implication()), extras.clone()),
+            AgentNecessary(agent, box p, extras) => AgentNecessary(agent.clone(), bx!(p.eliminate_implication()), extras.clone()),
+            EveryoneKnows(agents, box p, extras) => EveryoneKnows(agents.clone(), bx!(p.eliminate_implication()), extras.clone()),
+            CommonKnowledge(agents, box p, extras) => CommonKnowledge(agents.clone(), bx!(p.eliminate_implication()), extras.clone()),
+            DistributedKnowledge(agents, box p, extras) => DistributedKnowledge(agents.clone(), bx!(p.eliminate_implication()), extras.clone()),
             Possible(box p, extras) => Possible(bx!(p.eliminate_implication()), extras.clone()),
             Necessary(box p, extras) => Necessary(bx!(p.eliminate_implication()), extras.clone()),
 
@@ -84,10 +92,18 @@ impl Formula
             Imply(box p, box q, extras) => Imply(bx!(p.eliminate_equivalence()), bx!(q.eliminate_equivalence()), extras.clone()),
             StrictImply(box p, box q, extras) => StrictImply(bx!(p.eliminate_equivalence()), bx!(q.eliminate_equivalence()), extras.clone()),
             Conditional(box p, box q, extras) => Conditional(bx!(p.eliminate_equivalence()), bx!(q.eliminate_equivalence()), extras.clone()),
+            Until(box p, box q, extras) => Until(bx!(p.eliminate_equivalence()), bx!(q.eliminate_equivalence()), extras.clone()),
+            Since(box p, box q, extras) => Since(bx!(p.eliminate_equivalence()), bx!(q.eliminate_equivalence()), extras.clone()),
             Exists(x, box p, extras) => Exists(x.clone(), bx!(p.eliminate_equivalence()), extras.clone()),
             ForAll(x, box p, extras) => ForAll(x.clone(), bx!(p.eliminate_equivalence()), extras.clone()),
             InPast(box p, extras) => InPast(bx!(p.eliminate_equivalence()), extras.clone()),
             InFuture(box p, extras) => InFuture(bx!(p.eliminate_equivalence()), extras.clone()),
+            Next(box p, extras) => Next(bx!(p.eliminate_equivalence()), extras.clone()),
+            AgentPossible(agent, box p, extras) => AgentPossible(agent.clone(), bx!(p.eliminate_equivalence()), extras.clone()),
+            AgentNecessary(agent, box p, extras) => AgentNecessary(agent.clone(), bx!(p.eliminate_equivalence()), extras.clone()),
+            EveryoneKnows(agents, box p, extras) => EveryoneKnows(agents.clone(), bx!(p.eliminate_equivalence()), extras.clone()),
+            CommonKnowledge(agents, box p, extras) => CommonKnowledge(agents.clone(), bx!(p.eliminate_equivalence()), extras.clone()),
+            DistributedKnowledge(agents, box p, extras) => DistributedKnowledge(agents.clone(), bx!(p.eliminate_equivalence()), extras.clone()),
             Possible(box p, extras) => Possible(bx!(p.eliminate_equivalence()), extras.clone()),
             Necessary(box p, extras) => Necessary(bx!(p.eliminate_equivalence()), extras.clone()),
 
@@ -8,6 +8,7 @@ use smol_str::SmolStr;
 use crate::countermodel::{CountermodelGraph, CountermodelGraphNode, CountermodelGraphVertex};
 use crate::formula::PossibleWorld;
 use crate::logic::Logic;
+use crate::proof::cancellation::ProofCancellationToken;
 use crate::utils::{get_config_value, CONFIG_KEY_SHOULD_SHUFFLE_COUNTERMODEL_GRAPHS};
 
 pub struct CountermodelGraphGenerator
@@ -18,71 +19,71 @@ pub struct CountermodelGraphGenerator
 
 impl CountermodelGraphGenerator
 {
-    fn generate_graph_codes(&self, number_of_nodes : u8) -> Vec<BigUint>
+    //the codes are generated lazily, there are 2^(n^2) of them
+    fn generate_graph_codes(&self, number_of_nodes : u8, cancellation_token : &ProofCancellationToken) -> Box<dyn Iterator<Item = BigUint>>
     {
-        let mut random = rand::thread_rng();
-        let mut codes : Vec<BigUint> = Vec::new();
-        let mut code = BigUint::ZERO;
-
-        while code < BigUint::from(2u8).pow(number_of_nodes.pow(2) as u32)
-        {
-            codes.push(code.clone());
-            code = code.add(BigUint::one());
-        }
+        let number_of_codes = BigUint::from(2u8).pow(number_of_nodes.pow(2) as u32);
+        let codes = std::iter::successors(Some(BigUint::ZERO), |code| Some(code.add(BigUint::one())))
+            .take_while(move |code| *code < number_of_codes);
 
         if get_config_value::<bool>(CONFIG_KEY_SHOULD_SHUFFLE_COUNTERMODEL_GRAPHS).unwrap_or_default()
         {
-            codes.shuffle(&mut random);
+            //shuffling needs all the codes at once
+            let mut shuffled_codes = codes
+                .take_while(|_| !cancellation_token.is_cancelled())
+                .collect::<Vec<BigUint>>();
+
+            shuffled_codes.shuffle(&mut rand::thread_rng());
+            return Box::new(shuffled_codes.into_iter());
         }
 
-        return codes;
+        return Box::new(codes);
     }
 
-    pub fn generate_graphs(&self, number_of_nodes : u8) -> Vec<CountermodelGraph>
+    //most of the graphs are not valid on the logic, thus the graphs are filtered lazily, until the proof is cancelled
+    pub fn generate_graphs<'a>(&'a self, number_of_nodes : u8, cancellation_token : &'a ProofCancellationToken) -> impl Iterator<Item = CountermodelGraph> + 'a
     {
         let atomics: BTreeMap<String, bool> = self.atomic_names.clone()
             .into_iter().map(|name| (name.to_string(), false)).collect();
 
-        let mut generated_graphs : Vec<CountermodelGraph> = vec![];
+        return self.generate_graph_codes(number_of_nodes, cancellation_token)
+            .take_while(|_| !cancellation_token.is_cancelled())
+            .map(move |code| self.generate_graph(number_of_nodes, &code, &atomics))
+            .filter(|graph| graph.validate(&self.logic).is_ok());
+    }
 
-        for code in self.generate_graph_codes(number_of_nodes)
-        {
-            let mut graph = CountermodelGraph::new();
+    fn generate_graph(&self, number_of_nodes : u8, code : &BigUint, atomics : &BTreeMap<String, bool>) -> CountermodelGraph
+    {
+        let mut graph = CountermodelGraph::new();
 
-            for world_index in 0..number_of_nodes
+        for world_index in 0..number_of_nodes
+        {
+            graph.nodes.insert(CountermodelGraphNode
             {
-                graph.nodes.insert(CountermodelGraphNode
-                {
-                    possible_world: PossibleWorld { index: world_index },
-                    is_normal_world: true, atomics: atomics.clone(),
-                });
-            }
+                possible_world: PossibleWorld { index: world_index },
+                is_normal_world: true, atomics: atomics.clone(),
+            });
+        }
 
-            let mut bit_index = 0u64;
-            for from_world_index in 0..number_of_nodes
+        let mut bit_index = 0u64;
+        for from_world_index in 0..number_of_nodes
+        {
+            for to_world_index in 0..number_of_nodes
             {
-                for to_world_index in 0..number_of_nodes
+                if code.bit(bit_index)
                 {
-                    if code.bit(bit_index)
+                    graph.vertices.insert(CountermodelGraphVertex
                     {
-                        graph.vertices.insert(CountermodelGraphVertex
-                        {
-                            from: PossibleWorld { index: from_world_index },
-                            to: PossibleWorld { index: to_world_index },
-                            tags: Vec::new(),
-                        });
-                    }
-
-                    bit_index += 1;
+                        from: PossibleWorld { index: from_world_index },
+                        to: PossibleWorld { index: to_world_index },
+                        tags: Vec::new(),
+                    });
                 }
-            }
 
-            if graph.validate(&self.logic).is_ok()
-            {
-                generated_graphs.push(graph);
+                bit_index += 1;
             }
         }
 
-        return generated_graphs;
+        return graph;
     }
 }
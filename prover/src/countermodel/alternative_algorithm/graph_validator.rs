@@ -33,9 +33,14 @@ impl CountermodelGraph
             is_valid = false;
         }
 
-        if logic.get_name().is_normal_modal_logic()
+        if logic.get_name().is_linear_temporal_logic() && !self.is_linear()
+        {
+            validation_message.push_str("Invalid graph: not a linear trace!");
+            is_valid = false;
+        }
+
+        if logic.get_name().is_normal_modal_logic() && let Some(logic) = logic.cast_to::<NormalModalLogic>()
         {
-            let logic = logic.cast_to::<NormalModalLogic>().unwrap();
             if logic.is_reflexive && !self.is_reflexive()
             {
                 validation_message.push_str("Invalid graph: not reflexive!");
@@ -48,11 +53,30 @@ impl CountermodelGraph
                 is_valid = false;
             }
 
-            if logic.is_transitive && self.is_transitive()
+            if logic.is_transitive && !self.is_transitive()
             {
                 validation_message.push_str("Invalid graph: not transitive!");
                 is_valid = false;
             }
+
+            if logic.is_serial && !self.is_serial()
+            {
+                validation_message.push_str("Invalid graph: not serial!");
+                is_valid = false;
+            }
+
+            if logic.is_euclidean && !self.is_euclidean()
+            {
+                validation_message.push_str("Invalid graph: not euclidean!");
+                is_valid = false;
+            }
+
+            //on finite graphs, transitive and irreflexive implies converse well-founded (no cycles)
+            if logic.is_converse_well_founded && !(self.is_irreflexive() && self.is_transitive())
+            {
+                validation_message.push_str("Invalid graph: not converse well-founded!");
+                is_valid = false;
+            }
         }
 
         return if is_valid { Ok(()) } else { Err(anyhow!(validation_message)) };
@@ -65,6 +89,11 @@ impl CountermodelGraph
                 vertex.from == node.possible_world && vertex.from == vertex.to))
     }
 
+    pub fn is_irreflexive(&self) -> bool
+    {
+        return self.vertices.iter().all(|vertex| vertex.from != vertex.to);
+    }
+
     pub fn is_symmetric(&self) -> bool
     {
         return self.vertices.iter()
@@ -76,9 +105,30 @@ impl CountermodelGraph
 
     pub fn is_transitive(&self) -> bool
     {
-        return !self.vertices.iter().cartesian_product(self.vertices.iter())
+        return self.vertices.iter().cartesian_product(self.vertices.iter())
             .filter(|(v1, v2)| v1.from != v1.to && v2.from != v2.to && v2.from == v1.to)
             .all(|(v1, v2)| self.vertices.iter()
                 .any(|v3| v3.from == v1.from && v3.to == v2.to))
     }
+
+    pub fn is_serial(&self) -> bool
+    {
+        return self.nodes.iter().all(|node|
+            self.vertices.iter().any(|vertex| vertex.from == node.possible_world))
+    }
+
+    //every world has exactly one next world
+    pub fn is_linear(&self) -> bool
+    {
+        return self.nodes.iter().all(|node|
+            self.vertices.iter().filter(|vertex| vertex.from == node.possible_world).count() == 1)
+    }
+
+    pub fn is_euclidean(&self) -> bool
+    {
+        return self.vertices.iter().cartesian_product(self.vertices.iter())
+            .filter(|(v1, v2)| v1.from == v2.from)
+            .all(|(v1, v2)| self.vertices.iter()
+                .any(|v3| v3.from == v1.to && v3.to == v2.to))
+    }
 }
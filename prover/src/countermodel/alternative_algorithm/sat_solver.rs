@@ -37,6 +37,7 @@ impl SATSolver
                 {
                     nodes: nodes_with_attached_atomic_values,
                     vertices: graph.vertices.clone(),
+                    star_worlds: graph.star_worlds.clone(),
                     was_built_from_modality_graph: false,
                     comment: String::new(),
                 });
@@ -1,10 +1,14 @@
 use std::collections::{BTreeMap, BTreeSet};
+use itertools::Itertools;
 use smol_str::SmolStr;
 use crate::countermodel::{CountermodelGraph, CountermodelGraphNode, CountermodelGraphVertex};
 use crate::formula::Formula::{Atomic, Necessary, StrictImply};
 use crate::formula::PossibleWorld;
+use crate::formula::Sign::Plus;
 use crate::formula::to_string::FormulaFormatOptions;
 use crate::graph::GraphVertex;
+use crate::logic::multi_agent_modal_logic::MultiAgentModalLogic;
+use crate::logic::normal_modal_logic::NormalModalLogic;
 use crate::tree::path::ProofTreePath;
 use crate::tree::ProofTree;
 
@@ -15,13 +19,16 @@ impl ProofTree
         //no countermodel if proof is correct
         if self.is_proof_correct { return None };
 
-        //on timeout, the alternate algorithm is used
-        if self.has_timeout { return self.find_countermodel_alt() };
+        //on timeout or cancellation the tree is incomplete, thus the alternate algorithm is used
+        if self.has_timeout || self.cancellation.is_some() { return self.find_countermodel_alt() };
 
         //not yet implemented on first order logic and many valued logics
         let logic = self.problem.logic.clone();
         if logic.get_name().is_first_order_logic() { return None };
-        if logic.get_semantics().number_of_truth_values() > 2 { return None };
+        if logic.get_semantics().number_of_truth_values_on_each_world() > 2 { return None };
+
+        //the values of truth matrix logics are tags on the formulas, even with two values, eg: Post2MatrixLogic
+        if logic.get_name().is_truth_matrix_logic() { return None };
 
         let atomic_names = self.problem.premises.iter()
             .chain(Some(&self.problem.conclusion).into_iter())
@@ -34,10 +41,24 @@ impl ProofTree
         let mut graph_nodes : BTreeSet<CountermodelGraphNode> = BTreeSet::new();
         let mut graph_vertices : BTreeSet<CountermodelGraphVertex> = BTreeSet::new();
 
-        let possible_worlds = path.nodes.iter()
+        let mut possible_worlds = path.nodes.iter()
             .map(|node| node.formula.get_possible_world())
             .collect::<BTreeSet<PossibleWorld>>();
 
+        let star_worlds = self.modality_graph.star_worlds()
+            .filter(|(possible_world, _star_world)| possible_worlds.contains(possible_world))
+            .flat_map(|(possible_world, star_world)| [(*possible_world, *star_world), (*star_world, *possible_world)])
+            .collect::<BTreeMap<PossibleWorld, PossibleWorld>>();
+        possible_worlds.extend(star_worlds.keys());
+
+        let mut comment = String::new();
+        if logic.get_name().is_hybrid_logic()
+        {
+            //worlds named by the same nominal were merged into one world
+            possible_worlds.retain(|possible_world| self.modality_graph.get_identical_world_on_path(&path, *possible_world) == *possible_world);
+            comment = self.get_nominals_assignment(&path, &mut possible_worlds);
+        }
+
         for possible_world in &possible_worlds
         {
             graph_nodes.insert(CountermodelGraphNode
@@ -48,16 +69,51 @@ impl ProofTree
             });
         }
 
-        for possible_world in possible_worlds
+        for possible_world in &possible_worlds
+        {
+            self.populate_with_graph_vertices(*possible_world, &path, &graph_nodes, &mut graph_vertices);
+        }
+
+        if !logic.get_name().is_description_logic()
         {
-            self.populate_with_graph_vertices(possible_world, &graph_nodes, &mut graph_vertices);
+            self.populate_with_blocked_worlds_graph_vertices(&path, true, &mut graph_vertices);
+        }
+
+        if let Some(normal_modal_logic) = logic.cast_to::<NormalModalLogic>() && normal_modal_logic.is_serial
+        {
+            //worlds without accessible worlds hold no necessities, they can safely access themselves
+            self.populate_with_serial_graph_vertices(&possible_worlds, &mut graph_vertices);
+        }
+
+        if let Some(multi_agent_modal_logic) = logic.cast_to::<MultiAgentModalLogic>()
+        {
+            self.populate_with_agents_graph_vertices(multi_agent_modal_logic, &possible_worlds, &mut graph_vertices);
+        }
+
+        if logic.get_name().is_description_logic()
+        {
+            //the roles are not transitive, a blocked individual only takes the role fillers of its blocking individual
+            self.populate_with_blocked_worlds_graph_vertices(&path, false, &mut graph_vertices);
+            comment = self.get_assertional_box(&graph_nodes, &graph_vertices);
+        }
+
+        if logic.get_name().is_dynamic_logic()
+        {
+            self.populate_with_tagged_graph_vertices(&possible_worlds, &mut graph_vertices);
+        }
+
+        if logic.get_name().is_linear_temporal_logic()
+        {
+            //the last instant of the branch holds no temporal formulas, it can safely repeat forever
+            self.populate_with_serial_graph_vertices(&possible_worlds, &mut graph_vertices);
+            comment = self.get_lasso_trace(&graph_vertices);
         }
 
         return Some(CountermodelGraph
         {
-            nodes: graph_nodes, vertices: graph_vertices,
+            nodes: graph_nodes, vertices: graph_vertices, star_worlds,
             was_built_from_modality_graph: true,
-            comment: String::new(),
+            comment,
         });
     }
 
@@ -68,27 +124,30 @@ impl ProofTree
             return possible_world == PossibleWorld::zero() ||
                 path.nodes.iter().any(|node|
                     node.formula.get_possible_world() == possible_world &&
-                    matches!(node.formula, Necessary(..) | StrictImply(..)));
+                    matches!(*node.formula, Necessary(..) | StrictImply(..)));
         }
 
         return true;
     }
 
-    fn populate_with_graph_vertices(&self, possible_world : PossibleWorld,
+    fn populate_with_graph_vertices(&self, possible_world : PossibleWorld, path : &ProofTreePath,
         output_nodes : &BTreeSet<CountermodelGraphNode>, output_vertices : &mut BTreeSet<CountermodelGraphVertex>)
     {
         let formula_format_options = FormulaFormatOptions::default();
 
-        let original_vertices = self.modality_graph.vertices()
+        //the graph of the open branch, including vertices added by branching frame rules
+        let original_vertices = self.modality_graph.vertices_on_path(path).into_iter()
             .filter(|vertex| (vertex.from == possible_world || vertex.to == possible_world) &&
                 output_nodes.iter().any(|node| node.possible_world == vertex.from) &&
                 output_nodes.iter().any(|node| node.possible_world == vertex.to))
-            .collect::<BTreeSet<&GraphVertex>>();
+            .collect::<BTreeSet<GraphVertex>>();
+
+        let vertices_tags = self.modality_graph.vertices_tags_on_path(path);
 
         for original_vertex in original_vertices
         {
-            let tags = self.modality_graph.vertices_tags()
-                .filter(|(vertex, _tag)| vertex == original_vertex)
+            let tags = vertices_tags.iter()
+                .filter(|(vertex, _tag)| *vertex == original_vertex)
                 .map(|(_v, tag)| tag.to_string_with_options(&formula_format_options))
                 .collect::<Vec<String>>();
 
@@ -100,6 +159,143 @@ impl ProofTree
         }
     }
 
+    //a blocked world accesses the worlds accessible from its blocking world, they fulfill its possibilities.
+    //if the frame is transitive, the worlds accessing the blocked world access them too
+    fn populate_with_blocked_worlds_graph_vertices(&self, path : &ProofTreePath, is_transitive : bool, output_vertices : &mut BTreeSet<CountermodelGraphVertex>)
+    {
+        let blocked_worlds = self.modality_graph.blocked_worlds_on_path(path);
+        if blocked_worlds.is_empty() { return }
+
+        loop
+        {
+            let inherited_vertices = blocked_worlds.iter()
+                .flat_map(|(blocked_world, blocking_world)| output_vertices.iter()
+                    .filter(|vertex| vertex.from == *blocking_world)
+                    .map(|vertex| CountermodelGraphVertex { from: *blocked_world, to: vertex.to, tags: vertex.tags.clone() }));
+
+            let transitive_vertices = output_vertices.iter()
+                .filter(|_| is_transitive)
+                .flat_map(|i_vertex| output_vertices.iter()
+                    .filter(|j_vertex| i_vertex.to == j_vertex.from)
+                    .map(|j_vertex| CountermodelGraphVertex { from: i_vertex.from, to: j_vertex.to, tags: i_vertex.tags.clone() }));
+
+            let vertices_to_add = inherited_vertices.chain(transitive_vertices)
+                .filter(|vertex| !output_vertices.contains(vertex))
+                .collect::<BTreeSet<CountermodelGraphVertex>>();
+
+            if vertices_to_add.is_empty() { return }
+            output_vertices.extend(vertices_to_add);
+        }
+    }
+
+    fn populate_with_serial_graph_vertices(&self, possible_worlds : &BTreeSet<PossibleWorld>, output_vertices : &mut BTreeSet<CountermodelGraphVertex>)
+    {
+        for possible_world in possible_worlds
+        {
+            if !output_vertices.iter().any(|vertex| vertex.from == *possible_world)
+            {
+                output_vertices.insert(CountermodelGraphVertex
+                {
+                    from: *possible_world,
+                    to: *possible_world,
+                    tags: Vec::new(),
+                });
+            }
+        }
+    }
+
+    //eg: w0 → w1 → w2 ↺ w1 is the infinite trace w0, w1, w2, w1, w2, ...
+    fn get_lasso_trace(&self, vertices : &BTreeSet<CountermodelGraphVertex>) -> String
+    {
+        let mut trace = vec![PossibleWorld::zero()];
+        while let Some(vertex) = vertices.iter().find(|vertex| vertex.from == *trace.last().unwrap())
+        {
+            let trace_string = trace.iter().map(|world| world.to_string()).join(" → ");
+            if trace.contains(&vertex.to) { return format!("{} ↺ {}", trace_string, vertex.to) }
+            trace.push(vertex.to);
+        }
+
+        return trace.iter().map(|world| world.to_string()).join(" → ");
+    }
+
+    //on description logic, worlds are individuals and vertices are role assertions, eg: Person(w0), hasChild(w0, w1), Doctor(w1)
+    fn get_assertional_box(&self, nodes : &BTreeSet<CountermodelGraphNode>, vertices : &BTreeSet<CountermodelGraphVertex>) -> String
+    {
+        let concept_assertions = nodes.iter().flat_map(|node| node.atomics.iter()
+            .filter(|(_concept, is_instance)| **is_instance)
+            .map(|(concept, _is_instance)| format!("{}({})", concept, node.possible_world)));
+
+        let role_assertions = vertices.iter().flat_map(|vertex| vertex.tags.iter()
+            .map(|role| format!("{}({}, {})", role, vertex.from, vertex.to)));
+
+        return concept_assertions.chain(role_assertions).join(", ");
+    }
+
+    //eg: i = w0, j = w2, nominals that do not name any world of the branch name a new world
+    fn get_nominals_assignment(&self, path : &ProofTreePath, possible_worlds : &mut BTreeSet<PossibleWorld>) -> String
+    {
+        let nominal_names = self.problem.premises.iter()
+            .chain(Some(&self.problem.conclusion).into_iter())
+            .flat_map(|formula| formula.get_all_nominal_names())
+            .collect::<BTreeSet<SmolStr>>();
+
+        let mut new_world : Option<PossibleWorld> = None;
+        let mut assignment : Vec<String> = Vec::new();
+        for nominal_name in nominal_names
+        {
+            let named_world = match self.modality_graph.get_world_named_by_nominal(path, &nominal_name)
+            {
+                Some(named_world) => named_world,
+                None => *new_world.get_or_insert(self.modality_graph.nodes().max().unwrap_or(&PossibleWorld::zero()).fork())
+            };
+
+            assignment.push(format!("{} = {}", nominal_name, named_world));
+        }
+
+        possible_worlds.extend(new_world);
+        return assignment.join(", ");
+    }
+
+    fn populate_with_agents_graph_vertices(&self, logic : &MultiAgentModalLogic, possible_worlds : &BTreeSet<PossibleWorld>, output_vertices : &mut BTreeSet<CountermodelGraphVertex>)
+    {
+        self.populate_with_tagged_graph_vertices(possible_worlds, output_vertices);
+
+        for agent in self.modality_graph.agents()
+        {
+            if !logic.get_agent_logic(&agent).is_serial { continue }
+
+            for possible_world in possible_worlds
+            {
+                if !output_vertices.iter().any(|vertex| vertex.from == *possible_world && vertex.tags.contains(&agent.to_string()))
+                {
+                    output_vertices.insert(CountermodelGraphVertex
+                    {
+                        from: *possible_world,
+                        to: *possible_world,
+                        tags: vec![agent.to_string()],
+                    });
+                }
+            }
+        }
+    }
+
+    //vertices are labelled with the agent (or the atomic program) whose accessibility relation contains them
+    fn populate_with_tagged_graph_vertices(&self, possible_worlds : &BTreeSet<PossibleWorld>, output_vertices : &mut BTreeSet<CountermodelGraphVertex>)
+    {
+        for (agent, vertex) in self.modality_graph.agents_vertices()
+        {
+            if possible_worlds.contains(&vertex.from) && possible_worlds.contains(&vertex.to)
+            {
+                output_vertices.insert(CountermodelGraphVertex
+                {
+                    from: vertex.from,
+                    to: vertex.to,
+                    tags: vec![agent.to_string()],
+                });
+            }
+        }
+    }
+
     fn populate_atomics(&self, atomic_names : &BTreeSet<SmolStr>, path : &ProofTreePath, possible_world : PossibleWorld) -> BTreeMap<String, bool>
     {
         let mut values : BTreeMap<String, bool> = BTreeMap::new();
@@ -108,7 +304,8 @@ impl ProofTree
         {
             let p_value = path.nodes.iter()
                 .filter(|node| node.formula.get_possible_world() == possible_world)
-                .filter_map(|node| if let Atomic(p, _) = &node.formula { Some(p) } else { None })
+                .filter(|node| node.formula.get_sign() == Plus)
+                .filter_map(|node| if let Atomic(p, _) = node.formula.as_ref() { Some(p) } else { None })
                 .any(|q| p == q);
 
             values.insert(p.to_string(), p_value);
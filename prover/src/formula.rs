@@ -28,11 +28,35 @@ pub enum Formula
     Necessary(Box<Formula>, FormulaExtras),
     InPast(Box<Formula>, FormulaExtras),
     InFuture(Box<Formula>, FormulaExtras),
+    Next(Box<Formula>, FormulaExtras),
+    Until(Box<Formula>, Box<Formula>, FormulaExtras),
+    Since(Box<Formula>, Box<Formula>, FormulaExtras),
+    AgentPossible(SmolStr, Box<Formula>, FormulaExtras),
+    AgentNecessary(SmolStr, Box<Formula>, FormulaExtras),
+    EveryoneKnows(Vec<SmolStr>, Box<Formula>, FormulaExtras),
+    CommonKnowledge(Vec<SmolStr>, Box<Formula>, FormulaExtras),
+    DistributedKnowledge(Vec<SmolStr>, Box<Formula>, FormulaExtras),
+    Nominal(SmolStr, FormulaExtras),
+    At(SmolStr, Box<Formula>, FormulaExtras),
+    Bind(SmolStr, Box<Formula>, FormulaExtras),
+    ProgramNecessary(Program, Box<Formula>, FormulaExtras),
+    ProgramPossible(Program, Box<Formula>, FormulaExtras),
     LessThan(FuzzyTags, FuzzyTags, FormulaExtras),
     GreaterOrEqualThan(FuzzyTags, FuzzyTags, FormulaExtras),
     Comment(SmolStr),
 }
 
+//programs of dynamic logic, eg: a;b, a∪b, p?, a*
+#[derive(Eq, PartialEq, Hash, Clone)]
+pub enum Program
+{
+    AtomicProgram(SmolStr),
+    Sequence(Box<Program>, Box<Program>),
+    Choice(Box<Program>, Box<Program>),
+    Test(Box<Formula>),
+    Iteration(Box<Program>),
+}
+
 #[derive(Eq, PartialEq, Hash, Clone)]
 pub struct AtomicFormulaExtras
 {
@@ -76,8 +100,16 @@ pub const LAST_OBJECT_NAME : char = 's';
 #[derive(Eq, Hash, Ord, PartialOrd, Clone)]
 pub struct PredicateArgument
 {
-    pub variable_name : SmolStr, //variable name eg: x,y,z
-    pub object_name : SmolStr, //object name eg: a,b,c
+    pub variable_name : SmolStr, //variable name eg: x,y,z or f(x,b)
+    pub object_name : SmolStr, //object name eg: a,b,c or f(a,b)
+    pub function_term : Option<FunctionTerm>, //the term tree of a function term eg: f(x,b)
+}
+
+#[derive(Eq, PartialEq, Hash, Ord, PartialOrd, Clone)]
+pub struct FunctionTerm
+{
+    pub function_name : SmolStr, //function name eg: f,g,h
+    pub args : Vec<PredicateArgument>,
 }
 
 impl PredicateArgument
@@ -85,21 +117,43 @@ impl PredicateArgument
     pub fn is_variable(&self) -> bool
     {
         let first_char = self.object_name.chars().next().unwrap();
-        return self.object_name == self.variable_name && first_char > LAST_OBJECT_NAME;
+        return self.function_term.is_none() && self.object_name == self.variable_name && first_char > LAST_OBJECT_NAME;
     }
 
     pub fn is_instantiated(&self) -> bool
     {
+        //a variable can be instantiated into a function term, eg: f(a):x
         let first_char = self.object_name.chars().next().unwrap();
-        return self.object_name != self.variable_name && first_char <= LAST_OBJECT_NAME;
+        return self.function_term.is_none() && self.object_name != self.variable_name &&
+            (first_char <= LAST_OBJECT_NAME || self.object_name.contains('('));
     }
 
     pub fn is_free_object(&self) -> bool
     {
+        if let Some(function_term) = &self.function_term
+        {
+            //a ground function term, eg: f(a,b) but not f(x,b)
+            return function_term.args.iter().all(|arg| arg.is_instantiated() || arg.is_free_object());
+        }
+
         let first_char = self.object_name.chars().next().unwrap();
         return self.object_name == self.variable_name && first_char <= LAST_OBJECT_NAME;
     }
 
+    //the nesting depth of function terms, eg: 0 for a, 1 for f(a), 2 for f(g(a),b)
+    pub fn get_term_depth(&self) -> usize
+    {
+        let mut depth = 0usize;
+        let mut max_depth = 0usize;
+        for character in self.object_name.chars()
+        {
+            if character == '(' { depth += 1; max_depth = max_depth.max(depth); }
+            else if character == ')' { depth -= 1; }
+        }
+
+        return max_depth;
+    }
+
     pub fn is_non_rigid_designator(&self) -> bool
     {
         return self.object_name == "α" || self.object_name == "β" || self.object_name == "γ"
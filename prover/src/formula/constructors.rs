@@ -1,6 +1,7 @@
-use smol_str::{SmolStr, ToSmolStr};
+use itertools::Itertools;
+use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 use substring::Substring;
-use crate::formula::{AtomicFormulaExtras, FormulaExtras, FuzzyTag, FuzzyTags, PossibleWorld, PredicateArgument, PredicateArguments, Sign};
+use crate::formula::{AtomicFormulaExtras, FormulaExtras, FunctionTerm, FuzzyTag, FuzzyTags, PossibleWorld, PredicateArgument, PredicateArguments, Sign};
 use crate::formula::Sign::Plus;
 
 impl AtomicFormulaExtras
@@ -89,17 +90,61 @@ impl PredicateArgument
 {
     pub fn new(name : SmolStr) -> PredicateArgument
     {
-        if name.contains(':')
+        if let Some(index_of_colon) = Self::find_outside_of_function_terms(&name, ':')
         {
             //this is an instantiated predicate argument
-            let index_of_colon = name.find(':').unwrap();
             let object_name = name.substring(0, index_of_colon).to_smolstr();
-            let variable_name = name.substring(index_of_colon+1, name.len()).to_smolstr();
-            return PredicateArgument { variable_name, object_name };
+            let variable_name = name.substring(index_of_colon+1, name.chars().count()).to_smolstr();
+            return PredicateArgument { variable_name, object_name, function_term: None };
+        }
+
+        if let Some(index_of_open_parenthesis) = Self::find_outside_of_function_terms(&name, '(') && name.ends_with(')')
+        {
+            //this is a function term, eg: f(x,a)
+            let function_name = name.substring(0, index_of_open_parenthesis).to_smolstr();
+            let args = Self::split_names(name.substring(index_of_open_parenthesis+1, name.chars().count()-1))
+                .into_iter().map(PredicateArgument::new).collect();
+            return PredicateArgument::with_function_term(FunctionTerm { function_name, args });
         }
 
         let (variable_name, object_name) = (name.clone(), name);
-        return PredicateArgument { variable_name, object_name };
+        return PredicateArgument { variable_name, object_name, function_term: None };
+    }
+
+    pub fn with_function_term(function_term : FunctionTerm) -> PredicateArgument
+    {
+        let format_name = |arg_names : Vec<&SmolStr>| format_smolstr!("{}({})", function_term.function_name, arg_names.iter().join(","));
+        let variable_name = format_name(function_term.args.iter().map(|arg| &arg.variable_name).collect());
+        let object_name = format_name(function_term.args.iter().map(|arg| &arg.object_name).collect());
+        return PredicateArgument { variable_name, object_name, function_term: Some(function_term) };
+    }
+
+    //splits comma separated names, but not the arguments of function terms, eg: x,f(a,b) into x and f(a,b)
+    pub fn split_names(names : &str) -> Vec<SmolStr>
+    {
+        let mut output_names : Vec<SmolStr> = Vec::new();
+        let mut rest_of_names = names;
+        while let Some(index_of_comma) = Self::find_outside_of_function_terms(rest_of_names, ',')
+        {
+            output_names.push(rest_of_names.substring(0, index_of_comma).trim().to_smolstr());
+            rest_of_names = rest_of_names.substring(index_of_comma+1, rest_of_names.chars().count());
+        }
+
+        output_names.push(rest_of_names.trim().to_smolstr());
+        return output_names;
+    }
+
+    fn find_outside_of_function_terms(name : &str, character_to_find : char) -> Option<usize>
+    {
+        let mut depth = 0;
+        for (index, character) in name.chars().enumerate()
+        {
+            if character == character_to_find && depth == 0 { return Some(index) }
+            if character == '(' { depth += 1 }
+            else if character == ')' { depth -= 1 }
+        }
+
+        return None;
     }
 }
 
@@ -1,8 +1,8 @@
 use std::collections::BTreeSet;
 use box_macro::bx;
 use smol_str::SmolStr;
-use crate::formula::{AtomicFormulaExtras, Formula, FormulaExtras, FuzzyTags, PossibleWorld, PredicateArgument, PredicateArguments, Sign};
-use crate::formula::Formula::{And, Atomic, BiImply, Comment, Conditional, DefinitelyExists, Equals, Exists, ForAll, GreaterOrEqualThan, Imply, InFuture, InPast, LessThan, Necessary, Non, Or, Possible, StrictImply};
+use crate::formula::{AtomicFormulaExtras, Formula, FormulaExtras, FuzzyTags, PossibleWorld, PredicateArgument, PredicateArguments, Program, Sign};
+use crate::formula::Formula::{AgentNecessary, AgentPossible, And, At, Atomic, BiImply, Bind, Comment, CommonKnowledge, Conditional, DefinitelyExists, DistributedKnowledge, Equals, EveryoneKnows, Exists, ForAll, GreaterOrEqualThan, Imply, InFuture, InPast, LessThan, Necessary, Next, Nominal, Non, Or, Possible, ProgramNecessary, ProgramPossible, Since, StrictImply, Until};
 
 mod extras_in_world;
 mod extras_with_sign;
@@ -23,6 +23,8 @@ impl Formula
             BiImply(box p, box q, extras) => { BiImply(bx!(p.in_world(world)), bx!(q.in_world(world)), extras.in_world(world)) }
             StrictImply(box p, box q, extras) => { StrictImply(bx!(p.in_world(world)), bx!(q.in_world(world)), extras.in_world(world)) }
             Conditional(box p, box q, extras) => { Conditional(bx!(p.in_world(world)), bx!(q.in_world(world)), extras.in_world(world)) }
+            Until(box p, box q, extras) => { Until(bx!(p.in_world(world)), bx!(q.in_world(world)), extras.in_world(world)) }
+            Since(box p, box q, extras) => { Since(bx!(p.in_world(world)), bx!(q.in_world(world)), extras.in_world(world)) }
             Exists(x, box p, extras) => { Exists(x.clone(), bx!(p.in_world(world)), extras.in_world(world)) }
             ForAll(x, box p, extras) => { ForAll(x.clone(), bx!(p.in_world(world)), extras.in_world(world)) }
             Equals(x, y, extras) => { Equals(x.clone(), y.clone(), extras.in_world(world)) }
@@ -31,6 +33,17 @@ impl Formula
             Necessary(box p, extras) => { Necessary(bx!(p.in_world(world)), extras.in_world(world)) }
             InPast(box p, extras) => { InPast(bx!(p.in_world(world)), extras.in_world(world)) }
             InFuture(box p, extras) => { InFuture(bx!(p.in_world(world)), extras.in_world(world)) }
+            Next(box p, extras) => { Next(bx!(p.in_world(world)), extras.in_world(world)) }
+            AgentPossible(agent, box p, extras) => { AgentPossible(agent.clone(), bx!(p.in_world(world)), extras.in_world(world)) }
+            AgentNecessary(agent, box p, extras) => { AgentNecessary(agent.clone(), bx!(p.in_world(world)), extras.in_world(world)) }
+            EveryoneKnows(agents, box p, extras) => { EveryoneKnows(agents.clone(), bx!(p.in_world(world)), extras.in_world(world)) }
+            CommonKnowledge(agents, box p, extras) => { CommonKnowledge(agents.clone(), bx!(p.in_world(world)), extras.in_world(world)) }
+            DistributedKnowledge(agents, box p, extras) => { DistributedKnowledge(agents.clone(), bx!(p.in_world(world)), extras.in_world(world)) }
+            Nominal(i, extras) => { Nominal(i.clone(), extras.in_world(world)) }
+            At(i, box p, extras) => { At(i.clone(), bx!(p.in_world(world)), extras.in_world(world)) }
+            Bind(i, box p, extras) => { Bind(i.clone(), bx!(p.in_world(world)), extras.in_world(world)) }
+            ProgramNecessary(program, box p, extras) => { ProgramNecessary(program.clone(), bx!(p.in_world(world)), extras.in_world(world)) }
+            ProgramPossible(program, box p, extras) => { ProgramPossible(program.clone(), bx!(p.in_world(world)), extras.in_world(world)) }
             LessThan(x, y, extras) => { LessThan(x.clone(), y.clone(), extras.in_world(world)) }
             GreaterOrEqualThan(x, y, extras) => { GreaterOrEqualThan(x.clone(), y.clone(), extras.in_world(world)) }
             Comment(payload) => { Comment(payload.clone()) }
@@ -49,6 +62,8 @@ impl Formula
             BiImply(_, _, extras) => { extras.possible_world }
             StrictImply(_, _, extras) => { extras.possible_world }
             Conditional(_, _, extras) => { extras.possible_world }
+            Until(_, _, extras) => { extras.possible_world }
+            Since(_, _, extras) => { extras.possible_world }
             Exists(_, _, extras) => { extras.possible_world }
             ForAll(_, _, extras) => { extras.possible_world }
             Equals(_, _, extras) => { extras.possible_world }
@@ -57,6 +72,17 @@ impl Formula
             Necessary(_, extras) => { extras.possible_world }
             InPast(_, extras) => { extras.possible_world }
             InFuture(_, extras) => { extras.possible_world }
+            Next(_, extras) => { extras.possible_world }
+            AgentPossible(_, _, extras) => { extras.possible_world }
+            AgentNecessary(_, _, extras) => { extras.possible_world }
+            EveryoneKnows(_, _, extras) => { extras.possible_world }
+            CommonKnowledge(_, _, extras) => { extras.possible_world }
+            DistributedKnowledge(_, _, extras) => { extras.possible_world }
+            Nominal(_, extras) => { extras.possible_world }
+            At(_, _, extras) => { extras.possible_world }
+            Bind(_, _, extras) => { extras.possible_world }
+            ProgramNecessary(_, _, extras) => { extras.possible_world }
+            ProgramPossible(_, _, extras) => { extras.possible_world }
             LessThan(_, _, extras) => { extras.possible_world }
             GreaterOrEqualThan(_, _, extras) => { extras.possible_world }
             Comment(_) => { PossibleWorld::zero() }
@@ -75,6 +101,8 @@ impl Formula
             BiImply(p, q, extras) => { BiImply(p.clone(), q.clone(), extras.with_sign(sign)) }
             StrictImply(p, q, extras) => { StrictImply(p.clone(), q.clone(), extras.with_sign(sign)) }
             Conditional(p, q, extras) => { Conditional(p.clone(), q.clone(), extras.with_sign(sign)) }
+            Until(p, q, extras) => { Until(p.clone(), q.clone(), extras.with_sign(sign)) }
+            Since(p, q, extras) => { Since(p.clone(), q.clone(), extras.with_sign(sign)) }
             Exists(x, p, extras) => { Exists(x.clone(), p.clone(), extras.with_sign(sign)) }
             ForAll(x, p, extras) => { ForAll(x.clone(), p.clone(), extras.with_sign(sign)) }
             Equals(x, y, extras) => { Equals(x.clone(), y.clone(), extras.with_sign(sign)) }
@@ -83,6 +111,17 @@ impl Formula
             Necessary(p, extras) => { Necessary(p.clone(), extras.with_sign(sign)) }
             InPast(p, extras) => { InPast(p.clone(), extras.with_sign(sign)) }
             InFuture(p, extras) => { InFuture(p.clone(), extras.with_sign(sign)) }
+            Next(p, extras) => { Next(p.clone(), extras.with_sign(sign)) }
+            AgentPossible(agent, p, extras) => { AgentPossible(agent.clone(), p.clone(), extras.with_sign(sign)) }
+            AgentNecessary(agent, p, extras) => { AgentNecessary(agent.clone(), p.clone(), extras.with_sign(sign)) }
+            EveryoneKnows(agents, p, extras) => { EveryoneKnows(agents.clone(), p.clone(), extras.with_sign(sign)) }
+            CommonKnowledge(agents, p, extras) => { CommonKnowledge(agents.clone(), p.clone(), extras.with_sign(sign)) }
+            DistributedKnowledge(agents, p, extras) => { DistributedKnowledge(agents.clone(), p.clone(), extras.with_sign(sign)) }
+            Nominal(i, extras) => { Nominal(i.clone(), extras.with_sign(sign)) }
+            At(i, p, extras) => { At(i.clone(), p.clone(), extras.with_sign(sign)) }
+            Bind(i, p, extras) => { Bind(i.clone(), p.clone(), extras.with_sign(sign)) }
+            ProgramNecessary(program, p, extras) => { ProgramNecessary(program.clone(), p.clone(), extras.with_sign(sign)) }
+            ProgramPossible(program, p, extras) => { ProgramPossible(program.clone(), p.clone(), extras.with_sign(sign)) }
             LessThan(x, y, extras) => { LessThan(x.clone(), y.clone(), extras.with_sign(sign)) }
             GreaterOrEqualThan(x, y, extras) => { GreaterOrEqualThan(x.clone(), y.clone(), extras.with_sign(sign)) }
             Comment(payload) => { Comment(payload.clone()) }
@@ -101,6 +140,8 @@ impl Formula
             BiImply(_, _, extras) => { extras.sign }
             StrictImply(_, _, extras) => { extras.sign }
             Conditional(_, _, extras) => { extras.sign }
+            Until(_, _, extras) => { extras.sign }
+            Since(_, _, extras) => { extras.sign }
             Exists(_, _, extras) => { extras.sign }
             ForAll(_, _, extras) => { extras.sign }
             Equals(_, _, extras) => { extras.sign }
@@ -109,6 +150,17 @@ impl Formula
             Necessary(_, extras) => { extras.sign }
             InPast(_, extras) => { extras.sign }
             InFuture(_, extras) => { extras.sign }
+            Next(_, extras) => { extras.sign }
+            AgentPossible(_, _, extras) => { extras.sign }
+            AgentNecessary(_, _, extras) => { extras.sign }
+            EveryoneKnows(_, _, extras) => { extras.sign }
+            CommonKnowledge(_, _, extras) => { extras.sign }
+            DistributedKnowledge(_, _, extras) => { extras.sign }
+            Nominal(_, extras) => { extras.sign }
+            At(_, _, extras) => { extras.sign }
+            Bind(_, _, extras) => { extras.sign }
+            ProgramNecessary(_, _, extras) => { extras.sign }
+            ProgramPossible(_, _, extras) => { extras.sign }
             LessThan(_, _, extras) => { extras.sign }
             GreaterOrEqualThan(_, _, extras) => { extras.sign }
             Comment(_) => { Sign::Plus }
@@ -127,6 +179,8 @@ impl Formula
             BiImply(p, q, extras) => { BiImply(p.clone(), q.clone(), extras.with_fuzzy_tags(tags)) }
             StrictImply(p, q, extras) => { StrictImply(p.clone(), q.clone(), extras.with_fuzzy_tags(tags)) }
             Conditional(p, q, extras) => { Conditional(p.clone(), q.clone(), extras.with_fuzzy_tags(tags)) }
+            Until(p, q, extras) => { Until(p.clone(), q.clone(), extras.with_fuzzy_tags(tags)) }
+            Since(p, q, extras) => { Since(p.clone(), q.clone(), extras.with_fuzzy_tags(tags)) }
             Exists(x, p, extras) => { Exists(x.clone(), p.clone(), extras.with_fuzzy_tags(tags)) }
             ForAll(x, p, extras) => { ForAll(x.clone(), p.clone(), extras.with_fuzzy_tags(tags)) }
             Equals(x, y, extras) => { Equals(x.clone(), y.clone(), extras.with_fuzzy_tags(tags)) }
@@ -135,6 +189,17 @@ impl Formula
             Necessary(p, extras) => { Necessary(p.clone(), extras.with_fuzzy_tags(tags)) }
             InPast(p, extras) => { InPast(p.clone(), extras.with_fuzzy_tags(tags)) }
             InFuture(p, extras) => { InFuture(p.clone(), extras.with_fuzzy_tags(tags)) }
+            Next(p, extras) => { Next(p.clone(), extras.with_fuzzy_tags(tags)) }
+            AgentPossible(agent, p, extras) => { AgentPossible(agent.clone(), p.clone(), extras.with_fuzzy_tags(tags)) }
+            AgentNecessary(agent, p, extras) => { AgentNecessary(agent.clone(), p.clone(), extras.with_fuzzy_tags(tags)) }
+            EveryoneKnows(agents, p, extras) => { EveryoneKnows(agents.clone(), p.clone(), extras.with_fuzzy_tags(tags)) }
+            CommonKnowledge(agents, p, extras) => { CommonKnowledge(agents.clone(), p.clone(), extras.with_fuzzy_tags(tags)) }
+            DistributedKnowledge(agents, p, extras) => { DistributedKnowledge(agents.clone(), p.clone(), extras.with_fuzzy_tags(tags)) }
+            Nominal(i, extras) => { Nominal(i.clone(), extras.with_fuzzy_tags(tags)) }
+            At(i, p, extras) => { At(i.clone(), p.clone(), extras.with_fuzzy_tags(tags)) }
+            Bind(i, p, extras) => { Bind(i.clone(), p.clone(), extras.with_fuzzy_tags(tags)) }
+            ProgramNecessary(program, p, extras) => { ProgramNecessary(program.clone(), p.clone(), extras.with_fuzzy_tags(tags)) }
+            ProgramPossible(program, p, extras) => { ProgramPossible(program.clone(), p.clone(), extras.with_fuzzy_tags(tags)) }
             LessThan(x, y, extras) => { LessThan(x.clone(), y.clone(), extras.with_fuzzy_tags(tags)) }
             GreaterOrEqualThan(x, y, extras) => { GreaterOrEqualThan(x.clone(), y.clone(), extras.with_fuzzy_tags(tags)) }
             Comment(payload) => { Comment(payload.clone()) }
@@ -153,6 +218,8 @@ impl Formula
             BiImply(_, _, extras) => { extras.fuzzy_tags.clone() }
             StrictImply(_, _, extras) => { extras.fuzzy_tags.clone() }
             Conditional(_, _, extras) => { extras.fuzzy_tags.clone() }
+            Until(_, _, extras) => { extras.fuzzy_tags.clone() }
+            Since(_, _, extras) => { extras.fuzzy_tags.clone() }
             Exists(_, _, extras) => { extras.fuzzy_tags.clone() }
             ForAll(_, _, extras) => { extras.fuzzy_tags.clone() }
             Equals(_, _, extras) => { extras.fuzzy_tags.clone() }
@@ -161,6 +228,17 @@ impl Formula
             Necessary(_, extras) => { extras.fuzzy_tags.clone() }
             InPast(_, extras) => { extras.fuzzy_tags.clone() }
             InFuture(_, extras) => { extras.fuzzy_tags.clone() }
+            Next(_, extras) => { extras.fuzzy_tags.clone() }
+            AgentPossible(_, _, extras) => { extras.fuzzy_tags.clone() }
+            AgentNecessary(_, _, extras) => { extras.fuzzy_tags.clone() }
+            EveryoneKnows(_, _, extras) => { extras.fuzzy_tags.clone() }
+            CommonKnowledge(_, _, extras) => { extras.fuzzy_tags.clone() }
+            DistributedKnowledge(_, _, extras) => { extras.fuzzy_tags.clone() }
+            Nominal(_, extras) => { extras.fuzzy_tags.clone() }
+            At(_, _, extras) => { extras.fuzzy_tags.clone() }
+            Bind(_, _, extras) => { extras.fuzzy_tags.clone() }
+            ProgramNecessary(_, _, extras) => { extras.fuzzy_tags.clone() }
+            ProgramPossible(_, _, extras) => { extras.fuzzy_tags.clone() }
             LessThan(_, _, extras) => { extras.fuzzy_tags.clone() }
             GreaterOrEqualThan(_, _, extras) => { extras.fuzzy_tags.clone() }
             Comment(_) => { FuzzyTags::empty() }
@@ -179,6 +257,8 @@ impl Formula
             BiImply(p, q, extras) => { BiImply(p.clone(), q.clone(), extras.with_is_hidden(is_hidden)) }
             StrictImply(p, q, extras) => { StrictImply(p.clone(), q.clone(), extras.with_is_hidden(is_hidden)) }
             Conditional(p, q, extras) => { Conditional(p.clone(), q.clone(), extras.with_is_hidden(is_hidden)) }
+            Until(p, q, extras) => { Until(p.clone(), q.clone(), extras.with_is_hidden(is_hidden)) }
+            Since(p, q, extras) => { Since(p.clone(), q.clone(), extras.with_is_hidden(is_hidden)) }
             Exists(x, p, extras) => { Exists(x.clone(), p.clone(), extras.with_is_hidden(is_hidden)) }
             ForAll(x, p, extras) => { ForAll(x.clone(), p.clone(), extras.with_is_hidden(is_hidden)) }
             Equals(x, y, extras) => { Equals(x.clone(), y.clone(), extras.with_is_hidden(is_hidden)) }
@@ -187,6 +267,17 @@ impl Formula
             Necessary(p, extras) => { Necessary(p.clone(), extras.with_is_hidden(is_hidden)) }
             InPast(p, extras) => { InPast(p.clone(), extras.with_is_hidden(is_hidden)) }
             InFuture(p, extras) => { InFuture(p.clone(), extras.with_is_hidden(is_hidden)) }
+            Next(p, extras) => { Next(p.clone(), extras.with_is_hidden(is_hidden)) }
+            AgentPossible(agent, p, extras) => { AgentPossible(agent.clone(), p.clone(), extras.with_is_hidden(is_hidden)) }
+            AgentNecessary(agent, p, extras) => { AgentNecessary(agent.clone(), p.clone(), extras.with_is_hidden(is_hidden)) }
+            EveryoneKnows(agents, p, extras) => { EveryoneKnows(agents.clone(), p.clone(), extras.with_is_hidden(is_hidden)) }
+            CommonKnowledge(agents, p, extras) => { CommonKnowledge(agents.clone(), p.clone(), extras.with_is_hidden(is_hidden)) }
+            DistributedKnowledge(agents, p, extras) => { DistributedKnowledge(agents.clone(), p.clone(), extras.with_is_hidden(is_hidden)) }
+            Nominal(i, extras) => { Nominal(i.clone(), extras.with_is_hidden(is_hidden)) }
+            At(i, p, extras) => { At(i.clone(), p.clone(), extras.with_is_hidden(is_hidden)) }
+            Bind(i, p, extras) => { Bind(i.clone(), p.clone(), extras.with_is_hidden(is_hidden)) }
+            ProgramNecessary(program, p, extras) => { ProgramNecessary(program.clone(), p.clone(), extras.with_is_hidden(is_hidden)) }
+            ProgramPossible(program, p, extras) => { ProgramPossible(program.clone(), p.clone(), extras.with_is_hidden(is_hidden)) }
             LessThan(x, y, extras) => { LessThan(x.clone(), y.clone(), extras.with_is_hidden(is_hidden)) }
             GreaterOrEqualThan(x, y, extras) => { GreaterOrEqualThan(x.clone(), y.clone(), extras.with_is_hidden(is_hidden)) }
             Comment(payload) => { Comment(payload.clone()) }
@@ -205,6 +296,8 @@ impl Formula
             BiImply(_, _, extras) => { extras.is_hidden }
             StrictImply(_, _, extras) => { extras.is_hidden }
             Conditional(_, _, extras) => { extras.is_hidden }
+            Until(_, _, extras) => { extras.is_hidden }
+            Since(_, _, extras) => { extras.is_hidden }
             Exists(_, _, extras) => { extras.is_hidden }
             ForAll(_, _, extras) => { extras.is_hidden }
             Equals(_, _, extras) => { extras.is_hidden }
@@ -213,6 +306,17 @@ impl Formula
             Necessary(_, extras) => { extras.is_hidden }
             InPast(_, extras) => { extras.is_hidden }
             InFuture(_, extras) => { extras.is_hidden }
+            Next(_, extras) => { extras.is_hidden }
+            AgentPossible(_, _, extras) => { extras.is_hidden }
+            AgentNecessary(_, _, extras) => { extras.is_hidden }
+            EveryoneKnows(_, _, extras) => { extras.is_hidden }
+            CommonKnowledge(_, _, extras) => { extras.is_hidden }
+            DistributedKnowledge(_, _, extras) => { extras.is_hidden }
+            Nominal(_, extras) => { extras.is_hidden }
+            At(_, _, extras) => { extras.is_hidden }
+            Bind(_, _, extras) => { extras.is_hidden }
+            ProgramNecessary(_, _, extras) => { extras.is_hidden }
+            ProgramPossible(_, _, extras) => { extras.is_hidden }
             LessThan(_, _, extras) => { extras.is_hidden }
             GreaterOrEqualThan(_, _, extras) => { extras.is_hidden }
             Comment(_) => { false }
@@ -242,6 +346,8 @@ impl Formula
             BiImply(box p, box q, _) => { BiImply(bx!(p.with_stripped_extras()), bx!(q.with_stripped_extras()), FormulaExtras::empty()) }
             StrictImply(box p, box q, _) => { StrictImply(bx!(p.with_stripped_extras()), bx!(q.with_stripped_extras()), FormulaExtras::empty()) }
             Conditional(box p, box q, _) => { Conditional(bx!(p.with_stripped_extras()), bx!(q.with_stripped_extras()), FormulaExtras::empty()) }
+            Until(box p, box q, _) => { Until(bx!(p.with_stripped_extras()), bx!(q.with_stripped_extras()), FormulaExtras::empty()) }
+            Since(box p, box q, _) => { Since(bx!(p.with_stripped_extras()), bx!(q.with_stripped_extras()), FormulaExtras::empty()) }
             Exists(x, box p, _) => { Exists(x.clone(), bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
             ForAll(x, box p, _) => { ForAll(x.clone(), bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
             Equals(x, y, _) => { Equals(x.clone(), y.clone(), FormulaExtras::empty()) }
@@ -250,6 +356,17 @@ impl Formula
             Necessary(box p, _) => { Necessary(bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
             InPast(box p, _) => { InPast(bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
             InFuture(box p, _) => { InFuture(bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
+            Next(box p, _) => { Next(bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
+            AgentPossible(agent, box p, _) => { AgentPossible(agent.clone(), bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
+            AgentNecessary(agent, box p, _) => { AgentNecessary(agent.clone(), bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
+            EveryoneKnows(agents, box p, _) => { EveryoneKnows(agents.clone(), bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
+            CommonKnowledge(agents, box p, _) => { CommonKnowledge(agents.clone(), bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
+            DistributedKnowledge(agents, box p, _) => { DistributedKnowledge(agents.clone(), bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
+            Nominal(i, _) => { Nominal(i.clone(), FormulaExtras::empty()) }
+            At(i, box p, _) => { At(i.clone(), bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
+            Bind(i, box p, _) => { Bind(i.clone(), bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
+            ProgramNecessary(program, box p, _) => { ProgramNecessary(program.clone(), bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
+            ProgramPossible(program, box p, _) => { ProgramPossible(program.clone(), bx!(p.with_stripped_extras()), FormulaExtras::empty()) }
             LessThan(x, y, _) => { LessThan(x.clone(), y.clone(), FormulaExtras::empty()) }
             GreaterOrEqualThan(x, y, _) => { GreaterOrEqualThan(x.clone(), y.clone(), FormulaExtras::empty()) }
             Comment(payload) => { Comment(payload.clone()) }
@@ -271,6 +388,8 @@ impl Formula
             BiImply(box p, box q, _) => { get_predicate_arguments_of_atomic_from_tuple((p, q)) }
             StrictImply(box p, box q, _) => { get_predicate_arguments_of_atomic_from_tuple((p, q)) }
             Conditional(box p, box q, _) => { get_predicate_arguments_of_atomic_from_tuple((p, q)) }
+            Until(box p, box q, _) => { get_predicate_arguments_of_atomic_from_tuple((p, q)) }
+            Since(box p, box q, _) => { get_predicate_arguments_of_atomic_from_tuple((p, q)) }
             Exists(_, box p, _) => { p.get_predicate_arguments_of_atomic() }
             ForAll(_, box p, _) => { p.get_predicate_arguments_of_atomic() }
             Equals(_, _, _) => { None }
@@ -279,6 +398,17 @@ impl Formula
             Necessary(box p, _) => { p.get_predicate_arguments_of_atomic() }
             InPast(box p, _) => { p.get_predicate_arguments_of_atomic() }
             InFuture(box p, _) => { p.get_predicate_arguments_of_atomic() }
+            Next(box p, _) => { p.get_predicate_arguments_of_atomic() }
+            AgentPossible(_, box p, _) => { p.get_predicate_arguments_of_atomic() }
+            AgentNecessary(_, box p, _) => { p.get_predicate_arguments_of_atomic() }
+            EveryoneKnows(_, box p, _) => { p.get_predicate_arguments_of_atomic() }
+            CommonKnowledge(_, box p, _) => { p.get_predicate_arguments_of_atomic() }
+            DistributedKnowledge(_, box p, _) => { p.get_predicate_arguments_of_atomic() }
+            Nominal(_, _) => { None }
+            At(_, box p, _) => { p.get_predicate_arguments_of_atomic() }
+            Bind(_, box p, _) => { p.get_predicate_arguments_of_atomic() }
+            ProgramNecessary(_, box p, _) => { p.get_predicate_arguments_of_atomic() }
+            ProgramPossible(_, box p, _) => { p.get_predicate_arguments_of_atomic() }
             LessThan(_, _, _) => { None }
             GreaterOrEqualThan(_, _, _) => { None }
             Comment(_) => { None }
@@ -306,7 +436,7 @@ impl Formula
             {
                 for predicate_arg in extras.predicate_args.iter()
                 {
-                    output.insert(predicate_arg.clone());
+                    predicate_arg.insert_with_function_term_args(output);
                 }
             }
 
@@ -322,8 +452,8 @@ impl Formula
             }
             Equals(x, y, _) =>
             {
-                output.insert(x.clone());
-                output.insert(y.clone());
+                x.insert_with_function_term_args(output);
+                y.insert_with_function_term_args(output);
             }
             DefinitelyExists(x, _) =>
             {
@@ -337,10 +467,22 @@ impl Formula
             BiImply(box p, box q, _) => { get_all_predicate_arguments_recursively_from_tuple((p, q)) }
             StrictImply(box p, box q, _) => { get_all_predicate_arguments_recursively_from_tuple((p, q)) }
             Conditional(box p, box q, _) => { get_all_predicate_arguments_recursively_from_tuple((p, q)) }
+            Until(box p, box q, _) => { get_all_predicate_arguments_recursively_from_tuple((p, q)) }
+            Since(box p, box q, _) => { get_all_predicate_arguments_recursively_from_tuple((p, q)) }
             Possible(box p, _) => { p.get_all_predicate_arguments_recursively(output) }
             Necessary(box p, _) => { p.get_all_predicate_arguments_recursively(output) }
             InPast(box p, _) => { p.get_all_predicate_arguments_recursively(output) }
             InFuture(box p, _) => { p.get_all_predicate_arguments_recursively(output) }
+            Next(box p, _) => { p.get_all_predicate_arguments_recursively(output) }
+            AgentPossible(_, box p, _) => { p.get_all_predicate_arguments_recursively(output) }
+            AgentNecessary(_, box p, _) => { p.get_all_predicate_arguments_recursively(output) }
+            EveryoneKnows(_, box p, _) => { p.get_all_predicate_arguments_recursively(output) }
+            CommonKnowledge(_, box p, _) => { p.get_all_predicate_arguments_recursively(output) }
+            DistributedKnowledge(_, box p, _) => { p.get_all_predicate_arguments_recursively(output) }
+            At(_, box p, _) => { p.get_all_predicate_arguments_recursively(output) }
+            Bind(_, box p, _) => { p.get_all_predicate_arguments_recursively(output) }
+            ProgramNecessary(_, box p, _) => { p.get_all_predicate_arguments_recursively(output) }
+            ProgramPossible(_, box p, _) => { p.get_all_predicate_arguments_recursively(output) }
 
             _ => {}
         }
@@ -366,6 +508,16 @@ impl Formula
             Necessary(box p, _) => { p.contains_quantifier_with_argument(y) }
             InPast(box p, _) => { p.contains_quantifier_with_argument(y) }
             InFuture(box p, _) => { p.contains_quantifier_with_argument(y) }
+            Next(box p, _) => { p.contains_quantifier_with_argument(y) }
+            AgentPossible(_, box p, _) => { p.contains_quantifier_with_argument(y) }
+            AgentNecessary(_, box p, _) => { p.contains_quantifier_with_argument(y) }
+            EveryoneKnows(_, box p, _) => { p.contains_quantifier_with_argument(y) }
+            CommonKnowledge(_, box p, _) => { p.contains_quantifier_with_argument(y) }
+            DistributedKnowledge(_, box p, _) => { p.contains_quantifier_with_argument(y) }
+            At(_, box p, _) => { p.contains_quantifier_with_argument(y) }
+            Bind(_, box p, _) => { p.contains_quantifier_with_argument(y) }
+            ProgramNecessary(_, box p, _) => { p.contains_quantifier_with_argument(y) }
+            ProgramPossible(_, box p, _) => { p.contains_quantifier_with_argument(y) }
 
             And(box p, box q, _) => { p.contains_quantifier_with_argument(y) || q.contains_quantifier_with_argument(y) }
             Or(box p, box q, _) => { p.contains_quantifier_with_argument(y) || q.contains_quantifier_with_argument(y) }
@@ -373,6 +525,8 @@ impl Formula
             BiImply(box p, box q, _) => { p.contains_quantifier_with_argument(y) || q.contains_quantifier_with_argument(y) }
             StrictImply(box p, box q, _) => { p.contains_quantifier_with_argument(y) || q.contains_quantifier_with_argument(y) }
             Conditional(box p, box q, _) => { p.contains_quantifier_with_argument(y) || q.contains_quantifier_with_argument(y) }
+            Until(box p, box q, _) => { p.contains_quantifier_with_argument(y) || q.contains_quantifier_with_argument(y) }
+            Since(box p, box q, _) => { p.contains_quantifier_with_argument(y) || q.contains_quantifier_with_argument(y) }
 
             _ => { false }
         }
@@ -390,6 +544,8 @@ impl Formula
             BiImply(_, _, extras) => { extras.clone() }
             StrictImply(_, _, extras) => { extras.clone() }
             Conditional(_, _, extras) => { extras.clone() }
+            Until(_, _, extras) => { extras.clone() }
+            Since(_, _, extras) => { extras.clone() }
             Exists(_, _, extras) => { extras.clone() }
             ForAll(_, _, extras) => { extras.clone() }
             Equals(_, _, extras) => { extras.clone() }
@@ -398,6 +554,17 @@ impl Formula
             Necessary(_, extras) => { extras.clone() }
             InPast(_, extras) => { extras.clone() }
             InFuture(_, extras) => { extras.clone() }
+            Next(_, extras) => { extras.clone() }
+            AgentPossible(_, _, extras) => { extras.clone() }
+            AgentNecessary(_, _, extras) => { extras.clone() }
+            EveryoneKnows(_, _, extras) => { extras.clone() }
+            CommonKnowledge(_, _, extras) => { extras.clone() }
+            DistributedKnowledge(_, _, extras) => { extras.clone() }
+            Nominal(_, extras) => { extras.clone() }
+            At(_, _, extras) => { extras.clone() }
+            Bind(_, _, extras) => { extras.clone() }
+            ProgramNecessary(_, _, extras) => { extras.clone() }
+            ProgramPossible(_, _, extras) => { extras.clone() }
             LessThan(_, _, extras) => { extras.clone() }
             GreaterOrEqualThan(_, _, extras) => { extras.clone() }
             Comment(_) => { FormulaExtras::empty() }
@@ -429,12 +596,28 @@ impl Formula
             BiImply(box p, box q, _) => { get_all_atomic_names_recursively_from_tuple((p, q)); }
             StrictImply(box p, box q, _) => { get_all_atomic_names_recursively_from_tuple((p, q)); }
             Conditional(box p, box q, _) => { get_all_atomic_names_recursively_from_tuple((p, q)); }
+            Until(box p, box q, _) => { get_all_atomic_names_recursively_from_tuple((p, q)); }
+            Since(box p, box q, _) => { get_all_atomic_names_recursively_from_tuple((p, q)); }
             Exists(_, box p, _) => { p.get_all_atomic_names_recursively(output); }
             ForAll(_, box p, _) => { p.get_all_atomic_names_recursively(output); }
             Possible(box p, _) => { p.get_all_atomic_names_recursively(output); }
             Necessary(box p, _) => { p.get_all_atomic_names_recursively(output); }
             InPast(box p, _) => { p.get_all_atomic_names_recursively(output); }
             InFuture(box p, _) => { p.get_all_atomic_names_recursively(output); }
+            Next(box p, _) => { p.get_all_atomic_names_recursively(output); }
+            AgentPossible(_, box p, _) => { p.get_all_atomic_names_recursively(output); }
+            AgentNecessary(_, box p, _) => { p.get_all_atomic_names_recursively(output); }
+            EveryoneKnows(_, box p, _) => { p.get_all_atomic_names_recursively(output); }
+            CommonKnowledge(_, box p, _) => { p.get_all_atomic_names_recursively(output); }
+            DistributedKnowledge(_, box p, _) => { p.get_all_atomic_names_recursively(output); }
+            Nominal(_, _) => {}
+            At(_, box p, _) => { p.get_all_atomic_names_recursively(output); }
+            Bind(_, box p, _) => { p.get_all_atomic_names_recursively(output); }
+            ProgramNecessary(program, box p, _) | ProgramPossible(program, box p, _) =>
+            {
+                program.get_test_formulas().into_iter().for_each(|test| test.get_all_atomic_names_recursively(output));
+                p.get_all_atomic_names_recursively(output);
+            }
             DefinitelyExists(_, _) => {}
             Equals(_, _, _) => {}
             LessThan(_, _, _) => {}
@@ -443,6 +626,60 @@ impl Formula
         }
     }
 
+    pub fn get_all_nominal_names(&self) -> BTreeSet<SmolStr>
+    {
+        let mut output : BTreeSet<SmolStr> = BTreeSet::new();
+        self.get_all_nominal_names_recursively(&mut output);
+        return output;
+    }
+
+    fn get_all_nominal_names_recursively(&self, output : &mut BTreeSet<SmolStr>)
+    {
+        match self
+        {
+            Nominal(name, _) => { output.insert(name.clone()); }
+            At(name, box p, _) => { output.insert(name.clone()); p.get_all_nominal_names_recursively(output); }
+            //the nominals bound by ↓ are not free
+            Bind(name, box p, _) =>
+            {
+                let mut bound_output : BTreeSet<SmolStr> = BTreeSet::new();
+                p.get_all_nominal_names_recursively(&mut bound_output);
+                bound_output.remove(name);
+                output.append(&mut bound_output);
+            }
+            Non(box p, _) | Possible(box p, _) | Necessary(box p, _) => { p.get_all_nominal_names_recursively(output); }
+            And(box p, box q, _) | Or(box p, box q, _) | Imply(box p, box q, _) | BiImply(box p, box q, _) | StrictImply(box p, box q, _) =>
+            {
+                p.get_all_nominal_names_recursively(output);
+                q.get_all_nominal_names_recursively(output);
+            }
+            _ => {}
+        }
+    }
+
+    //replaces the nominal i with the nominal j, except under ↓_i, which binds i again
+    pub fn with_renamed_nominal(&self, i : &SmolStr, j : &SmolStr) -> Formula
+    {
+        let rename = |p : &Formula| bx!(p.with_renamed_nominal(i, j));
+
+        return match self
+        {
+            Nominal(name, extras) => { Nominal(if name == i { j.clone() } else { name.clone() }, extras.clone()) }
+            At(name, box p, extras) => { At(if name == i { j.clone() } else { name.clone() }, rename(p), extras.clone()) }
+            Bind(name, _, _) if name == i => { self.clone() }
+            Bind(name, box p, extras) => { Bind(name.clone(), rename(p), extras.clone()) }
+            Non(box p, extras) => { Non(rename(p), extras.clone()) }
+            Possible(box p, extras) => { Possible(rename(p), extras.clone()) }
+            Necessary(box p, extras) => { Necessary(rename(p), extras.clone()) }
+            And(box p, box q, extras) => { And(rename(p), rename(q), extras.clone()) }
+            Or(box p, box q, extras) => { Or(rename(p), rename(q), extras.clone()) }
+            Imply(box p, box q, extras) => { Imply(rename(p), rename(q), extras.clone()) }
+            BiImply(box p, box q, extras) => { BiImply(rename(p), rename(q), extras.clone()) }
+            StrictImply(box p, box q, extras) => { StrictImply(rename(p), rename(q), extras.clone()) }
+            _ => { self.clone() }
+        }
+    }
+
     pub fn count_number_of_operators(&self) -> usize
     {
         return match self
@@ -455,6 +692,8 @@ impl Formula
             BiImply(box p, box q, _) => { 1 + p.count_number_of_operators() + q.count_number_of_operators() }
             StrictImply(box p, box q, _) => { 1 + p.count_number_of_operators() + q.count_number_of_operators() }
             Conditional(box p, box q, _) => { 1 + p.count_number_of_operators() + q.count_number_of_operators() }
+            Until(box p, box q, _) => { 1 + p.count_number_of_operators() + q.count_number_of_operators() }
+            Since(box p, box q, _) => { 1 + p.count_number_of_operators() + q.count_number_of_operators() }
             Exists(_, box p, _) => { 1 + p.count_number_of_operators() }
             ForAll(_, box p, _) => { 1 + p.count_number_of_operators() }
             Equals(_, _, _) => { 0 }
@@ -465,11 +704,41 @@ impl Formula
             Necessary(box p, _) => { 1 + p.count_number_of_operators() }
             InPast(box p, _) => { 1 + p.count_number_of_operators() }
             InFuture(box p, _) => { 1 + p.count_number_of_operators() }
+            Next(box p, _) => { 1 + p.count_number_of_operators() }
+            AgentPossible(_, box p, _) => { 1 + p.count_number_of_operators() }
+            AgentNecessary(_, box p, _) => { 1 + p.count_number_of_operators() }
+            EveryoneKnows(_, box p, _) => { 1 + p.count_number_of_operators() }
+            CommonKnowledge(_, box p, _) => { 1 + p.count_number_of_operators() }
+            DistributedKnowledge(_, box p, _) => { 1 + p.count_number_of_operators() }
+            Nominal(_, _) => { 0 }
+            At(_, box p, _) => { 1 + p.count_number_of_operators() }
+            Bind(_, box p, _) => { 1 + p.count_number_of_operators() }
+            ProgramNecessary(_, box p, _) => { 1 + p.count_number_of_operators() }
+            ProgramPossible(_, box p, _) => { 1 + p.count_number_of_operators() }
             Comment(_) => { 0 }
         }
     }
 }
 
+impl Program
+{
+    pub fn get_test_formulas(&self) -> Vec<&Formula>
+    {
+        return match self
+        {
+            Program::AtomicProgram(_) => { vec![] }
+            Program::Sequence(box a, box b) | Program::Choice(box a, box b) =>
+            {
+                let mut output = a.get_test_formulas();
+                output.append(&mut b.get_test_formulas());
+                output
+            }
+            Program::Test(box p) => { vec![p] }
+            Program::Iteration(box a) => { a.get_test_formulas() }
+        }
+    }
+}
+
 impl AtomicFormulaExtras
 {
     pub fn to_formula_extras(&self) -> FormulaExtras
@@ -482,4 +751,21 @@ impl AtomicFormulaExtras
             is_hidden: self.is_hidden,
         }
     }
+}
+
+impl PredicateArgument
+{
+    //the argument itself and, for function terms, all its subterms, eg: f(g(a),b), g(a), a, b
+    fn insert_with_function_term_args(&self, output : &mut BTreeSet<PredicateArgument>)
+    {
+        output.insert(self.clone());
+
+        if let Some(function_term) = &self.function_term
+        {
+            for arg in &function_term.args
+            {
+                arg.insert_with_function_term_args(output);
+            }
+        }
+    }
 }
\ No newline at end of file
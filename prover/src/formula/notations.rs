@@ -29,6 +29,26 @@ impl OperatorNotations
                     TokenTypeID::Conditional => { 'ᐅ' }
                     TokenTypeID::Possible => { '◇' }
                     TokenTypeID::Necessary => { '□' }
+                    TokenTypeID::AgentPossible => { '◇' }
+                    TokenTypeID::AgentNecessary => { '□' }
+                    TokenTypeID::EveryoneKnows => { 'E' }
+                    TokenTypeID::CommonKnowledge => { 'C' }
+                    TokenTypeID::DistributedKnowledge => { 'D' }
+                    TokenTypeID::Obligatory => { 'O' }
+                    TokenTypeID::Permitted => { 'P' }
+                    TokenTypeID::ConceptIntersection => { '⊓' }
+                    TokenTypeID::ConceptUnion => { '⊔' }
+                    TokenTypeID::RoleExists => { '∃' }
+                    TokenTypeID::RoleForAll => { '∀' }
+                    TokenTypeID::Subsumption => { '⊑' }
+                    TokenTypeID::Next => { 'X' }
+                    TokenTypeID::Until => { 'U' }
+                    TokenTypeID::Since => { 'S' }
+                    TokenTypeID::At => { '@' }
+                    TokenTypeID::Bind => { '↓' }
+                    TokenTypeID::Nominal => { ' ' }
+                    TokenTypeID::ProgramNecessary => { '[' }
+                    TokenTypeID::ProgramPossible => { '⟨' }
                     TokenTypeID::InPast => { 'ᵖ' }
                     TokenTypeID::InFuture => { 'ᶠ' }
                     TokenTypeID::Exists => { '∃' }
@@ -55,6 +75,26 @@ impl OperatorNotations
                     TokenTypeID::Conditional => { 'ᐅ' }
                     TokenTypeID::Possible => { '◇' }
                     TokenTypeID::Necessary => { '□' }
+                    TokenTypeID::AgentPossible => { '◇' }
+                    TokenTypeID::AgentNecessary => { '□' }
+                    TokenTypeID::EveryoneKnows => { 'E' }
+                    TokenTypeID::CommonKnowledge => { 'C' }
+                    TokenTypeID::DistributedKnowledge => { 'D' }
+                    TokenTypeID::Obligatory => { 'O' }
+                    TokenTypeID::Permitted => { 'P' }
+                    TokenTypeID::ConceptIntersection => { '⊓' }
+                    TokenTypeID::ConceptUnion => { '⊔' }
+                    TokenTypeID::RoleExists => { '∃' }
+                    TokenTypeID::RoleForAll => { '∀' }
+                    TokenTypeID::Subsumption => { '⊑' }
+                    TokenTypeID::Next => { 'X' }
+                    TokenTypeID::Until => { 'U' }
+                    TokenTypeID::Since => { 'S' }
+                    TokenTypeID::At => { '@' }
+                    TokenTypeID::Bind => { '↓' }
+                    TokenTypeID::Nominal => { ' ' }
+                    TokenTypeID::ProgramNecessary => { '[' }
+                    TokenTypeID::ProgramPossible => { '⟨' }
                     TokenTypeID::InPast => { 'ᵖ' }
                     TokenTypeID::InFuture => { 'ᶠ' }
                     TokenTypeID::Exists => { '∃' }
@@ -81,6 +121,26 @@ impl OperatorNotations
                     TokenTypeID::Conditional => { 'ᐅ' }
                     TokenTypeID::Possible => { '◇' }
                     TokenTypeID::Necessary => { '□' }
+                    TokenTypeID::AgentPossible => { '◇' }
+                    TokenTypeID::AgentNecessary => { '□' }
+                    TokenTypeID::EveryoneKnows => { 'E' }
+                    TokenTypeID::CommonKnowledge => { 'C' }
+                    TokenTypeID::DistributedKnowledge => { 'D' }
+                    TokenTypeID::Obligatory => { 'O' }
+                    TokenTypeID::Permitted => { 'P' }
+                    TokenTypeID::ConceptIntersection => { '⊓' }
+                    TokenTypeID::ConceptUnion => { '⊔' }
+                    TokenTypeID::RoleExists => { '∃' }
+                    TokenTypeID::RoleForAll => { '∀' }
+                    TokenTypeID::Subsumption => { '⊑' }
+                    TokenTypeID::Next => { 'X' }
+                    TokenTypeID::Until => { 'U' }
+                    TokenTypeID::Since => { 'S' }
+                    TokenTypeID::At => { '@' }
+                    TokenTypeID::Bind => { '↓' }
+                    TokenTypeID::Nominal => { ' ' }
+                    TokenTypeID::ProgramNecessary => { '[' }
+                    TokenTypeID::ProgramPossible => { '⟨' }
                     TokenTypeID::InPast => { 'ᵖ' }
                     TokenTypeID::InFuture => { 'ᶠ' }
                     TokenTypeID::Exists => { '∃' }
@@ -107,6 +167,26 @@ impl OperatorNotations
                     TokenTypeID::Conditional => { 'ᐅ' }
                     TokenTypeID::Possible => { '◇' }
                     TokenTypeID::Necessary => { '□' }
+                    TokenTypeID::AgentPossible => { '◇' }
+                    TokenTypeID::AgentNecessary => { '□' }
+                    TokenTypeID::EveryoneKnows => { 'E' }
+                    TokenTypeID::CommonKnowledge => { 'C' }
+                    TokenTypeID::DistributedKnowledge => { 'D' }
+                    TokenTypeID::Obligatory => { 'O' }
+                    TokenTypeID::Permitted => { 'P' }
+                    TokenTypeID::ConceptIntersection => { '⊓' }
+                    TokenTypeID::ConceptUnion => { '⊔' }
+                    TokenTypeID::RoleExists => { '∃' }
+                    TokenTypeID::RoleForAll => { '∀' }
+                    TokenTypeID::Subsumption => { '⊑' }
+                    TokenTypeID::Next => { 'X' }
+                    TokenTypeID::Until => { 'U' }
+                    TokenTypeID::Since => { 'S' }
+                    TokenTypeID::At => { '@' }
+                    TokenTypeID::Bind => { '↓' }
+                    TokenTypeID::Nominal => { ' ' }
+                    TokenTypeID::ProgramNecessary => { '[' }
+                    TokenTypeID::ProgramPossible => { '⟨' }
                     TokenTypeID::InPast => { 'ᵖ' }
                     TokenTypeID::InFuture => { 'ᶠ' }
                     TokenTypeID::Exists => { '∃' }
@@ -2,8 +2,8 @@ use std::cell::RefCell;
 use std::fmt::{Debug, Display, Formatter};
 use std::rc::Rc;
 use itertools::Itertools;
-use crate::formula::{Formula, FuzzyTag, FuzzyTags, PossibleWorld, PredicateArgument, PredicateArguments, Sign};
-use crate::formula::Formula::{And, Atomic, BiImply, Comment, Conditional, DefinitelyExists, Equals, Exists, ForAll, GreaterOrEqualThan, Imply, InFuture, InPast, LessThan, Necessary, Non, Or, Possible, StrictImply};
+use crate::formula::{Formula, FunctionTerm, FuzzyTag, FuzzyTags, PossibleWorld, PredicateArgument, PredicateArguments, Program, Sign};
+use crate::formula::Formula::{AgentNecessary, AgentPossible, And, At, Atomic, BiImply, Bind, Comment, CommonKnowledge, Conditional, DefinitelyExists, DistributedKnowledge, Equals, EveryoneKnows, Exists, ForAll, GreaterOrEqualThan, Imply, InFuture, InPast, LessThan, Necessary, Next, Nominal, Non, Or, Possible, ProgramNecessary, ProgramPossible, Since, StrictImply, Until};
 use crate::formula::notations::OperatorNotations;
 use crate::logic::Logic;
 use crate::parser::token_types::TokenTypeID;
@@ -32,6 +32,9 @@ pub struct FormulaFormatOptions
     should_show_possible_worlds : bool,
     should_show_sign : bool,
     should_show_fuzzy_tags : bool,
+    should_show_truth_values : bool,
+    pub should_show_deontic_operators : bool,
+    pub should_show_description_logic_operators : bool,
 }
 
 impl FormulaFormatOptions
@@ -52,6 +55,9 @@ impl FormulaFormatOptions
                 should_show_possible_worlds: false,
                 should_show_sign: false,
                 should_show_fuzzy_tags: false,
+                should_show_truth_values: false,
+                should_show_deontic_operators: false,
+                should_show_description_logic_operators: false,
             }
         })
     }
@@ -62,8 +68,12 @@ impl FormulaFormatOptions
         formula_format_options.should_show_possible_worlds = logic.get_name().is_modal_logic();
 
         let number_of_truth_values = logic.get_semantics().number_of_truth_values();
-        formula_format_options.should_show_sign = number_of_truth_values > 2;
+        let is_truth_matrix_logic = logic.get_name().is_truth_matrix_logic();
+        formula_format_options.should_show_sign = number_of_truth_values > 2 && !is_truth_matrix_logic;
         formula_format_options.should_show_fuzzy_tags = number_of_truth_values == u8::MAX;
+        formula_format_options.should_show_truth_values = is_truth_matrix_logic;
+        formula_format_options.should_show_deontic_operators = logic.get_parser_syntax().contains(&TokenTypeID::Obligatory);
+        formula_format_options.should_show_description_logic_operators = logic.get_parser_syntax().contains(&TokenTypeID::RoleExists);
 
         return formula_format_options;
     }
@@ -93,6 +103,13 @@ impl Formula
             formula_string = format!("{}, {}", self.get_fuzzy_tags(), formula_string);
         }
 
+        //on truth matrix logics, fuzzy tags are the truth values the formula may take, premises are designated
+        if options.should_show_truth_values && !is_comment && !self.get_fuzzy_tags().is_empty()
+        {
+            let truth_values = self.get_fuzzy_tags().iter().map(|tag| tag.object_name.clone()).join(", ");
+            formula_string = format!("{} ∈ {{{}}}", formula_string, truth_values);
+        }
+
         if self.is_hidden()
         {
             formula_string = format!("[HIDDEN] {}", formula_string);
@@ -121,6 +138,18 @@ impl Formula
                 return format!("{}{}", non, p.to_string_impl(options, index+1));
             }
 
+            And(p, q, _) if options.should_show_description_logic_operators =>
+            {
+                let intersection = options.notations.get_operator_character(TokenTypeID::ConceptIntersection);
+                return format_binary_formula(p, intersection, q);
+            }
+
+            Or(p, q, _) if options.should_show_description_logic_operators =>
+            {
+                let union = options.notations.get_operator_character(TokenTypeID::ConceptUnion);
+                return format_binary_formula(p, union, q);
+            }
+
             And(p, q, _) =>
             {
                 let and = options.notations.get_operator_character(TokenTypeID::And);
@@ -133,6 +162,12 @@ impl Formula
                 return format_binary_formula(p, or, q);
             }
 
+            Imply(p, q, _) if options.should_show_description_logic_operators =>
+            {
+                let subsumption = options.notations.get_operator_character(TokenTypeID::Subsumption);
+                return format_binary_formula(p, subsumption, q);
+            }
+
             Imply(p, q, _) =>
             {
                 let imply = options.notations.get_operator_character(TokenTypeID::Imply);
@@ -155,6 +190,18 @@ impl Formula
                 return format_binary_formula(p, 'ᐅ', q);
             }
 
+            Until(p, q, _) =>
+            {
+                let until = options.notations.get_operator_character(TokenTypeID::Until);
+                return format_binary_formula(p, until, q);
+            }
+
+            Since(p, q, _) =>
+            {
+                let since = options.notations.get_operator_character(TokenTypeID::Since);
+                return format_binary_formula(p, since, q);
+            }
+
             Exists(x, p, _) =>
             {
                 return format!("∃{}({})", x, p.to_string_impl(options, index+1));
@@ -176,6 +223,19 @@ impl Formula
                 return format!("𝔈{}", x);
             }
 
+            //the space separates the deontic operator from an atomic formula, eg: O p
+            Possible(p, _) if options.should_show_deontic_operators =>
+            {
+                let permitted = options.notations.get_operator_character(TokenTypeID::Permitted);
+                return format!("{} {}", permitted, p.to_string_impl(options, index+1));
+            }
+
+            Necessary(p, _) if options.should_show_deontic_operators =>
+            {
+                let obligatory = options.notations.get_operator_character(TokenTypeID::Obligatory);
+                return format!("{} {}", obligatory, p.to_string_impl(options, index+1));
+            }
+
             Possible(p, _) =>
             {
                 return format!("◇{}", p.to_string_impl(options, index+1));
@@ -196,6 +256,77 @@ impl Formula
                 return format!("ᶠ{}", p.to_string_impl(options, index+1));
             }
 
+            //the space separates the operator from an atomic formula, eg: X p
+            Next(p, _) =>
+            {
+                let next = options.notations.get_operator_character(TokenTypeID::Next);
+                return format!("{} {}", next, p.to_string_impl(options, index+1));
+            }
+
+            AgentPossible(role, p, _) if options.should_show_description_logic_operators =>
+            {
+                return format!("∃{}.{}", role, p.to_string_impl(options, index+1));
+            }
+
+            AgentNecessary(role, p, _) if options.should_show_description_logic_operators =>
+            {
+                return format!("∀{}.{}", role, p.to_string_impl(options, index+1));
+            }
+
+            //the space separates the agent from an atomic formula, eg: □_a p
+            AgentPossible(agent, p, _) =>
+            {
+                return format!("◇_{} {}", agent, p.to_string_impl(options, index+1));
+            }
+
+            AgentNecessary(agent, p, _) =>
+            {
+                return format!("□_{} {}", agent, p.to_string_impl(options, index+1));
+            }
+
+            EveryoneKnows(agents, p, _) =>
+            {
+                return format!("E_{{{}}} {}", agents.join(","), p.to_string_impl(options, index+1));
+            }
+
+            CommonKnowledge(agents, p, _) =>
+            {
+                return format!("C_{{{}}} {}", agents.join(","), p.to_string_impl(options, index+1));
+            }
+
+            DistributedKnowledge(agents, p, _) =>
+            {
+                return format!("D_{{{}}} {}", agents.join(","), p.to_string_impl(options, index+1));
+            }
+
+            Nominal(i, _) =>
+            {
+                return i.to_string();
+            }
+
+            //the space separates the nominal from an atomic formula, eg: @_i p
+            At(i, p, _) =>
+            {
+                let at = options.notations.get_operator_character(TokenTypeID::At);
+                return format!("{}_{} {}", at, i, p.to_string_impl(options, index+1));
+            }
+
+            Bind(i, p, _) =>
+            {
+                let bind = options.notations.get_operator_character(TokenTypeID::Bind);
+                return format!("{}_{} {}", bind, i, p.to_string_impl(options, index+1));
+            }
+
+            ProgramNecessary(program, p, _) =>
+            {
+                return format!("[{}]{}", program.to_string_with_options(options), p.to_string_impl(options, index+1));
+            }
+
+            ProgramPossible(program, p, _) =>
+            {
+                return format!("⟨{}⟩{}", program.to_string_with_options(options), p.to_string_impl(options, index+1));
+            }
+
             LessThan(x, y, _) =>
             {
                 return if index == 0 { format!("{} < {}", x, y) }
@@ -216,6 +347,40 @@ impl Formula
     }
 }
 
+impl Display for Program
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> std::fmt::Result
+    {
+        let options = FormulaFormatOptions::default();
+        return write!(f, "{}", self.to_string_with_options(&options));
+    }
+}
+
+impl Program
+{
+    //; binds stronger than ∪, eg: (a∪b);c, while * binds stronger than both, eg: (a;b)*
+    pub fn to_string_with_options(&self, options : &FormulaFormatOptions) -> String
+    {
+        return match self
+        {
+            Program::AtomicProgram(name) => { name.to_string() }
+            Program::Sequence(box a, box b) =>
+            {
+                let format_operand = |x : &Program| if let Program::Choice(..) = x
+                    { format!("({})", x.to_string_with_options(options)) } else { x.to_string_with_options(options) };
+                format!("{};{}", format_operand(a), format_operand(b))
+            }
+            Program::Choice(box a, box b) => { format!("{}∪{}", a.to_string_with_options(options), b.to_string_with_options(options)) }
+            Program::Test(box p) => { format!("{}?", p.to_string_impl(options, 1)) }
+            Program::Iteration(box a) => match a
+            {
+                Program::Sequence(..) | Program::Choice(..) => { format!("({})*", a.to_string_with_options(options)) }
+                _ => { format!("{}*", a.to_string_with_options(options)) }
+            }
+        }
+    }
+}
+
 impl Display for PredicateArguments
 {
     fn fmt(&self, f : &mut Formatter<'_>) -> std::fmt::Result
@@ -233,12 +398,22 @@ impl Display for PredicateArgument
 {
     fn fmt(&self, f : &mut Formatter<'_>) -> std::fmt::Result
     {
-        return if self.is_instantiated()
+        return if let Some(function_term) = &self.function_term
+            { write!(f, "{}", function_term) }
+        else if self.is_instantiated()
             { write!(f, "{}:{}", self.object_name, self.variable_name) }
         else { write!(f, "{}", self.variable_name) };
     }
 }
 
+impl Display for FunctionTerm
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> std::fmt::Result
+    {
+        return write!(f, "{}({})", self.function_name, self.args.iter().join(","));
+    }
+}
+
 impl Display for PossibleWorld
 {
     fn fmt(&self, f : &mut Formatter<'_>) -> std::fmt::Result
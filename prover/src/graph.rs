@@ -1,21 +1,40 @@
 pub mod to_json;
 mod missing_vertices;
 
-use std::slice::{Iter as VecIter, Iter};
+use std::slice::{Iter as VecIter, Iter, IterMut};
 use std::collections::btree_set::Iter as BTreeSetIter;
-use std::collections::BTreeSet;
+use std::collections::btree_map::Iter as BTreeMapIter;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Debug, Display, Formatter};
 use smol_str::{SmolStr, ToSmolStr};
 use crate::formula::{Formula, PossibleWorld};
-use crate::logic::common_modal_logic::NecessityReapplicationData;
+use crate::logic::common_modal_logic::{BlockedPossibility, NecessityReapplicationData};
+use crate::logic::first_degree_entailment::relevant_logic::RelevantImplicationReapplicationData;
 use crate::proof::execution_log::ExecutionLogHelperData;
+use crate::tree::node_factory::ProofTreeNodeID;
+use crate::tree::path::ProofTreePath;
+
+const GROUP_OF_AGENTS_SEPARATOR : &str = ",";
 
 pub struct Graph
 {
     nodes : BTreeSet<PossibleWorld>,
     vertices : BTreeSet<GraphVertex>,
+    ternary_vertices : BTreeSet<GraphTernaryVertex>,
+    star_worlds : BTreeMap<PossibleWorld, PossibleWorld>,
     vertices_tags : Vec<(GraphVertex, Formula)>,
+    //vertices added by branching frame rules, they hold only on the paths going through a given node
+    branch_vertices : BTreeMap<ProofTreeNodeID, BTreeSet<GraphVertex>>,
+    branch_vertices_tags : Vec<(ProofTreeNodeID, GraphVertex, Formula)>,
+    branch_world_identities : BTreeMap<ProofTreeNodeID, (PossibleWorld, PossibleWorld)>,
     necessity_reapplications : Vec<NecessityReapplicationData>,
+    inverted_necessity_reapplications : Vec<NecessityReapplicationData>,
+    relevant_implication_reapplications : Vec<RelevantImplicationReapplicationData>,
+    blocked_possibilities : Vec<BlockedPossibility>,
+    //on multi-agent modal logics, each agent has its own accessibility relation:
+    //the relation of the selected agent is kept above, the relations of other agents are kept here
+    agent : SmolStr,
+    agents_relations : BTreeMap<SmolStr, AgentRelation>,
     log_line_formatter : Box<dyn Fn(&GraphVertex) -> String>,
     log : String,
 }
@@ -34,8 +53,18 @@ impl Graph
         {
             nodes: BTreeSet::new(),
             vertices: BTreeSet::new(),
+            ternary_vertices: BTreeSet::new(),
+            star_worlds: BTreeMap::new(),
             vertices_tags: vec![],
+            branch_vertices: BTreeMap::new(),
+            branch_vertices_tags: vec![],
+            branch_world_identities: BTreeMap::new(),
             necessity_reapplications: vec![],
+            inverted_necessity_reapplications: vec![],
+            relevant_implication_reapplications: vec![],
+            blocked_possibilities: vec![],
+            agent: SmolStr::default(),
+            agents_relations: BTreeMap::new(),
             log_line_formatter: default_log_line_formatter!(),
             log: String::new(),
         };
@@ -43,7 +72,7 @@ impl Graph
 
     pub fn is_empty(&self) -> bool
     {
-        return self.nodes.is_empty() && self.vertices.is_empty();
+        return self.nodes.is_empty() && self.vertices.is_empty() && self.ternary_vertices.is_empty();
     }
 
     pub fn nodes(&self) -> BTreeSetIter<'_, PossibleWorld>
@@ -92,6 +121,136 @@ impl Graph
         }
     }
 
+    pub fn branch_vertices(&self) -> BTreeMapIter<'_, ProofTreeNodeID, BTreeSet<GraphVertex>>
+    {
+        return self.branch_vertices.iter();
+    }
+
+    pub fn set_branch_vertices(&mut self, branch_vertices : BTreeMap<ProofTreeNodeID, BTreeSet<GraphVertex>>)
+    {
+        self.branch_vertices = branch_vertices;
+    }
+
+    pub fn add_branch_vertices(&mut self, node_id : ProofTreeNodeID, vertices_to_add : Vec<GraphVertex>)
+    {
+        for vertex in vertices_to_add
+        {
+            self.log.push_str((self.log_line_formatter)(&vertex).as_str());
+
+            self.branch_vertices.entry(node_id).or_default().insert(vertex.clone());
+
+            ExecutionLogHelperData::with(|mut helper_data|
+                helper_data.new_graph_vertices.insert(vertex));
+        }
+    }
+
+    pub fn add_branch_vertex_tag(&mut self, node_id : ProofTreeNodeID, vertex : GraphVertex, tag : Formula)
+    {
+        self.branch_vertices_tags.push((node_id, vertex, tag));
+    }
+
+    pub fn add_branch_world_identity(&mut self, node_id : ProofTreeNodeID, world : PossibleWorld, identical_world : PossibleWorld)
+    {
+        self.log.push_str(format!("{}={}\n", world, identical_world).as_str());
+        self.branch_world_identities.insert(node_id, (world, identical_world));
+    }
+
+    pub fn has_branch_vertices(&self) -> bool
+    {
+        return !self.branch_vertices.is_empty() || !self.branch_world_identities.is_empty();
+    }
+
+    pub fn vertices_on_path(&self, path : &ProofTreePath) -> BTreeSet<GraphVertex>
+    {
+        let mut vertices = self.vertices.clone();
+
+        for (node_id, branch_vertices) in &self.branch_vertices
+        {
+            if path.contains_node_with_id(*node_id)
+            {
+                vertices.extend(branch_vertices.iter().cloned());
+            }
+        }
+
+        return vertices;
+    }
+
+    pub fn vertices_tags_on_path(&self, path : &ProofTreePath) -> Vec<(GraphVertex, Formula)>
+    {
+        let branch_vertices_tags = self.branch_vertices_tags.iter()
+            .filter(|(node_id, _vertex, _tag)| path.contains_node_with_id(*node_id))
+            .map(|(_node_id, vertex, tag)| (vertex.clone(), tag.clone()));
+
+        return self.vertices_tags.iter().cloned().chain(branch_vertices_tags).collect();
+    }
+
+    pub fn world_identities_on_path(&self, path : &ProofTreePath) -> Vec<(PossibleWorld, PossibleWorld)>
+    {
+        return self.branch_world_identities.iter()
+            .filter(|(node_id, _identity)| path.contains_node_with_id(**node_id))
+            .map(|(_node_id, identity)| *identity)
+            .collect();
+    }
+
+    //the world into which the given world was merged, or the world itself
+    pub fn get_identical_world_on_path(&self, path : &ProofTreePath, world : PossibleWorld) -> PossibleWorld
+    {
+        let world_identities = self.world_identities_on_path(path);
+
+        let mut identical_world = world;
+        while let Some((merged_into_world, _)) = world_identities.iter().find(|(_, merged_world)| *merged_world == identical_world)
+        {
+            identical_world = *merged_into_world;
+        }
+
+        return identical_world;
+    }
+
+    pub fn ternary_vertices(&self) -> BTreeSetIter<'_, GraphTernaryVertex>
+    {
+        return self.ternary_vertices.iter();
+    }
+
+    pub fn add_ternary_vertices(&mut self, vertices_to_add : Vec<GraphTernaryVertex>)
+    {
+        for vertex in vertices_to_add
+        {
+            self.log.push_str(format!("{}\n", vertex).as_str());
+            self.ternary_vertices.insert(vertex);
+        }
+    }
+
+    pub fn star_worlds(&self) -> BTreeMapIter<'_, PossibleWorld, PossibleWorld>
+    {
+        return self.star_worlds.iter();
+    }
+
+    pub fn get_star_world(&self, world : PossibleWorld) -> Option<PossibleWorld>
+    {
+        return self.star_worlds.get(&world).cloned();
+    }
+
+    pub fn add_node_with_star_world(&mut self) -> PossibleWorld
+    {
+        let forked_world = self.nodes.iter().max().map(|world| world.fork()).unwrap_or(PossibleWorld::zero());
+        self.add_node(forked_world);
+        self.add_star_world(forked_world);
+        return forked_world;
+    }
+
+    pub fn add_star_world(&mut self, world : PossibleWorld)
+    {
+        if self.star_worlds.contains_key(&world) { return };
+
+        let star_world = self.nodes.iter().max().unwrap().fork();
+        self.add_node(star_world);
+
+        self.star_worlds.insert(world, star_world);
+        self.star_worlds.insert(star_world, world);
+
+        self.log.push_str(format!("{}* = {}\n", world, star_world).as_str());
+    }
+
     pub fn vertices_tags(&self) -> VecIter<'_, (GraphVertex, Formula)>
     {
         return self.vertices_tags.iter();
@@ -117,11 +276,122 @@ impl Graph
         return self.necessity_reapplications.pop();
     }
 
+    pub fn necessity_reapplications_mut(&mut self) -> IterMut<'_, NecessityReapplicationData>
+    {
+        return self.necessity_reapplications.iter_mut();
+    }
+
+    pub fn inverted_necessity_reapplications_mut(&mut self) -> IterMut<'_, NecessityReapplicationData>
+    {
+        return self.inverted_necessity_reapplications.iter_mut();
+    }
+
+    //necessities applied on the inverted graph (eg: past necessities) must be reapplied only on the inverted graph
+    pub fn invert_necessity_reapplications(&mut self)
+    {
+        std::mem::swap(&mut self.necessity_reapplications, &mut self.inverted_necessity_reapplications);
+    }
+
     pub fn clear_necessity_reapplications(&mut self)
     {
         self.necessity_reapplications.clear();
     }
 
+    pub fn add_blocked_possibility(&mut self, blocked_possibility : BlockedPossibility)
+    {
+        self.blocked_possibilities.push(blocked_possibility);
+    }
+
+    pub fn take_blocked_possibilities(&mut self) -> Vec<BlockedPossibility>
+    {
+        return std::mem::take(&mut self.blocked_possibilities);
+    }
+
+    //pairs of (blocked world, blocking world)
+    pub fn blocked_worlds_on_path(&self, path : &ProofTreePath) -> BTreeSet<(PossibleWorld, PossibleWorld)>
+    {
+        return self.blocked_possibilities.iter()
+            .filter(|blocked_possibility| path.contains_node_with_id(blocked_possibility.leaf_node_id))
+            .map(|blocked_possibility| (blocked_possibility.blocked_world, blocked_possibility.blocking_world))
+            .collect();
+    }
+
+    pub fn push_relevant_implication_reapplication(&mut self, reapplication : RelevantImplicationReapplicationData)
+    {
+        self.relevant_implication_reapplications.push(reapplication);
+    }
+
+    pub fn pop_relevant_implication_reapplication(&mut self) -> Option<RelevantImplicationReapplicationData>
+    {
+        return self.relevant_implication_reapplications.pop();
+    }
+
+    pub fn agent(&self) -> SmolStr
+    {
+        return self.agent.clone();
+    }
+
+    //the empty agent selects the relation of the modal operators without agent
+    pub fn select_agent_relation(&mut self, agent : &SmolStr)
+    {
+        if self.agent == *agent { return }
+
+        let selected_relation = self.agents_relations.remove(agent).unwrap_or_default();
+        let previous_relation = AgentRelation
+        {
+            vertices: std::mem::replace(&mut self.vertices, selected_relation.vertices),
+            necessity_reapplications: std::mem::replace(&mut self.necessity_reapplications, selected_relation.necessity_reapplications),
+        };
+
+        let previous_agent = std::mem::replace(&mut self.agent, agent.clone());
+        self.agents_relations.insert(previous_agent, previous_relation);
+    }
+
+    pub fn agents(&self) -> Vec<SmolStr>
+    {
+        return self.agents_relations.keys().chain([&self.agent])
+            .filter(|agent| !agent.is_empty() && !Self::is_group_of_agents(agent))
+            .cloned().collect();
+    }
+
+    pub fn groups_of_agents(&self) -> Vec<SmolStr>
+    {
+        return self.agents_relations.keys().chain([&self.agent])
+            .filter(|agent| Self::is_group_of_agents(agent))
+            .cloned().collect();
+    }
+
+    //a group of agents has the intersection of the relations of its agents, eg: a,b
+    pub fn get_group_of_agents_name(agents : &Vec<SmolStr>) -> SmolStr
+    {
+        return agents.join(GROUP_OF_AGENTS_SEPARATOR).to_smolstr();
+    }
+
+    pub fn get_agents_of_group(group : &SmolStr) -> Vec<SmolStr>
+    {
+        return group.split(GROUP_OF_AGENTS_SEPARATOR).map(|agent| agent.to_smolstr()).collect();
+    }
+
+    pub fn is_group_of_agents(agent : &SmolStr) -> bool
+    {
+        return agent.contains(GROUP_OF_AGENTS_SEPARATOR);
+    }
+
+    //vertices of all agents, no matter which relation is selected, groups of agents excluded
+    pub fn agents_vertices(&self) -> Vec<(SmolStr, GraphVertex)>
+    {
+        let selected_vertices = self.vertices.iter()
+            .map(|vertex| (self.agent.clone(), vertex.clone()));
+
+        let other_vertices = self.agents_relations.iter()
+            .flat_map(|(agent, relation)| relation.vertices.iter()
+                .map(|vertex| (agent.clone(), vertex.clone())));
+
+        return selected_vertices.chain(other_vertices)
+            .filter(|(agent, _vertex)| !agent.is_empty() && !Self::is_group_of_agents(agent))
+            .collect();
+    }
+
     pub fn set_log_line_formatter(&mut self, formatter : Box<dyn Fn(&GraphVertex) -> String>)
     {
         self.log_line_formatter = formatter;
@@ -129,12 +399,26 @@ impl Graph
 
     pub fn flush_log(&mut self) -> SmolStr
     {
-        let log = self.log.trim().to_smolstr();
+        let log = if self.agent.is_empty() { self.log.trim().to_smolstr() }
+        else
+        {
+            self.log.trim().lines()
+                .map(|line| format!("{}: {}", self.agent, line))
+                .collect::<Vec<String>>().join("\n").to_smolstr()
+        };
+
         self.log = String::new();
         return log;
     }
 }
 
+#[derive(Default)]
+struct AgentRelation
+{
+    vertices : BTreeSet<GraphVertex>,
+    necessity_reapplications : Vec<NecessityReapplicationData>,
+}
+
 #[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Clone)]
 pub struct GraphVertex
 {
@@ -165,3 +449,36 @@ impl Debug for GraphVertex
         return write!(f, "{}→{}", self.from, self.to);
     }
 }
+
+//the ternary accessibility relation Rxyz of relevant logics
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Clone)]
+pub struct GraphTernaryVertex
+{
+    pub x : PossibleWorld,
+    pub y : PossibleWorld,
+    pub z : PossibleWorld,
+}
+
+impl GraphTernaryVertex
+{
+    pub fn new(x : PossibleWorld, y : PossibleWorld, z : PossibleWorld) -> GraphTernaryVertex
+    {
+        return GraphTernaryVertex { x, y, z };
+    }
+}
+
+impl Display for GraphTernaryVertex
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> std::fmt::Result
+    {
+        return write!(f, "R{}{}{}", self.x, self.y, self.z);
+    }
+}
+
+impl Debug for GraphTernaryVertex
+{
+    fn fmt(&self, f : &mut Formatter<'_>) -> std::fmt::Result
+    {
+        return write!(f, "R{}{}{}", self.x, self.y, self.z);
+    }
+}
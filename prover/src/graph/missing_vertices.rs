@@ -1,6 +1,8 @@
 use box_macro::bx;
+use smol_str::SmolStr;
 use crate::default_log_line_formatter;
-use crate::graph::{Graph, GraphVertex};
+use crate::formula::PossibleWorld;
+use crate::graph::{Graph, GraphTernaryVertex, GraphVertex};
 
 impl Graph
 {
@@ -66,4 +68,110 @@ impl Graph
 
         self.log_line_formatter = default_log_line_formatter!();
     }
+
+    pub fn add_missing_euclidean_vertices(&mut self)
+    {
+        let mut vertices_to_add : Vec<GraphVertex> = vec![];
+
+        //iRj and iRk => jRk, repeated until nothing else can be added
+        loop
+        {
+            for i_vertex in &self.vertices
+            {
+                for j_vertex in &self.vertices
+                {
+                    if i_vertex.from == j_vertex.from
+                    {
+                        let euclidean_vertex = GraphVertex::new(i_vertex.to, j_vertex.to);
+                        if !self.vertices.contains(&euclidean_vertex) && !vertices_to_add.contains(&euclidean_vertex)
+                        {
+                            vertices_to_add.push(euclidean_vertex);
+                        }
+                    }
+                }
+            }
+
+            if vertices_to_add.is_empty() { break }
+
+            self.set_log_line_formatter(bx!(|v| format!("{}ε{}\n", v.from, v.to)));
+            self.add_vertices(vertices_to_add.drain(..).collect());
+        }
+
+        self.log_line_formatter = default_log_line_formatter!();
+    }
+
+    pub fn add_serial_world(&mut self, possible_world : PossibleWorld) -> PossibleWorld
+    {
+        //on serial frames, every world sees at least one world
+        let serial_world = self.nodes.iter().max().unwrap().fork();
+        self.add_node(serial_world);
+
+        self.set_log_line_formatter(bx!(|v| format!("{}η{}\n", v.from, v.to)));
+        self.add_vertex(GraphVertex::new(possible_world, serial_world));
+
+        self.log_line_formatter = default_log_line_formatter!();
+
+        return serial_world;
+    }
+
+    pub fn add_missing_normal_world_ternary_vertices(&mut self, normal_world : PossibleWorld)
+    {
+        let mut vertices_to_add : Vec<GraphTernaryVertex> = vec![];
+
+        //on a normal world x, Rxyz holds iff y=z
+        for node in &self.nodes
+        {
+            let normal_vertex = GraphTernaryVertex::new(normal_world, *node, *node);
+            if !self.ternary_vertices.contains(&normal_vertex)
+            {
+                vertices_to_add.push(normal_vertex);
+            }
+        }
+
+        self.add_ternary_vertices(vertices_to_add);
+    }
+
+    pub fn add_missing_contraposed_ternary_vertices(&mut self)
+    {
+        let mut vertices_to_add : Vec<GraphTernaryVertex> = vec![];
+
+        //Rxyz => Rxz*y*
+        for vertex in &self.ternary_vertices
+        {
+            if let Some(y_star) = self.get_star_world(vertex.y) &&
+                let Some(z_star) = self.get_star_world(vertex.z)
+            {
+                let contraposed_vertex = GraphTernaryVertex::new(vertex.x, z_star, y_star);
+                if !self.ternary_vertices.contains(&contraposed_vertex) && !vertices_to_add.contains(&contraposed_vertex)
+                {
+                    vertices_to_add.push(contraposed_vertex);
+                }
+            }
+        }
+
+        self.add_ternary_vertices(vertices_to_add);
+    }
+
+    //the selected relation is the one of a group of agents, it must be the intersection of the relations of the agents
+    pub fn add_missing_intersection_vertices(&mut self, agents : &Vec<SmolStr>)
+    {
+        let mut vertices_to_add : Vec<GraphVertex> = vec![];
+
+        if let Some(first_agent_relation) = agents.first().and_then(|agent| self.agents_relations.get(agent))
+        {
+            for vertex in &first_agent_relation.vertices
+            {
+                if !self.vertices.contains(vertex) && agents.iter().all(|agent| self.agents_relations.get(agent)
+                    .is_some_and(|agent_relation| agent_relation.vertices.contains(vertex)))
+                {
+                    vertices_to_add.push(vertex.clone());
+                }
+            }
+        }
+
+        self.set_log_line_formatter(bx!(|v| format!("{}∩{}\n", v.from, v.to)));
+        self.add_vertices(vertices_to_add);
+
+        self.log_line_formatter = default_log_line_formatter!();
+    }
 }
@@ -1,15 +1,17 @@
 use std::collections::BTreeSet;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use substring::Substring;
-use crate::formula::PossibleWorld;
+use crate::formula::{Formula, PossibleWorld};
 use crate::formula::to_string::FormulaFormatOptions;
-use crate::graph::Graph;
+use crate::graph::{Graph, GraphVertex};
+use crate::tree::path::ProofTreePath;
 
 #[derive(Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq)]
 pub struct GraphJSON
 {
     nodes : BTreeSet<PossibleWorld>,
     vertices : BTreeSet<GraphVertexJSON>,
+    ternary_vertices : BTreeSet<GraphTernaryVertexJSON>,
 }
 
 #[derive(Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq)]
@@ -20,6 +22,14 @@ pub struct GraphVertexJSON
     tags : Vec<String>,
 }
 
+#[derive(Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq)]
+pub struct GraphTernaryVertexJSON
+{
+    x : PossibleWorld,
+    y : PossibleWorld,
+    z : PossibleWorld,
+}
+
 impl Serialize for PossibleWorld
 {
     fn serialize<S>(&self, serializer : S) -> Result<S::Ok, S::Error> where S : Serializer
@@ -41,13 +51,38 @@ impl <'de> Deserialize<'de> for PossibleWorld
 impl Graph
 {
     pub fn to_json(&self) -> GraphJSON
+    {
+        return self.to_json_with(self.nodes.clone(), self.vertices.clone(), self.vertices_tags.clone());
+    }
+
+    //the graph that belongs to a branch: worlds on the branch, vertices added on the branch included
+    pub fn to_json_on_path(&self, path : &ProofTreePath) -> GraphJSON
+    {
+        let mut nodes = path.nodes.iter()
+            .map(|node| node.formula.get_possible_world())
+            .filter(|possible_world| self.nodes.contains(possible_world))
+            .collect::<BTreeSet<PossibleWorld>>();
+
+        let star_worlds = nodes.iter()
+            .filter_map(|possible_world| self.get_star_world(*possible_world))
+            .collect::<BTreeSet<PossibleWorld>>();
+        nodes.extend(star_worlds);
+
+        let vertices = self.vertices_on_path(path).into_iter()
+            .filter(|vertex| nodes.contains(&vertex.from) && nodes.contains(&vertex.to))
+            .collect::<BTreeSet<GraphVertex>>();
+
+        return self.to_json_with(nodes, vertices, self.vertices_tags_on_path(path));
+    }
+
+    fn to_json_with(&self, nodes : BTreeSet<PossibleWorld>, vertices : BTreeSet<GraphVertex>, vertices_tags : Vec<(GraphVertex, Formula)>) -> GraphJSON
     {
         let mut vertices_for_json : BTreeSet<GraphVertexJSON> = BTreeSet::new();
         let formula_format_options = FormulaFormatOptions::default();
 
-        for vertex in &self.vertices
+        for vertex in &vertices
         {
-            let tags = self.vertices_tags.iter()
+            let tags = vertices_tags.iter()
                 .filter(|(v, _tag)| v.from==vertex.from && v.to==vertex.to)
                 .map(|(_v, tag)| tag.to_string_with_options(&formula_format_options))
                 .collect::<Vec<String>>();
@@ -55,6 +90,20 @@ impl Graph
             vertices_for_json.insert(GraphVertexJSON { from:vertex.from, to:vertex.to, tags:tags });
         }
 
-        return GraphJSON { nodes:self.nodes.clone(), vertices:vertices_for_json }
+        //on multi-agent modal logics, vertices are labelled with the agent
+        for (agent, vertex) in self.agents_vertices()
+        {
+            if nodes.contains(&vertex.from) && nodes.contains(&vertex.to)
+            {
+                vertices_for_json.insert(GraphVertexJSON { from:vertex.from, to:vertex.to, tags:vec![agent.to_string()] });
+            }
+        }
+
+        let ternary_vertices_for_json = self.ternary_vertices.iter()
+            .filter(|vertex| nodes.contains(&vertex.x) && nodes.contains(&vertex.y) && nodes.contains(&vertex.z))
+            .map(|vertex| GraphTernaryVertexJSON { x:vertex.x, y:vertex.y, z:vertex.z })
+            .collect::<BTreeSet<GraphTernaryVertexJSON>>();
+
+        return GraphJSON { nodes:nodes, vertices:vertices_for_json, ternary_vertices:ternary_vertices_for_json }
     }
 }
@@ -1,10 +1,12 @@
 use std::any::{Any, TypeId};
 use std::fmt::{Display, Formatter};
+use std::collections::{BTreeMap, BTreeSet};
 use std::rc::Rc;
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use box_macro::bx;
+use smol_str::{SmolStr, ToSmolStr};
 use FirstOrderLogicIdentityType::{ContingentIdentity, NecessaryIdentity};
-use crate::logic::common_modal_logic::ModalityRef;
+use crate::logic::common_modal_logic::{FrameCondition, MODAL_LOGIC_NAME_SUFFIX, ModalityRef};
 use crate::logic::conditional_modal_logic::ConditionalModalLogic;
 use crate::logic::first_degree_entailment::kleene_modal_logic::KleeneModalLogic;
 use crate::logic::first_degree_entailment::logic_of_constructible_negation::LogicOfConstructibleNegation;
@@ -13,15 +15,23 @@ use crate::logic::first_degree_entailment::lukasiewicz_modal_logic::LukasiewiczM
 use crate::logic::first_degree_entailment::MinimalFirstDegreeEntailmentLogic;
 use crate::logic::first_degree_entailment::priest_logic_of_paradox::PriestLPModalLogic;
 use crate::logic::first_degree_entailment::rmingle3_modal_logic::RMingle3ModalLogic;
-use crate::logic::first_order_logic::{FirstOrderLogicIdentityType, FirstOrderLogic, FIRST_ORDER_LOGIC_NAME_PREFIX, VariableDomainFlags};
+use crate::logic::first_degree_entailment::relevant_logic::RelevantLogic;
+use crate::logic::first_degree_entailment::routley_star_first_degree_entailment::RoutleyStarFirstDegreeEntailmentLogic;
+use crate::logic::first_order_logic::{FirstOrderLogicDomainType, FirstOrderLogicIdentityType, FirstOrderLogic, FIRST_ORDER_LOGIC_NAME_PREFIX, VariableDomainFlags};
 use crate::logic::first_order_logic::FirstOrderLogicDomainType::{ConstantDomain, VariableDomain};
 use crate::logic::fuzzy_logic::LukasiewiczFuzzyLogic;
+use crate::logic::fuzzy_logic::t_norm_fuzzy_logic::TNormFuzzyLogic;
+use crate::logic::truth_matrix_logic::{TRUTH_MATRIX_LOGIC_NAME_SUFFIX, TruthMatrixLogic};
+use crate::logic::multi_agent_modal_logic::{MULTI_AGENT_MODAL_LOGIC_NAME_SUFFIX, MultiAgentModalLogic};
 use crate::logic::intuitionistic_logic::IntuitionisticLogic;
 use crate::logic::non_normal_modal_logic::NonNormalModalLogic;
 use crate::logic::normal_modal_logic::NormalModalLogic;
 use crate::logic::propositional_logic::PropositionalLogic;
 use crate::logic::rule_apply_factory::RuleApplyFactory;
 use crate::logic::temporal_modal_logic::TemporalModalLogic;
+use crate::logic::linear_temporal_logic::LinearTemporalLogic;
+use crate::logic::hybrid_logic::HybridLogic;
+use crate::logic::propositional_dynamic_logic::PropositionalDynamicLogic;
 use crate::parser::token_types::TokenTypeID;
 use crate::semantics::Semantics;
 use crate::tree::node::ProofTreeNode;
@@ -36,9 +46,14 @@ pub mod common_modal_logic;
 pub mod rule_apply_factory;
 pub mod intuitionistic_logic;
 mod temporal_modal_logic;
+mod linear_temporal_logic;
+mod hybrid_logic;
+pub mod propositional_dynamic_logic;
+pub mod multi_agent_modal_logic;
 mod conditional_modal_logic;
-mod first_degree_entailment;
+pub mod first_degree_entailment;
 mod fuzzy_logic;
+pub mod truth_matrix_logic;
 mod logic_rule_result_impl;
 
 pub trait Logic : Any
@@ -155,7 +170,10 @@ impl LogicName
     {
         return !self.matches_name_of_logic(bx!(PropositionalLogic{})) &&
             !self.matches_name_of_logic(bx!(MinimalFirstDegreeEntailmentLogic{})) &&
-            !self.matches_name_of_logic(bx!(LukasiewiczFuzzyLogic{}));
+            !self.matches_name_of_logic(bx!(LukasiewiczFuzzyLogic{})) &&
+            !self.matches_name_of_logic(bx!(TNormFuzzyLogic::godel())) &&
+            !self.matches_name_of_logic(bx!(TNormFuzzyLogic::product())) &&
+            !self.is_truth_matrix_logic();
     }
 
     pub fn is_non_normal_modal_logic(&self) -> bool
@@ -169,12 +187,38 @@ impl LogicName
 
     pub fn is_intuitionistic_logic(&self) -> bool
     {
-        return self.matches_name_of_logic(bx!(IntuitionisticLogic{})) ||
+        return self.matches_name_of_logic(bx!(IntuitionisticLogic::first_kind())) ||
+            self.matches_name_of_logic(bx!(IntuitionisticLogic::second_kind())) ||
             self.matches_name_of_logic(bx!(LogicOfConstructibleNegation::I4())) ||
             self.matches_name_of_logic(bx!(LogicOfConstructibleNegation::I3())) ||
             self.matches_name_of_logic(bx!(LogicOfConstructibleNegation::W()));
     }
 
+    pub fn is_truth_matrix_logic(&self) -> bool
+    {
+        return self.value.ends_with(TRUTH_MATRIX_LOGIC_NAME_SUFFIX);
+    }
+
+    pub fn is_linear_temporal_logic(&self) -> bool
+    {
+        return self.matches_name_of_logic(bx!(LinearTemporalLogic {}));
+    }
+
+    pub fn is_hybrid_logic(&self) -> bool
+    {
+        return self.matches_name_of_logic(bx!(HybridLogic {}));
+    }
+
+    pub fn is_description_logic(&self) -> bool
+    {
+        return self.matches_name_of_logic(bx!(MultiAgentModalLogic::ALC()));
+    }
+
+    pub fn is_dynamic_logic(&self) -> bool
+    {
+        return self.matches_name_of_logic(bx!(PropositionalDynamicLogic {}));
+    }
+
     fn matches_name_of_logic(&self, logic : Box<dyn Logic>) -> bool
     {
         let target_value = logic.get_name().to_string();
@@ -202,11 +246,144 @@ impl LogicFactory
 {
     pub fn get_logic_by_name(name : &String) -> Result<Rc<dyn Logic>>
     {
-        return Self::get_logic_theories().into_iter()
+        if let Some(logic) = Self::get_logic_theories().into_iter()
             .find(|logic| logic.get_name().to_string().as_str() == name.as_str())
+        {
+            return Ok(logic);
+        }
+
+        return Self::compose_logic_by_name(name.as_str())
             .context(format!("Invalid logic with name {}!", name));
     }
 
+    //logics that are not in the catalog can be composed from the name,
+    //eg: Reflexive+Euclidean+ModalLogic, Kleene+Symmetric+Transitive+ModalLogic
+    fn compose_logic_by_name(name : &str) -> Result<Rc<dyn Logic>>
+    {
+        if let Some(name_without_prefix) = name.strip_prefix(format!("{}+", FIRST_ORDER_LOGIC_NAME_PREFIX).as_str())
+        {
+            for domain_type in Self::get_first_order_logic_domain_types()
+            {
+                for identity_type in [NecessaryIdentity, ContingentIdentity]
+                {
+                    if let Some(base_logic_name) = name_without_prefix.strip_prefix(format!("{}+{}+", domain_type, identity_type).as_str())
+                    {
+                        let base_logic = Self::compose_base_logic_by_name(base_logic_name)?;
+                        return Ok(Rc::new(FirstOrderLogic { domain_type, identity_type, base_logic }));
+                    }
+                }
+            }
+
+            return Err(anyhow!("Invalid first order logic domain or identity type!"));
+        }
+
+        return Self::compose_base_logic_by_name(name);
+    }
+
+    fn compose_base_logic_by_name(name : &str) -> Result<Rc<dyn Logic>>
+    {
+        if name.ends_with(MULTI_AGENT_MODAL_LOGIC_NAME_SUFFIX)
+        {
+            return Self::compose_multi_agent_modal_logic_by_name(name);
+        }
+
+        if name.ends_with(TRUTH_MATRIX_LOGIC_NAME_SUFFIX)
+        {
+            return Ok(Rc::new(TruthMatrixLogic::with_name(name)?));
+        }
+
+        return Self::compose_modal_logic_by_name(name);
+    }
+
+    //agents with frame conditions other than the default ones, eg: a=S5+b=KD45+KMultiAgentModalLogic
+    fn compose_multi_agent_modal_logic_by_name(name : &str) -> Result<Rc<dyn Logic>>
+    {
+        let mut name_parts = name.split('+').collect::<Vec<&str>>();
+        let base_logic_name = name_parts.pop().unwrap_or_default();
+        let base_logic = [MultiAgentModalLogic::K(), MultiAgentModalLogic::S5(), MultiAgentModalLogic::KD45()].into_iter()
+            .find(|logic| logic.get_name().to_string().as_str() == base_logic_name)
+            .context(format!("Invalid multi agent modal logic {}!", base_logic_name))?;
+
+        let mut agents_logics : BTreeMap<SmolStr, NormalModalLogic> = BTreeMap::new();
+        for name_part in name_parts
+        {
+            let (agent, agent_logic_name) = name_part.split_once('=')
+                .context(format!("Invalid agent {}, expected agent=logic, eg: a=S5!", name_part))?;
+
+            let agent_logic = Self::get_normal_modal_logics().into_iter()
+                .find(|logic| logic.get_name().to_string() == format!("{}{}", agent_logic_name, MODAL_LOGIC_NAME_SUFFIX))
+                .context(format!("Invalid modal logic {} of agent {}!", agent_logic_name, agent))?;
+
+            agents_logics.insert(agent.to_smolstr(), agent_logic);
+        }
+
+        return Ok(Rc::new(MultiAgentModalLogic::with_agents_logics(base_logic, agents_logics)));
+    }
+
+    fn compose_modal_logic_by_name(name : &str) -> Result<Rc<dyn Logic>>
+    {
+        let mut name_parts = name.split('+').collect::<Vec<&str>>();
+        if name_parts.pop() != Some(MODAL_LOGIC_NAME_SUFFIX)
+        {
+            return Err(anyhow!("{} is neither in the catalog, nor a list of frame conditions ending with {}!", name, MODAL_LOGIC_NAME_SUFFIX));
+        }
+
+        //the first part is the semantics if it's not a frame condition, classical by default
+        let semantics_name = match name_parts.first()
+        {
+            Some(name_part) if name_part.parse::<FrameCondition>().is_err() => Some(name_parts.remove(0)),
+            _ => None
+        };
+
+        let mut frame_conditions : BTreeSet<FrameCondition> = BTreeSet::new();
+        for name_part in name_parts
+        {
+            let frame_condition = name_part.parse::<FrameCondition>()
+                .context(format!("Invalid frame condition {}!", name_part))?;
+            frame_conditions.insert(frame_condition);
+        }
+
+        //seriality and euclideanity are implemented only for classical semantics
+        if semantics_name.is_some() && frame_conditions.iter()
+            .any(|frame_condition| matches!(frame_condition, FrameCondition::Serial | FrameCondition::Euclidean))
+        {
+            return Err(anyhow!("Frame conditions {} and {} are supported only with classical semantics!",
+                FrameCondition::Serial, FrameCondition::Euclidean));
+        }
+
+        return match semantics_name
+        {
+            None => Ok(Rc::new(NormalModalLogic::with_frame_conditions(&frame_conditions))),
+            Some("Lukasiewicz") => Ok(Rc::new(LukasiewiczModalLogic::with_frame_conditions(&frame_conditions))),
+            Some("RMingle3") => Ok(Rc::new(RMingle3ModalLogic::with_frame_conditions(&frame_conditions))),
+            Some("LP") => Ok(Rc::new(PriestLPModalLogic::with_frame_conditions(&frame_conditions))),
+            Some("Kleene") => Ok(Rc::new(KleeneModalLogic::with_frame_conditions(&frame_conditions))),
+            Some(semantics_name) => Err(anyhow!("Invalid semantics {}!", semantics_name)),
+        }
+    }
+
+    fn get_first_order_logic_domain_types() -> [FirstOrderLogicDomainType; 5]
+    {
+        return
+        [
+            ConstantDomain,
+            VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:false, has_negativity_constraint:true }),
+            VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:true, has_negativity_constraint:true }),
+            VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:false, has_negativity_constraint:false }),
+            VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:true, has_negativity_constraint:false }),
+        ];
+    }
+
+    fn get_normal_modal_logics() -> [NormalModalLogic; 10]
+    {
+        return
+        [
+            NormalModalLogic::K(), NormalModalLogic::D(), NormalModalLogic::T(), NormalModalLogic::B(),
+            NormalModalLogic::K4(), NormalModalLogic::K5(), NormalModalLogic::K45(), NormalModalLogic::KD45(),
+            NormalModalLogic::S4(), NormalModalLogic::S5(),
+        ];
+    }
+
     pub fn get_logic_theories() -> Vec<Rc<dyn Logic>>
     {
         let base_logics : Vec<Rc<dyn Logic>> = vec!
@@ -214,10 +391,22 @@ impl LogicFactory
             Rc::new(PropositionalLogic {}),
 
             Rc::new(NormalModalLogic::K()),
+            Rc::new(NormalModalLogic::D()),
             Rc::new(NormalModalLogic::T()),
             Rc::new(NormalModalLogic::B()),
+            Rc::new(NormalModalLogic::K4()),
+            Rc::new(NormalModalLogic::K5()),
+            Rc::new(NormalModalLogic::K45()),
+            Rc::new(NormalModalLogic::KD45()),
             Rc::new(NormalModalLogic::S4()),
             Rc::new(NormalModalLogic::S5()),
+            Rc::new(NormalModalLogic::GL()),
+            Rc::new(NormalModalLogic::SDL()),
+
+            Rc::new(MultiAgentModalLogic::K()),
+            Rc::new(MultiAgentModalLogic::S5()),
+            Rc::new(MultiAgentModalLogic::KD45()),
+            Rc::new(MultiAgentModalLogic::ALC()),
 
             Rc::new(NonNormalModalLogic::S0_5()),
             Rc::new(NonNormalModalLogic::N()),
@@ -227,13 +416,22 @@ impl LogicFactory
 
             Rc::new(TemporalModalLogic::basic()),
             Rc::new(TemporalModalLogic::extended()),
+            Rc::new(LinearTemporalLogic {}),
 
             Rc::new(ConditionalModalLogic::basic()),
             Rc::new(ConditionalModalLogic::extended()),
+            Rc::new(ConditionalModalLogic::C1()),
+            Rc::new(ConditionalModalLogic::C2()),
+            Rc::new(ConditionalModalLogic::VC()),
 
-            Rc::new(IntuitionisticLogic{}),
+            Rc::new(IntuitionisticLogic::first_kind()),
+            Rc::new(IntuitionisticLogic::second_kind()),
 
             Rc::new(MinimalFirstDegreeEntailmentLogic {}),
+            Rc::new(RoutleyStarFirstDegreeEntailmentLogic {}),
+
+            Rc::new(RelevantLogic::B()),
+            Rc::new(RelevantLogic::DW()),
 
             Rc::new(LukasiewiczModalLogic::L3_K()),
             Rc::new(LukasiewiczModalLogic::L3_T()),
@@ -271,14 +469,7 @@ impl LogicFactory
 
         let mut output_logics = base_logics.clone();
 
-        let domain_types =
-        [
-            ConstantDomain,
-            VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:false }),
-            VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:true }),
-        ];
-
-        for domain_type in domain_types
+        for domain_type in Self::get_first_order_logic_domain_types()
         {
             for identity_type in [NecessaryIdentity, ContingentIdentity]
             {
@@ -292,6 +483,22 @@ impl LogicFactory
             }
         }
 
+        //exact fuzzy tags of Gödel and Product or truth values of matrix logics
+        //cannot be shared by all the instances of a quantifier
+        output_logics.push(Rc::new(TNormFuzzyLogic::godel()));
+        output_logics.push(Rc::new(TNormFuzzyLogic::product()));
+
+        output_logics.push(Rc::new(TruthMatrixLogic::lukasiewicz(3)));
+        output_logics.push(Rc::new(TruthMatrixLogic::kleene()));
+        output_logics.push(Rc::new(TruthMatrixLogic::priest_lp()));
+        output_logics.push(Rc::new(TruthMatrixLogic::rmingle3()));
+        output_logics.push(Rc::new(TruthMatrixLogic::post(3)));
+        output_logics.push(Rc::new(TruthMatrixLogic::belnap()));
+
+        //nominals name worlds, not objects
+        output_logics.push(Rc::new(HybridLogic {}));
+        output_logics.push(Rc::new(PropositionalDynamicLogic {}));
+
         return output_logics;
     }
 }
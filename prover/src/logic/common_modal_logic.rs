@@ -1,10 +1,12 @@
 use std::collections::BTreeSet;
 use std::rc::Rc;
 use box_macro::bx;
+use smol_str::SmolStr;
+use strum_macros::{Display, EnumString};
 use crate::formula::{Formula, FormulaExtras, PossibleWorld};
-use crate::formula::Formula::{Imply, Necessary, Non, Possible, StrictImply};
+use crate::formula::Formula::{And, BiImply, Comment, EveryoneKnows, Imply, Necessary, Non, Or, Possible, StrictImply};
 use crate::graph::{Graph, GraphVertex};
-use crate::logic::{Logic, LogicRule, LogicRuleResult, LogicRuleResultCollection};
+use crate::logic::{Logic, LogicName, LogicRule, LogicRuleResult, LogicRuleResultCollection};
 use crate::logic::rule_apply_factory::RuleApplyFactory;
 use crate::tree::node::ProofTreeNode;
 use crate::tree::node_factory::ProofTreeNodeID;
@@ -28,7 +30,7 @@ impl <LOGIC : Logic> LogicRule for ModalLogicRules<LOGIC>
 {
     fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             Non(box Possible(box p, _), extras) =>
             {
@@ -90,16 +92,28 @@ pub struct NecessityReapplicationData
     pub already_iterated_possible_worlds : BTreeSet<PossibleWorld>,
 }
 
+//a possibility on a blocked world is not applied: on transitive frames, the world can access instead
+//the worlds accessible from the blocking world, which already fulfill the same possibility
+#[derive(Clone)]
+pub struct BlockedPossibility
+{
+    pub node_id : ProofTreeNodeID,
+    pub leaf_node_id : ProofTreeNodeID,
+    pub blocked_world : PossibleWorld,
+    pub blocking_world : PossibleWorld,
+}
+
 pub struct Modality<LOGIC : Logic>
 {
     pub is_possibility_applicable : fn(&RuleApplyFactory, &ProofTreeNode, &FormulaExtras) -> bool,
     pub is_necessity_applicable : fn(&RuleApplyFactory, &ProofTreeNode, &FormulaExtras) -> bool,
+    pub is_blocking_applicable : fn(&LOGIC) -> bool,
     pub add_missing_graph_vertices : fn(&LOGIC, &mut Graph) -> (),
 }
 
 impl <LOGIC : Logic> Modality<LOGIC>
 {
-    fn initialize_graph_if_needed(&self, factory : &mut RuleApplyFactory)
+    pub fn initialize_graph_if_needed(&self, factory : &mut RuleApplyFactory)
     {
         if factory.modality_graph.is_empty()
         {
@@ -119,13 +133,39 @@ impl <LOGIC : Logic> Modality<LOGIC>
         p : &Formula, extras : &FormulaExtras,
     ) -> LogicRuleResult
     {
-        if !(self.is_possibility_applicable)(factory, node, extras) { return LogicRuleResult::Empty }
+        let (result, _forked_world) = self.apply_possibility_with_forked_world(factory, node, p, extras);
+        return result;
+    }
+
+    //no world is forked if the possibility is not applicable or is blocked on all open paths
+    pub fn apply_possibility_with_forked_world(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        p : &Formula, extras : &FormulaExtras,
+    ) -> (LogicRuleResult, Option<PossibleWorld>)
+    {
+        if !(self.is_possibility_applicable)(factory, node, extras) { return (LogicRuleResult::Empty, None) }
 
         self.initialize_graph_if_needed(factory);
 
         let logic_pointer = factory.get_logic().clone();
         let logic = logic_pointer.cast_to::<LOGIC>().unwrap();
 
+        //with blocking, the new world is appended only on the open paths where the world is not blocked
+        let mut blocked_subtrees : Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
+        let mut unblocked_leaf_node_ids : Option<Vec<ProofTreeNodeID>> = None;
+        if (self.is_blocking_applicable)(logic) && !logic_pointer.get_name().is_first_order_logic()
+        {
+            let (subtrees, leaf_node_ids) = self.apply_blocking(factory, node, p);
+            if leaf_node_ids.is_empty()
+            {
+                if subtrees.is_empty() { return (LogicRuleResult::Empty, None) }
+                return (LogicRuleResult::Subtrees(subtrees), None);
+            }
+
+            blocked_subtrees = subtrees;
+            unblocked_leaf_node_ids = Some(leaf_node_ids);
+        }
+
         let current_world = extras.possible_world;
         let forked_world = factory.modality_graph.nodes().max().unwrap().fork();
 
@@ -135,17 +175,125 @@ impl <LOGIC : Logic> Modality<LOGIC>
         (self.add_missing_graph_vertices)(logic, factory.modality_graph);
 
         let p_in_forked_world = p.in_world(forked_world);
-        let p_in_forked_world_node = factory.new_node(p_in_forked_world);
-
         let comment = Formula::Comment(factory.modality_graph.flush_log());
-        let comment_node = factory.new_node(comment);
 
-        let subtree = ProofSubtree::with_middle_vertical_nodes(vec![comment_node, p_in_forked_world_node]);
-        let mut results = LogicRuleResultCollection::with(LogicRuleResult::Subtree(subtree));
+        let mut results = LogicRuleResultCollection::new();
+        match &unblocked_leaf_node_ids
+        {
+            None =>
+            {
+                let comment_node = factory.new_node(comment);
+                let p_in_forked_world_node = factory.new_node(p_in_forked_world);
 
-        self.reapply_necessity_after_possibility(factory, node, forked_world, &mut results);
+                let subtree = ProofSubtree::with_middle_vertical_nodes(vec![comment_node, p_in_forked_world_node]);
+                results.push(LogicRuleResult::Subtree(subtree));
+            }
 
-        return results.joined();
+            Some(leaf_node_ids) =>
+            {
+                let mut output_subtrees = blocked_subtrees;
+                for leaf_node_id in leaf_node_ids
+                {
+                    let comment_node = factory.new_node(comment.clone());
+                    let p_in_forked_world_node = factory.new_node(p_in_forked_world.clone());
+
+                    let subtree = ProofSubtree::with_middle_vertical_nodes(vec![comment_node, p_in_forked_world_node]);
+                    output_subtrees.push((*leaf_node_id, subtree));
+                }
+
+                results.push(LogicRuleResult::Subtrees(output_subtrees));
+            }
+        }
+
+        self.reapply_necessity_after_possibility_on_leafs(factory, node, forked_world, unblocked_leaf_node_ids.as_ref(), &mut results);
+
+        return (results.joined(), Some(forked_world));
+    }
+
+    //on transitive frames, the possibility is not applied on the open paths where it is already fulfilled or where its
+    //world is blocked, this keeps the tableau finite. Returns the comments of the blocked paths and the leafs of the other paths
+    fn apply_blocking(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode, p : &Formula) -> (Vec<(ProofTreeNodeID, ProofSubtree)>, Vec<ProofTreeNodeID>)
+    {
+        let blocked_world = node.formula.get_possible_world();
+
+        let open_paths = factory.tree.get_paths_that_goes_through_node(node).into_iter()
+            .filter(|path| path.nodes.iter().all(|node| !node.is_contradictory))
+            .collect::<Vec<ProofTreePath>>();
+
+        let mut output_subtrees : Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
+        let mut unblocked_leaf_node_ids : Vec<ProofTreeNodeID> = Vec::new();
+        for path in open_paths
+        {
+            if self.is_possibility_fulfilled_on_path(factory, &path, blocked_world, p) { continue }
+
+            let Some(blocking_world) = self.find_blocking_world(factory, &path, blocked_world) else
+            {
+                unblocked_leaf_node_ids.push(path.get_leaf_node_id());
+                continue;
+            };
+
+            factory.modality_graph.add_blocked_possibility(BlockedPossibility
+            {
+                node_id: node.id, leaf_node_id: path.get_leaf_node_id(),
+                blocked_world, blocking_world,
+            });
+
+            //blocked possibilities are checked again later, the comment is written only once
+            let comment = Comment(format!("{} blocked by {}", blocked_world, blocking_world).into());
+            if !path.nodes.iter().any(|path_node| *path_node.formula == comment)
+            {
+                let comment_node = factory.new_node(comment);
+                output_subtrees.push((path.get_leaf_node_id(), ProofSubtree::with_middle_node(comment_node)));
+            }
+        }
+
+        return (output_subtrees, unblocked_leaf_node_ids);
+    }
+
+    //a world accessible on the path already holds the possible formula, eg: the possibility was applied on the path before being blocked elsewhere
+    fn is_possibility_fulfilled_on_path(&self, factory : &RuleApplyFactory, path : &ProofTreePath, current_world : PossibleWorld, p : &Formula) -> bool
+    {
+        let vertices = factory.modality_graph.vertices_on_path(path);
+        let p_in_any_world = p.in_world(PossibleWorld::zero());
+
+        return path.nodes.iter()
+            .filter(|path_node| vertices.contains(&GraphVertex::new(current_world, path_node.formula.get_possible_world())))
+            .any(|path_node| path_node.formula.in_world(PossibleWorld::zero()) == p_in_any_world);
+    }
+
+    //a previous world, accessing the given world, which holds all its formulas and receives all the necessities it receives
+    fn find_blocking_world(&self, factory : &RuleApplyFactory, path : &ProofTreePath, blocked_world : PossibleWorld) -> Option<PossibleWorld>
+    {
+        let vertices = factory.modality_graph.vertices_on_path(path);
+
+        let get_formulas_on_world = |possible_world : PossibleWorld| path.nodes.iter()
+            .filter(|path_node| !matches!(*path_node.formula, Comment(..)))
+            .filter(|path_node| path_node.formula.get_possible_world() == possible_world)
+            .map(|path_node| path_node.formula.in_world(PossibleWorld::zero()))
+            .collect::<Vec<Formula>>();
+
+        let get_necessities_on_world = |possible_world : PossibleWorld| factory.modality_graph.necessity_reapplications()
+            .filter(|reapplication| reapplication.input_leafs_node_ids.iter().any(|leaf_id| path.contains_node_with_id(*leaf_id)))
+            .filter(|reapplication| reapplication.input_possible_world == possible_world ||
+                vertices.contains(&GraphVertex::new(reapplication.input_possible_world, possible_world)))
+            .map(|reapplication| reapplication.input_formula.in_world(PossibleWorld::zero()))
+            .collect::<Vec<Formula>>();
+
+        let formulas_on_blocked_world = get_formulas_on_world(blocked_world);
+        let necessities_on_blocked_world = get_necessities_on_world(blocked_world);
+
+        return path.nodes.iter()
+            .map(|path_node| path_node.formula.get_possible_world())
+            .filter(|possible_world| *possible_world < blocked_world)
+            .filter(|possible_world| vertices.contains(&GraphVertex::new(*possible_world, blocked_world)))
+            .collect::<BTreeSet<PossibleWorld>>().into_iter()
+            .find(|blocking_world|
+            {
+                let formulas_on_blocking_world = get_formulas_on_world(*blocking_world);
+                let necessities_on_blocking_world = get_necessities_on_world(*blocking_world);
+                return formulas_on_blocked_world.iter().all(|formula| formulas_on_blocking_world.contains(formula)) &&
+                    necessities_on_blocked_world.iter().all(|formula| necessities_on_blocking_world.contains(formula));
+            });
     }
 
     pub fn apply_necessity(&self,
@@ -175,22 +323,76 @@ impl <LOGIC : Logic> Modality<LOGIC>
         return result;
     }
 
-    fn reapply_necessity_after_possibility(
+    pub fn apply_seriality(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        extras : &FormulaExtras,
+    ) -> LogicRuleResult
+    {
+        self.initialize_graph_if_needed(factory);
+
+        let logic_pointer = factory.get_logic().clone();
+        let logic = logic_pointer.cast_to::<LOGIC>().unwrap();
+
+        let serial_world = factory.modality_graph.add_serial_world(extras.possible_world);
+
+        (self.add_missing_graph_vertices)(logic, factory.modality_graph);
+
+        let comment = Formula::Comment(factory.modality_graph.flush_log());
+        let comment_node = factory.new_node(comment);
+
+        let subtree = ProofSubtree::with_middle_node(comment_node);
+        let mut results = LogicRuleResultCollection::with(LogicRuleResult::Subtree(subtree));
+
+        //the new world is empty, it receives only the necessities
+        self.reapply_necessity_after_possibility(factory, node, serial_world, &mut results);
+
+        return results.joined();
+    }
+
+    pub fn has_accessible_worlds_on_all_paths(&self, factory : &RuleApplyFactory, node : &ProofTreeNode, possible_world : PossibleWorld) -> bool
+    {
+        return factory.tree.get_paths_that_goes_through_node(node).iter().all(|path|
+        {
+            let possible_worlds_on_path = path.nodes.iter()
+                .map(|node| node.formula.get_possible_world())
+                .collect::<BTreeSet<PossibleWorld>>();
+
+            return factory.modality_graph.vertices_on_path(path).iter()
+                .any(|vertex| vertex.from == possible_world && possible_worlds_on_path.contains(&vertex.to));
+        });
+    }
+
+    pub fn reapply_necessity_after_possibility(
         &self, factory : &mut RuleApplyFactory,
         node : &ProofTreeNode, forked_world : PossibleWorld,
         output_results : &mut LogicRuleResultCollection,
     )
     {
+        self.reapply_necessity_after_possibility_on_leafs(factory, node, forked_world, None, output_results);
+    }
+
+    //the forked world is only on the paths with the given leafs, or on all paths going through the node if none are given
+    fn reapply_necessity_after_possibility_on_leafs(
+        &self, factory : &mut RuleApplyFactory,
+        node : &ProofTreeNode, forked_world : PossibleWorld,
+        forked_world_leaf_node_ids : Option<&Vec<ProofTreeNodeID>>,
+        output_results : &mut LogicRuleResultCollection,
+    )
+    {
+        let paths_with_forked_world = factory.tree.get_paths_that_goes_through_node(node).into_iter()
+            .filter(|path| forked_world_leaf_node_ids.is_none_or(|leaf_node_ids| leaf_node_ids.contains(&path.get_leaf_node_id())))
+            .collect::<Vec<ProofTreePath>>();
+
         let mut reusable_necessity_reapplications : Vec<NecessityReapplicationData> = vec![];
 
         while let Some(mut reapplication) = factory.pop_next_necessity_reapplication()
         {
-            for path in factory.tree.get_paths_that_goes_through_node(node)
+            for path in &paths_with_forked_world
             {
                 //necessary reapplication should happen only if we're on one of some specific paths
                 if reapplication.input_leafs_node_ids.iter().any(|leaf_node_id| path.contains_node_with_id(*leaf_node_id))
                 {
-                    let output_from_necessity = self.reapply_necessity(factory, &mut reapplication, Some(node), forked_world);
+                    let output_from_necessity = self.reapply_necessity(factory, &mut reapplication, Some(&paths_with_forked_world), forked_world);
                     output_results.push(output_from_necessity);
                 }
             }
@@ -204,28 +406,39 @@ impl <LOGIC : Logic> Modality<LOGIC>
     fn reapply_necessity(&self,
         factory : &mut RuleApplyFactory,
         reapplication_data : &mut NecessityReapplicationData,
-        possibility_node : Option<&ProofTreeNode>,
+        paths_with_forked_world : Option<&Vec<ProofTreePath>>,
         forked_world : PossibleWorld,
     ) -> LogicRuleResult
     {
-        let mut output_subtrees: Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
-
-        let output_formulas = factory.modality_graph.vertices()
-            .filter(|vertex| vertex.from == reapplication_data.input_possible_world)
-            .filter(|vertex| !reapplication_data.already_iterated_possible_worlds.contains(&vertex.to))
-            .map(|vertex| reapplication_data.input_formula.in_world(vertex.to))
-            .collect::<Vec<Formula>>();
-
-        if output_formulas.is_empty() { return LogicRuleResult::Empty }
-
-        factory.set_spawner_node_id(Some(reapplication_data.input_spawner_node_id));
-
         let paths = factory.tree.get_all_paths().into_iter()
             .filter(|path| reapplication_data.input_leafs_node_ids.iter()
                 .any(|leaf_id| path.contains_node_with_id(*leaf_id)))
             .collect::<Vec<ProofTreePath>>();
 
+        //each path might have its own vertices, added by branching frame rules
+        let mut output_formulas_on_paths : Vec<(ProofTreePath, Vec<Formula>)> = Vec::new();
         for path in paths
+        {
+            let output_formulas = factory.modality_graph.vertices_on_path(&path).into_iter()
+                .filter(|vertex| vertex.from == reapplication_data.input_possible_world)
+                .filter(|vertex| !reapplication_data.already_iterated_possible_worlds.contains(&vertex.to))
+                .map(|vertex| reapplication_data.input_formula.in_world(vertex.to))
+                .collect::<Vec<Formula>>();
+
+            output_formulas_on_paths.push((path, output_formulas));
+        }
+
+        if output_formulas_on_paths.iter().all(|(_path, output_formulas)| output_formulas.is_empty())
+        {
+            return LogicRuleResult::Empty;
+        }
+
+        factory.set_spawner_node_id(Some(reapplication_data.input_spawner_node_id));
+
+        let mut output_subtrees: Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
+        let mut iterated_possible_worlds : BTreeSet<PossibleWorld> = BTreeSet::new();
+
+        for (path, output_formulas) in output_formulas_on_paths
         {
             let mut output_nodes_on_path: Vec<ProofTreeNode> = Vec::new();
 
@@ -233,14 +446,15 @@ impl <LOGIC : Logic> Modality<LOGIC>
                 .map(|node| node.formula.get_possible_world())
                 .collect::<BTreeSet<PossibleWorld>>();
 
-            if let Some(node) = possibility_node && path.contains(node)
+            if let Some(paths_with_forked_world) = paths_with_forked_world &&
+                paths_with_forked_world.iter().any(|path_with_forked_world| path_with_forked_world.get_leaf_node_id() == path.get_leaf_node_id())
             {
                 possible_worlds_on_path.insert(forked_world);
             }
 
             for formula in &output_formulas
             {
-                reapplication_data.already_iterated_possible_worlds.insert(formula.get_possible_world());
+                iterated_possible_worlds.insert(formula.get_possible_world());
 
                 if possible_worlds_on_path.contains(&formula.get_possible_world())
                 {
@@ -252,9 +466,68 @@ impl <LOGIC : Logic> Modality<LOGIC>
             output_subtrees.push((path.get_leaf_node_id(), subtree));
         }
 
+        reapplication_data.already_iterated_possible_worlds.extend(iterated_possible_worlds);
+
         return LogicRuleResult::Subtrees(output_subtrees);
     }
 
+    //the modal operators with a name, eg: the atomic programs of dynamic logic, have their own accessibility relation
+    pub fn apply_possibility_on_relation(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode, relation : &SmolStr,
+        p : &Formula, extras : &FormulaExtras,
+    ) -> LogicRuleResult
+    {
+        factory.modality_graph.select_agent_relation(relation);
+        let result = self.apply_possibility(factory, node, p, extras);
+        factory.modality_graph.select_agent_relation(&SmolStr::default());
+        return result;
+    }
+
+    pub fn apply_necessity_on_relation(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode, relation : &SmolStr,
+        p : &Formula, extras : &FormulaExtras,
+    ) -> LogicRuleResult
+    {
+        factory.modality_graph.select_agent_relation(relation);
+        let result = self.apply_necessity(factory, node, p, extras);
+        factory.modality_graph.select_agent_relation(&SmolStr::default());
+        return result;
+    }
+
+    //an eventuality, eg: ¬C_G P, must be fulfilled on some world reachable from here: if a previous world of the path
+    //already needed it with (at most) the same formulas, the search loops. Worlds are compared on their whole formula
+    //sets, as with blocking, but propositional formulas are skipped: they are already decomposed on the path, into the
+    //literals and modal formulas which are compared. The formulas spawned by unfolding the eventuality once are ignored,
+    //they are implied by the eventuality or by the choice of a successor
+    pub fn find_looping_world(&self, path : &ProofTreePath, node : &ProofTreeNode, eventuality_unfolding_formulas : &Vec<Formula>) -> Option<PossibleWorld>
+    {
+        let current_world = node.formula.get_possible_world();
+        let eventuality = node.formula.with_stripped_extras();
+
+        let get_formulas_on_world = |possible_world : PossibleWorld| path.nodes.iter()
+            .filter(|path_node| !matches!(*path_node.formula, Comment(..)))
+            .filter(|path_node| path_node.formula.get_possible_world() == possible_world)
+            .map(|path_node| path_node.formula.with_stripped_extras())
+            .filter(|formula| !eventuality_unfolding_formulas.contains(formula))
+            .filter(|formula| !matches!(formula, And(..) | Or(..) | Imply(..) | BiImply(..) | EveryoneKnows(..) |
+                Non(box Non(..) | box And(..) | box Or(..) | box Imply(..) | box BiImply(..) | box EveryoneKnows(..), _)))
+            .collect::<Vec<Formula>>();
+
+        let formulas_on_current_world = get_formulas_on_world(current_world);
+
+        return path.nodes.iter()
+            .take_while(|path_node| path_node.id != node.id)
+            .filter(|path_node| path_node.formula.get_possible_world() != current_world)
+            .filter(|path_node| path_node.formula.with_stripped_extras() == eventuality)
+            .map(|path_node| path_node.formula.get_possible_world())
+            .find(|previous_world|
+            {
+                //the current world has to fulfill everything the previous world had to fulfill, and the eventuality again
+                let formulas_on_previous_world = get_formulas_on_world(*previous_world);
+                return formulas_on_previous_world.iter().all(|formula| formulas_on_current_world.contains(formula));
+            });
+    }
+
     pub fn was_necessity_already_applied(&self, factory : &mut RuleApplyFactory, p : &Formula) -> bool
     {
         let p_with_stripped_extras = p.with_stripped_extras();
@@ -304,3 +577,120 @@ impl ModalityRef
         return (self.was_necessity_already_applied_ref)(factory, p);
     }
 }
+
+//properties of the accessibility relation, used to compose modal logics by name, eg: Reflexive+Euclidean+ModalLogic
+#[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Display, EnumString)]
+pub enum FrameCondition
+{
+    Reflexive, Symmetric, Transitive, Serial, Euclidean
+}
+
+pub const MODAL_LOGIC_NAME_SUFFIX : &str = "ModalLogic";
+
+impl FrameCondition
+{
+    pub fn get_logic_name(semantics_name : Option<&str>, frame_conditions : &BTreeSet<FrameCondition>) -> LogicName
+    {
+        let name_parts = semantics_name.into_iter().map(String::from)
+            .chain(frame_conditions.iter().map(|frame_condition| frame_condition.to_string()))
+            .chain([String::from(MODAL_LOGIC_NAME_SUFFIX)])
+            .collect::<Vec<String>>();
+
+        return LogicName::of(name_parts.join("+").as_str());
+    }
+}
+
+impl <'a> RuleApplyFactory<'a>
+{
+    //the same formula is already above the node on its world, eg: an eventuality unfolded again on a loop
+    pub fn was_already_applied_on_world(&self, node : &ProofTreeNode) -> bool
+    {
+        //nodes above the given node are common to all paths going through it
+        let paths = self.tree.get_paths_that_goes_through_node(node);
+        let Some(path) = paths.first() else { return true };  //the node was appended only on closed branches
+        let formula = node.formula.with_stripped_extras();
+        let possible_world = node.formula.get_possible_world();
+
+        return path.nodes.iter()
+            .take_while(|path_node| path_node.id != node.id)
+            .any(|path_node| path_node.formula.get_possible_world() == possible_world &&
+                path_node.formula.with_stripped_extras() == formula);
+    }
+
+    pub fn add_branch_world_identity(&mut self, path : &ProofTreePath, j : PossibleWorld, k : PossibleWorld) -> Vec<ProofTreeNode>
+    {
+        //k is merged into j: j inherits the vertices and the formulas of k
+        let vertices_on_path = self.modality_graph.vertices_on_path(path);
+        let inherited_vertices = vertices_on_path.iter()
+            .filter(|vertex| vertex.from == k || vertex.to == k)
+            .map(|vertex| GraphVertex::new(
+                if vertex.from == k { j } else { vertex.from },
+                if vertex.to == k { j } else { vertex.to }))
+            .filter(|vertex| !vertices_on_path.contains(vertex))
+            .collect::<BTreeSet<GraphVertex>>().into_iter()
+            .collect::<Vec<GraphVertex>>();
+
+        let mut comment_node = self.new_node(Comment(SmolStr::default()));
+        self.modality_graph.add_branch_world_identity(comment_node.id, j, k);
+        self.modality_graph.add_branch_vertices(comment_node.id, inherited_vertices.clone());
+        comment_node.formula = Rc::new(Comment(self.modality_graph.flush_log()));
+
+        let mut output_nodes = vec![comment_node];
+
+        //formulas of k that were already decomposed are not copied, their results are copied or inherited instead.
+        //necessities are still copied, since they must also apply on the vertices that j will get later
+        let decomposed_node_ids = path.nodes.iter()
+            .filter_map(|path_node| self.tree.get_node_with_id(path_node.id))
+            .filter_map(|tree_node| tree_node.spawner_node_id)
+            .collect::<BTreeSet<ProofTreeNodeID>>();
+
+        let formulas_on_path = path.nodes.iter().map(|node| node.formula.as_ref()).collect::<Vec<&Formula>>();
+        for path_node in &path.nodes
+        {
+            let formula_in_j = path_node.formula.in_world(j);
+            let was_decomposed = decomposed_node_ids.contains(&path_node.id) && !matches!(*path_node.formula, Necessary(..));
+            if path_node.formula.get_possible_world() == k && !matches!(*path_node.formula, Comment(..)) &&
+                !was_decomposed && !formulas_on_path.contains(&&formula_in_j)
+            {
+                output_nodes.push(self.new_node(formula_in_j));
+            }
+        }
+
+        output_nodes.append(&mut self.reapply_necessities_on_branch_vertices(path, inherited_vertices));
+        return output_nodes;
+    }
+
+    pub fn reapply_necessities_on_branch_vertices(&mut self, path : &ProofTreePath, vertices : Vec<GraphVertex>) -> Vec<ProofTreeNode>
+    {
+        let mut output_formulas : Vec<Formula> = vec![];
+
+        let is_reapplication_on_path = |reapplication : &NecessityReapplicationData|
+            reapplication.input_leafs_node_ids.iter().any(|leaf_node_id| path.contains_node_with_id(*leaf_node_id));
+
+        for reapplication in self.modality_graph.necessity_reapplications_mut()
+        {
+            for vertex in &vertices
+            {
+                if reapplication.input_possible_world == vertex.from && is_reapplication_on_path(reapplication)
+                {
+                    output_formulas.push(reapplication.input_formula.in_world(vertex.to));
+                    reapplication.already_iterated_possible_worlds.insert(vertex.to);
+                }
+            }
+        }
+
+        for reapplication in self.modality_graph.inverted_necessity_reapplications_mut()
+        {
+            for vertex in &vertices
+            {
+                if reapplication.input_possible_world == vertex.to && is_reapplication_on_path(reapplication)
+                {
+                    output_formulas.push(reapplication.input_formula.in_world(vertex.from));
+                    reapplication.already_iterated_possible_worlds.insert(vertex.from);
+                }
+            }
+        }
+
+        return output_formulas.into_iter().map(|formula| self.new_node(formula)).collect();
+    }
+}
@@ -1,12 +1,15 @@
 use std::any::Any;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
+use std::rc::Rc;
 use box_macro::bx;
+use itertools::Itertools;
+use smol_str::SmolStr;
 use crate::default_log_line_formatter;
-use crate::formula::{AtomicFormulaExtras, Formula, PossibleWorld, PredicateArguments};
-use crate::formula::Formula::{And, Atomic, BiImply, Comment, Conditional, DefinitelyExists, Equals, Exists, ForAll, GreaterOrEqualThan, Imply, InFuture, InPast, LessThan, Necessary, Non, Or, Possible, StrictImply};
+use crate::formula::{AtomicFormulaExtras, Formula, FormulaExtras, PossibleWorld, PredicateArguments};
+use crate::formula::Formula::{AgentNecessary, AgentPossible, And, At, Atomic, BiImply, Bind, Comment, CommonKnowledge, Conditional, DefinitelyExists, DistributedKnowledge, Equals, EveryoneKnows, Exists, ForAll, GreaterOrEqualThan, Imply, InFuture, InPast, LessThan, Necessary, Next, Nominal, Non, Or, Possible, ProgramNecessary, ProgramPossible, Since, StrictImply, Until};
 use crate::formula::to_string::FormulaFormatOptions;
 use crate::graph::GraphVertex;
-use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult};
+use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult, LogicRuleResultCollection};
 use crate::logic::common_modal_logic::{Modality, ModalityRef};
 use crate::logic::propositional_logic::PropositionalLogicRules;
 use crate::logic::rule_apply_factory::RuleApplyFactory;
@@ -14,27 +17,60 @@ use crate::parser::token_types::TokenTypeID;
 use crate::semantics::binary_logic_semantics::BinaryLogicSemantics;
 use crate::semantics::Semantics;
 use crate::tree::node::ProofTreeNode;
+use crate::tree::node_factory::ProofTreeNodeID;
 use crate::tree::path::ProofTreePath;
 use crate::tree::subtree::ProofSubtree;
 
 //check out book chapters 5 and 19
 pub struct ConditionalModalLogic
 {
-    pub is_extended : bool
+    pub is_extended : bool,
+    pub is_centered : bool,
+    pub has_unique_closest_world : bool,
+    pub is_sphere_ordered : bool,
 }
 
+#[allow(non_snake_case)]
 impl ConditionalModalLogic
 {
-    pub fn basic() -> ConditionalModalLogic { ConditionalModalLogic { is_extended:false } }
-    pub fn extended() -> ConditionalModalLogic { ConditionalModalLogic { is_extended:true } }
+    pub fn basic() -> ConditionalModalLogic
+    {
+        return ConditionalModalLogic { is_extended:false, is_centered:false, has_unique_closest_world:false, is_sphere_ordered:false };
+    }
+
+    pub fn extended() -> ConditionalModalLogic
+    {
+        return ConditionalModalLogic { is_extended:true, is_centered:false, has_unique_closest_world:false, is_sphere_ordered:false };
+    }
+
+    //C+ where the closest A-world to an A-world is only itself
+    pub fn C1() -> ConditionalModalLogic
+    {
+        return ConditionalModalLogic { is_extended:true, is_centered:true, has_unique_closest_world:false, is_sphere_ordered:false };
+    }
+
+    //C1 where there is at most one closest A-world (Stalnaker)
+    pub fn C2() -> ConditionalModalLogic
+    {
+        return ConditionalModalLogic { is_extended:true, is_centered:true, has_unique_closest_world:true, is_sphere_ordered:false };
+    }
+
+    //C1 where closest worlds come from a system of nested spheres (Lewis)
+    pub fn VC() -> ConditionalModalLogic
+    {
+        return ConditionalModalLogic { is_extended:true, is_centered:true, has_unique_closest_world:false, is_sphere_ordered:true };
+    }
 }
 
 impl Logic for ConditionalModalLogic
 {
     fn get_name(&self) -> LogicName
     {
-        return if !self.is_extended { LogicName::of("ConditionalModalLogic") }
-        else { LogicName::of("ConditionalExtModalLogic") };
+        return if self.is_sphere_ordered { LogicName::of("ConditionalVCModalLogic") }
+        else if self.has_unique_closest_world { LogicName::of("ConditionalC2ModalLogic") }
+        else if self.is_centered { LogicName::of("ConditionalC1ModalLogic") }
+        else if self.is_extended { LogicName::of("ConditionalExtModalLogic") }
+        else { LogicName::of("ConditionalModalLogic") };
     }
 
     fn as_any(&self) -> &dyn Any { self }
@@ -79,13 +115,9 @@ impl ConditionalModalLogic
         {
             is_possibility_applicable: |_, _, _| true,
             is_necessity_applicable: |_, _, _| true,
-            add_missing_graph_vertices: |logic, graph|
-            {
-                if logic.is_extended
-                {
-                    graph.add_missing_reflexive_vertices();
-                }
-            },
+            is_blocking_applicable: |_| false,
+            //on C+, ir_Ai is added by a branching rule
+            add_missing_graph_vertices: |_, _| {},
         };
     }
 }
@@ -107,7 +139,7 @@ impl LogicRule for ConditionalModalLogicRules
 {
     fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             Non(box Possible(box p, _), extras) =>
             {
@@ -142,6 +174,16 @@ impl LogicRule for ConditionalModalLogicRules
                 let logic_pointer = factory.get_logic().clone();
                 let logic = logic_pointer.cast_to::<ConditionalModalLogic>().unwrap();
 
+                if logic.is_centered && let Some(result) = self.apply_centering(factory, node, p, q, extras)
+                {
+                    return result;
+                }
+
+                if logic.has_unique_closest_world && let Some(result) = self.apply_uniqueness(factory, node, p, q, extras)
+                {
+                    return result;
+                }
+
                 let formula_format_options = FormulaFormatOptions::default();
                 let p_as_string = p.to_string_with_options(&formula_format_options);
 
@@ -180,10 +222,13 @@ impl LogicRule for ConditionalModalLogicRules
 
             Conditional(box p, box q, extras) =>
             {
-                let reflexive_vertices = factory.modality_graph.vertices()
-                    .filter(|vertex| vertex.from == vertex.to)
-                    .map(|vertex| vertex.clone())
-                    .collect::<BTreeSet<GraphVertex>>();
+                let logic_pointer = factory.get_logic().clone();
+                let logic = logic_pointer.cast_to::<ConditionalModalLogic>().unwrap();
+
+                if logic.is_sphere_ordered
+                {
+                    return self.apply_sphere_ordering(factory, node, p, q, extras);
+                }
 
                 let p_without_extras = p.with_stripped_extras();
                 let paths = factory.tree.get_paths_that_goes_through_node(node);
@@ -194,18 +239,25 @@ impl LogicRule for ConditionalModalLogicRules
 
                 let old_graph_vertices = factory.modality_graph.vertices()
                     .cloned().collect::<BTreeSet<GraphVertex>>();
+                let old_graph_branch_vertices = factory.modality_graph.branch_vertices()
+                    .map(|(node_id, vertices)| (*node_id, vertices.clone()))
+                    .collect::<BTreeMap<ProofTreeNodeID, BTreeSet<GraphVertex>>>();
 
-                let mut new_graph_vertices = reflexive_vertices;
-                new_graph_vertices.extend(vertices_with_right_tag);
-                factory.modality_graph.set_vertices(new_graph_vertices);
+                factory.modality_graph.set_vertices(vertices_with_right_tag);
+                factory.modality_graph.set_branch_vertices(BTreeMap::new());
 
                 let subtree = self.modality.apply_necessity(factory, node, &q, extras);
 
                 //necessity reapplication not working properly in C
                 factory.modality_graph.clear_necessity_reapplications();
                 factory.modality_graph.set_vertices(old_graph_vertices);
+                factory.modality_graph.set_branch_vertices(old_graph_branch_vertices);
 
-                return subtree;
+                if !logic.is_extended { return subtree }
+
+                let mut results = LogicRuleResultCollection::with(subtree);
+                results.push(self.apply_reflexivity(factory, node, p, q, extras));
+                return results.joined();
             }
 
             _ => LogicRuleResult::Empty
@@ -213,6 +265,325 @@ impl LogicRule for ConditionalModalLogicRules
     }
 }
 
+impl ConditionalModalLogicRules
+{
+    //check out book section 5.3: on C+, ¬A,i | ir_Ai, each branch having its own graph
+    fn apply_reflexivity(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        p : &Formula, q : &Formula, extras : &FormulaExtras,
+    ) -> LogicRuleResult
+    {
+        let current_world = extras.possible_world;
+        let reflexive_vertex = GraphVertex::new(current_world, current_world);
+        let p_without_extras = p.with_stripped_extras();
+
+        let formula_format_options = FormulaFormatOptions::default();
+        let p_as_string = p.to_string_with_options(&formula_format_options);
+
+        let paths = factory.tree.get_paths_that_goes_through_node(node).into_iter()
+            .filter(|path| path.nodes.iter().all(|node| !node.is_contradictory))
+            .collect::<Vec<ProofTreePath>>();
+
+        let mut output_subtrees : Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
+        for path in paths
+        {
+            let q_in_current_world_node = factory.new_node(q.in_world(current_world));
+
+            let is_reflexive_on_path = factory.modality_graph.vertices_tags_on_path(&path).iter()
+                .any(|(vertex, tag)| *vertex == reflexive_vertex && p_without_extras.is_replaceable_with(tag, &vec![path.clone()]));
+
+            if is_reflexive_on_path
+            {
+                output_subtrees.push((path.get_leaf_node_id(), ProofSubtree::with_middle_node(q_in_current_world_node)));
+                continue;
+            }
+
+            let non_p = Non(bx!(p.in_world(current_world)), extras.clone());
+            let non_p_node = factory.new_node(non_p);
+
+            let tag_as_string = p_as_string.clone();
+            factory.modality_graph.set_log_line_formatter(bx!(move |v|
+                format!("{}R{} [{}]\n", v.from, v.to, tag_as_string)));
+
+            let mut comment_node = factory.new_node(Comment(SmolStr::default()));
+            factory.modality_graph.add_branch_vertices(comment_node.id, vec![reflexive_vertex.clone()]);
+            factory.modality_graph.add_branch_vertex_tag(comment_node.id, reflexive_vertex.clone(), p_without_extras.clone());
+            comment_node.formula = Rc::new(Comment(factory.modality_graph.flush_log()));
+
+            factory.modality_graph.set_log_line_formatter(default_log_line_formatter!());
+
+            let reflexive_nodes = ProofSubtree::with_middle_vertical_nodes(vec![comment_node, q_in_current_world_node]);
+            let subtree = ProofSubtree::with_left_right_nodes(non_p_node, *reflexive_nodes.middle.unwrap());
+            output_subtrees.push((path.get_leaf_node_id(), subtree));
+        }
+
+        return LogicRuleResult::Subtrees(output_subtrees);
+    }
+}
+
+impl ConditionalModalLogicRules
+{
+    //on C1, if A is true at i, then i is the only closest A-world to i: A,i ¬B,i | ¬A,i ¬(A>B),i
+    fn apply_centering(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        p : &Formula, q : &Formula, extras : &FormulaExtras,
+    ) -> Option<LogicRuleResult>
+    {
+        let current_world = extras.possible_world;
+        let p_in_current_world = p.in_world(current_world);
+        let non_p_in_current_world = Non(bx!(p_in_current_world.clone()), extras.clone());
+        let non_q_in_current_world = Non(bx!(q.in_world(current_world)), extras.clone());
+
+        let paths = self.get_non_contradictory_paths(factory, node);
+        if paths.iter().all(|path| self.is_on_path(path, &non_p_in_current_world)) { return None }
+
+        let mut output_subtrees : Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
+        for path in paths
+        {
+            let subtree = if self.is_on_path(&path, &p_in_current_world)
+            {
+                ProofSubtree::with_middle_node(factory.new_node(non_q_in_current_world.clone()))
+            }
+            else if self.is_on_path(&path, &non_p_in_current_world)
+            {
+                //the hidden copy will be decomposed once ¬A,i is on every path
+                ProofSubtree::with_middle_node(factory.new_node(node.formula.with_is_hidden(true)))
+            }
+            else
+            {
+                let left_nodes = vec!
+                [
+                    factory.new_node(non_p_in_current_world.clone()),
+                    factory.new_node(node.formula.with_is_hidden(true)),
+                ];
+
+                let right_nodes = vec!
+                [
+                    factory.new_node(p_in_current_world.clone()),
+                    factory.new_node(non_q_in_current_world.clone()),
+                ];
+
+                self.new_split_subtree(left_nodes, right_nodes)
+            };
+
+            output_subtrees.push((path.get_leaf_node_id(), subtree));
+        }
+
+        return Some(LogicRuleResult::Subtrees(output_subtrees));
+    }
+
+    //on C2, there is at most one closest A-world, so ¬(A>B) reuses it instead of creating another one
+    fn apply_uniqueness(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        p : &Formula, q : &Formula, extras : &FormulaExtras,
+    ) -> Option<LogicRuleResult>
+    {
+        let current_world = extras.possible_world;
+        let non_q = Non(bx!(q.clone()), extras.clone());
+        let non_q_and_p = And(bx!(non_q), bx!(p.clone()), extras.clone()).with_is_hidden(true);
+
+        let mut closest_worlds : Vec<(ProofTreeNodeID, PossibleWorld)> = Vec::new();
+        for path in self.get_non_contradictory_paths(factory, node)
+        {
+            let closest_world = *self.get_closest_worlds_on_path(factory, &path, current_world, p).first()?;
+            closest_worlds.push((path.get_leaf_node_id(), closest_world));
+        }
+
+        let output_subtrees = closest_worlds.into_iter()
+            .map(|(leaf_id, closest_world)|
+            {
+                let non_q_and_p_node = factory.new_node(non_q_and_p.in_world(closest_world));
+                return (leaf_id, ProofSubtree::with_middle_node(non_q_and_p_node));
+            })
+            .collect::<Vec<(ProofTreeNodeID, ProofSubtree)>>();
+
+        return Some(LogicRuleResult::Subtrees(output_subtrees));
+    }
+
+    //on VC, if j is among the closest T-worlds to i and A is true at j, then either j is among the closest A-worlds,
+    //or there is an A-world k strictly closer than j, hence ¬T,k. Each branch decides the worlds one by one,
+    //leaving a hidden copy of A>B behind, thus the closest worlds are tracked with branch vertex tags.
+    fn apply_sphere_ordering(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        p : &Formula, q : &Formula, extras : &FormulaExtras,
+    ) -> LogicRuleResult
+    {
+        let current_world = extras.possible_world;
+        let p_in_current_world = p.in_world(current_world);
+        let non_p_in_current_world = Non(bx!(p_in_current_world.clone()), extras.clone());
+        let q_in_current_world = q.in_world(current_world);
+
+        let mut output_subtrees : Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
+        for path in self.get_non_contradictory_paths(factory, node)
+        {
+            let closest_worlds = self.get_closest_worlds_on_path(factory, &path, current_world, p);
+            let q_in_closest_worlds = closest_worlds.iter()
+                .map(|closest_world| q.in_world(*closest_world))
+                .filter(|q_in_closest_world| !self.is_on_path(&path, q_in_closest_world))
+                .collect::<Vec<Formula>>();
+
+            if self.is_on_path(&path, &p_in_current_world)
+            {
+                let mut formulas = q_in_closest_worlds.clone();
+                if !self.is_on_path(&path, &q_in_current_world) { formulas.push(q_in_current_world.clone()) }
+                if formulas.is_empty() { continue }
+
+                let nodes = formulas.into_iter().map(|formula| factory.new_node(formula)).collect();
+                output_subtrees.push((path.get_leaf_node_id(), ProofSubtree::with_middle_vertical_nodes(nodes)));
+                continue;
+            }
+
+            if !self.is_on_path(&path, &non_p_in_current_world)
+            {
+                let mut left_nodes = self.new_nodes(factory, &q_in_closest_worlds);
+                left_nodes.push(factory.new_node(non_p_in_current_world.clone()));
+                left_nodes.push(factory.new_node(node.formula.with_is_hidden(true)));
+
+                let mut right_nodes = self.new_nodes(factory, &q_in_closest_worlds);
+                right_nodes.push(factory.new_node(p_in_current_world.clone()));
+                right_nodes.push(factory.new_node(q_in_current_world.clone()));
+
+                output_subtrees.push((path.get_leaf_node_id(), self.new_split_subtree(left_nodes, right_nodes)));
+                continue;
+            }
+
+            let non_tag_in_world = |tag : &Formula, world : PossibleWorld| Non(bx!(tag.clone()), extras.clone()).in_world(world);
+            let undecided_vertex_with_tag = factory.modality_graph.vertices_tags_on_path(&path).into_iter()
+                .filter(|(vertex, _tag)| vertex.from == current_world && vertex.to != current_world)
+                .filter(|(vertex, _tag)| !closest_worlds.contains(&vertex.to) && self.is_world_on_path(&path, vertex.to))
+                .filter(|(vertex, _tag)| self.is_on_path(&path, &p.in_world(vertex.to)) && !self.is_on_path(&path, &q.in_world(vertex.to)))
+                .find(|(_vertex, tag)| !closest_worlds.iter().any(|world| self.is_on_path(&path, &non_tag_in_world(tag, *world))));
+
+            let Some((undecided_vertex, tag)) = undecided_vertex_with_tag else
+            {
+                if q_in_closest_worlds.is_empty() { continue }
+
+                let nodes = self.new_nodes(factory, &q_in_closest_worlds);
+                output_subtrees.push((path.get_leaf_node_id(), ProofSubtree::with_middle_vertical_nodes(nodes)));
+                continue;
+            };
+
+            let undecided_world = undecided_vertex.to;
+            let mut left_nodes = self.new_nodes(factory, &q_in_closest_worlds);
+            left_nodes.push(self.new_closest_world_comment_node(factory, undecided_vertex, p));
+            let consequents = self.get_consequents_in_world(&path, current_world, p, undecided_world);
+            left_nodes.append(&mut self.new_nodes(factory, &consequents));
+            left_nodes.push(factory.new_node(node.formula.with_is_hidden(true)));
+
+            let mut right_nodes = self.new_nodes(factory, &q_in_closest_worlds);
+            if closest_worlds.is_empty()
+            {
+                let closer_world = factory.modality_graph.nodes().max().unwrap().fork();
+                factory.modality_graph.add_node(closer_world);
+
+                let closer_vertex = GraphVertex::new(current_world, closer_world);
+                right_nodes.push(self.new_closest_world_comment_node(factory, closer_vertex, p));
+                right_nodes.push(factory.new_node(p.in_world(closer_world)));
+                right_nodes.push(factory.new_node(non_tag_in_world(&tag, closer_world)));
+                let consequents = self.get_consequents_in_world(&path, current_world, p, closer_world);
+                right_nodes.append(&mut self.new_nodes(factory, &consequents));
+            }
+            else
+            {
+                for closest_world in &closest_worlds
+                {
+                    right_nodes.push(factory.new_node(non_tag_in_world(&tag, *closest_world)));
+                }
+            }
+            right_nodes.push(factory.new_node(node.formula.with_is_hidden(true)));
+
+            output_subtrees.push((path.get_leaf_node_id(), self.new_split_subtree(left_nodes, right_nodes)));
+        }
+
+        return LogicRuleResult::Subtrees(output_subtrees);
+    }
+
+    fn new_closest_world_comment_node(&self, factory : &mut RuleApplyFactory, vertex : GraphVertex, p : &Formula) -> ProofTreeNode
+    {
+        let formula_format_options = FormulaFormatOptions::default();
+        let p_as_string = p.to_string_with_options(&formula_format_options);
+        factory.modality_graph.set_log_line_formatter(bx!(move |v|
+            format!("{}R{} [{}]\n", v.from, v.to, p_as_string)));
+
+        let mut comment_node = factory.new_node(Comment(SmolStr::default()));
+        factory.modality_graph.add_branch_vertices(comment_node.id, vec![vertex.clone()]);
+        factory.modality_graph.add_branch_vertex_tag(comment_node.id, vertex, p.with_stripped_extras());
+        comment_node.formula = Rc::new(Comment(factory.modality_graph.flush_log()));
+
+        factory.modality_graph.set_log_line_formatter(default_log_line_formatter!());
+
+        return comment_node;
+    }
+
+    //the consequents of all conditionals A>B from world i, moved into a closest A-world
+    fn get_consequents_in_world(&self,
+        path : &ProofTreePath, current_world : PossibleWorld,
+        p : &Formula, target_world : PossibleWorld,
+    ) -> Vec<Formula>
+    {
+        let p_without_extras = p.with_stripped_extras();
+        let paths = vec![path.clone()];
+
+        return path.nodes.iter()
+            .filter_map(|node| match node.formula.as_ref()
+            {
+                Conditional(box antecedent, box consequent, extras)
+                if extras.possible_world == current_world && p_without_extras.is_replaceable_with(&antecedent.with_stripped_extras(), &paths)
+                => Some(consequent.in_world(target_world)),
+                _ => None
+            })
+            .unique_by(|consequent| consequent.with_stripped_extras())
+            .filter(|consequent| !self.is_on_path(path, consequent))
+            .collect();
+    }
+
+    fn get_closest_worlds_on_path(&self,
+        factory : &mut RuleApplyFactory, path : &ProofTreePath,
+        current_world : PossibleWorld, p : &Formula,
+    ) -> Vec<PossibleWorld>
+    {
+        let p_without_extras = p.with_stripped_extras();
+        let paths = vec![path.clone()];
+
+        return factory.modality_graph.vertices_tags_on_path(path).into_iter()
+            .filter(|(vertex, tag)| vertex.from == current_world && vertex.to != current_world && p_without_extras.is_replaceable_with(tag, &paths))
+            .map(|(vertex, _tag)| vertex.to)
+            .filter(|world| self.is_world_on_path(path, *world))
+            .collect::<BTreeSet<PossibleWorld>>().into_iter().collect();
+    }
+
+    fn get_non_contradictory_paths(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> Vec<ProofTreePath>
+    {
+        return factory.tree.get_paths_that_goes_through_node(node).into_iter()
+            .filter(|path| path.nodes.iter().all(|node| !node.is_contradictory))
+            .collect();
+    }
+
+    fn is_on_path(&self, path : &ProofTreePath, formula : &Formula) -> bool
+    {
+        let formula_without_extras = formula.with_stripped_extras();
+        return path.nodes.iter().any(|node| node.formula.get_possible_world() == formula.get_possible_world() &&
+            node.formula.with_stripped_extras() == formula_without_extras);
+    }
+
+    fn is_world_on_path(&self, path : &ProofTreePath, world : PossibleWorld) -> bool
+    {
+        return path.nodes.iter().any(|node| !matches!(*node.formula, Comment(..)) && node.formula.get_possible_world() == world);
+    }
+
+    fn new_nodes(&self, factory : &mut RuleApplyFactory, formulas : &[Formula]) -> Vec<ProofTreeNode>
+    {
+        return formulas.iter().map(|formula| factory.new_node(formula.clone())).collect();
+    }
+
+    fn new_split_subtree(&self, left_nodes : Vec<ProofTreeNode>, right_nodes : Vec<ProofTreeNode>) -> ProofSubtree
+    {
+        let left_subtree = ProofSubtree::with_middle_vertical_nodes(left_nodes);
+        let right_subtree = ProofSubtree::with_middle_vertical_nodes(right_nodes);
+        return ProofSubtree::with_left_right_nodes(*left_subtree.middle.unwrap(), *right_subtree.middle.unwrap());
+    }
+}
+
 impl Formula
 {
     fn is_replaceable_with(&self, another : &Formula, paths : &Vec<ProofTreePath>) -> bool
@@ -259,6 +630,16 @@ impl Formula
                 if let Conditional(box w, box e, we_extras) = another
                 { p.is_replaceable_with(w, paths) && q.is_replaceable_with(e, paths) && pq_extras == we_extras } else { false }
             }
+            Until(box p, box q, pq_extras) =>
+            {
+                if let Until(box w, box e, we_extras) = another
+                { p.is_replaceable_with(w, paths) && q.is_replaceable_with(e, paths) && pq_extras == we_extras } else { false }
+            }
+            Since(box p, box q, pq_extras) =>
+            {
+                if let Since(box w, box e, we_extras) = another
+                { p.is_replaceable_with(w, paths) && q.is_replaceable_with(e, paths) && pq_extras == we_extras } else { false }
+            }
             Exists(x, box p, p_extras) =>
             {
                 if let Exists(y, box q, q_extras) = another
@@ -299,6 +680,61 @@ impl Formula
                 if let InFuture(box q, q_extras) = another
                 { p.is_replaceable_with(q, paths) && p_extras == q_extras } else { false }
             }
+            Next(box p, p_extras) =>
+            {
+                if let Next(box q, q_extras) = another
+                { p.is_replaceable_with(q, paths) && p_extras == q_extras } else { false }
+            }
+            AgentPossible(a, box p, p_extras) =>
+            {
+                if let AgentPossible(b, box q, q_extras) = another
+                { a==b && p.is_replaceable_with(q, paths) && p_extras == q_extras } else { false }
+            }
+            AgentNecessary(a, box p, p_extras) =>
+            {
+                if let AgentNecessary(b, box q, q_extras) = another
+                { a==b && p.is_replaceable_with(q, paths) && p_extras == q_extras } else { false }
+            }
+            EveryoneKnows(a, box p, p_extras) =>
+            {
+                if let EveryoneKnows(b, box q, q_extras) = another
+                { a==b && p.is_replaceable_with(q, paths) && p_extras == q_extras } else { false }
+            }
+            CommonKnowledge(a, box p, p_extras) =>
+            {
+                if let CommonKnowledge(b, box q, q_extras) = another
+                { a==b && p.is_replaceable_with(q, paths) && p_extras == q_extras } else { false }
+            }
+            DistributedKnowledge(a, box p, p_extras) =>
+            {
+                if let DistributedKnowledge(b, box q, q_extras) = another
+                { a==b && p.is_replaceable_with(q, paths) && p_extras == q_extras } else { false }
+            }
+            Nominal(i, p_extras) =>
+            {
+                if let Nominal(j, q_extras) = another
+                { i==j && p_extras == q_extras } else { false }
+            }
+            At(i, box p, p_extras) =>
+            {
+                if let At(j, box q, q_extras) = another
+                { i==j && p.is_replaceable_with(q, paths) && p_extras == q_extras } else { false }
+            }
+            Bind(i, box p, p_extras) =>
+            {
+                if let Bind(j, box q, q_extras) = another
+                { i==j && p.is_replaceable_with(q, paths) && p_extras == q_extras } else { false }
+            }
+            ProgramNecessary(a, box p, p_extras) =>
+            {
+                if let ProgramNecessary(b, box q, q_extras) = another
+                { a==b && p.is_replaceable_with(q, paths) && p_extras == q_extras } else { false }
+            }
+            ProgramPossible(a, box p, p_extras) =>
+            {
+                if let ProgramPossible(b, box q, q_extras) = another
+                { a==b && p.is_replaceable_with(q, paths) && p_extras == q_extras } else { false }
+            }
             LessThan(x, y, p_extras) =>
             {
                 if let LessThan(z, t, q_extras) = another
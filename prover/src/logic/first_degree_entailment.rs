@@ -17,7 +17,10 @@ pub mod priest_logic_of_paradox;
 pub mod kleene_modal_logic;
 pub mod logic_with_gaps_and_gluts;
 pub mod logic_of_constructible_negation;
+pub mod relevant_logic;
+pub mod routley_star_first_degree_entailment;
 mod generic_biimply_fde_rule;
+mod routley_star_negation_rule;
 
 //check out book chapters 8 and 22
 pub struct MinimalFirstDegreeEntailmentLogic {}
@@ -58,7 +61,7 @@ impl LogicRule for FirstDegreeEntailmentLogicRules
 {
     fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             And(box p, box q, extras) if extras.sign == Plus =>
             {
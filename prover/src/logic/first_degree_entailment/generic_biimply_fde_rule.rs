@@ -23,7 +23,7 @@ impl LogicRule for GenericBiImplyAsConjunctionRule
 {
     fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             BiImply(box p, box q, extras) =>
             {
@@ -98,6 +98,7 @@ impl LogicOfConstructibleNegation
         {
             is_possibility_applicable: |_, _, _| true,
             is_necessity_applicable: |_, _, _| true,
+            is_blocking_applicable: |_| false,
             add_missing_graph_vertices: |_logic, graph|
             {
                 graph.add_missing_reflexive_vertices();
@@ -126,7 +127,7 @@ impl LogicRule for LogicOfConstructibleNegationImplicationRules
 {
     fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             Imply(box p, box q, extras) if extras.sign == Plus =>
             {
@@ -79,6 +79,7 @@ impl LogicWithGapsGlutsAndWorlds
         {
             is_possibility_applicable: is_modality_applicable,
             is_necessity_applicable: is_modality_applicable,
+            is_blocking_applicable: |_| false,
             add_missing_graph_vertices: |_logic, graph|
             {
                 graph.add_missing_reflexive_vertices();
@@ -117,7 +118,7 @@ impl LogicRule for LogicWithGapsGlutsAndWorldsConditionalRules
 {
     fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             Conditional(box p, box q, extras) if extras.sign == Plus =>
             {
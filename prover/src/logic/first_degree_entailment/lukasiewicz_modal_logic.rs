@@ -1,6 +1,6 @@
 use crate::formula::Formula::{Imply, Non, Or};
 use crate::formula::Sign::{Minus, Plus};
-use crate::logic::common_modal_logic::{ModalLogicRules, Modality, ModalityRef};
+use crate::logic::common_modal_logic::{FrameCondition, ModalLogicRules, Modality, ModalityRef};
 use crate::logic::first_degree_entailment::generic_biimply_fde_rule::GenericBiImplyAsConjunctionRule;
 use crate::logic::first_degree_entailment::FirstDegreeEntailmentLogicRules;
 use crate::logic::rule_apply_factory::RuleApplyFactory;
@@ -12,6 +12,7 @@ use crate::tree::node::ProofTreeNode;
 use crate::tree::subtree::ProofSubtree;
 use box_macro::bx;
 use std::any::Any;
+use std::collections::BTreeSet;
 use std::rc::Rc;
 
 //check out book chapters 8 and 11a
@@ -33,6 +34,20 @@ impl LukasiewiczModalLogic
     pub fn L3_S5() -> LukasiewiczModalLogic { LukasiewiczModalLogic { name:LogicName::of("Lukasiewicz+S5ModalLogic"), is_reflexive:true, is_symmetric:true, is_transitive:true }}
 }
 
+impl LukasiewiczModalLogic
+{
+    pub fn with_frame_conditions(frame_conditions : &BTreeSet<FrameCondition>) -> LukasiewiczModalLogic
+    {
+        return LukasiewiczModalLogic
+        {
+            name: FrameCondition::get_logic_name(Some("Lukasiewicz"), frame_conditions),
+            is_reflexive: frame_conditions.contains(&FrameCondition::Reflexive),
+            is_symmetric: frame_conditions.contains(&FrameCondition::Symmetric),
+            is_transitive: frame_conditions.contains(&FrameCondition::Transitive),
+        }
+    }
+}
+
 impl Logic for LukasiewiczModalLogic
 {
     fn get_name(&self) -> LogicName { self.name.clone() }
@@ -83,6 +98,7 @@ impl LukasiewiczModalLogic
         {
             is_possibility_applicable: |_, _, _| true,
             is_necessity_applicable: |_, _, _| true,
+            is_blocking_applicable: |_| false,
             add_missing_graph_vertices: |logic, graph|
             {
                 if logic.is_reflexive { graph.add_missing_reflexive_vertices() }
@@ -99,7 +115,7 @@ impl LogicRule for LukasiewiczImplicationRules
 {
     fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             Imply(box p, box q, extras) if extras.sign == Plus =>
             {
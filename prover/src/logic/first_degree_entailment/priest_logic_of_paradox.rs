@@ -1,7 +1,8 @@
 use std::any::Any;
+use std::collections::BTreeSet;
 use std::rc::Rc;
 use crate::logic::{Logic, LogicName, LogicRuleCollection};
-use crate::logic::common_modal_logic::{Modality, ModalLogicRules, ModalityRef};
+use crate::logic::common_modal_logic::{FrameCondition, Modality, ModalLogicRules, ModalityRef};
 use crate::logic::first_degree_entailment::FirstDegreeEntailmentLogicRules;
 use crate::parser::token_types::TokenTypeID;
 use crate::semantics::Semantics;
@@ -26,6 +27,20 @@ impl PriestLPModalLogic
     pub fn LP_S5() -> PriestLPModalLogic { PriestLPModalLogic { name:LogicName::of("LP+S5ModalLogic"), is_reflexive:true, is_symmetric:true, is_transitive:true }}
 }
 
+impl PriestLPModalLogic
+{
+    pub fn with_frame_conditions(frame_conditions : &BTreeSet<FrameCondition>) -> PriestLPModalLogic
+    {
+        return PriestLPModalLogic
+        {
+            name: FrameCondition::get_logic_name(Some("LP"), frame_conditions),
+            is_reflexive: frame_conditions.contains(&FrameCondition::Reflexive),
+            is_symmetric: frame_conditions.contains(&FrameCondition::Symmetric),
+            is_transitive: frame_conditions.contains(&FrameCondition::Transitive),
+        }
+    }
+}
+
 impl Logic for PriestLPModalLogic
 {
     fn get_name(&self) -> LogicName { self.name.clone() }
@@ -72,6 +87,7 @@ impl PriestLPModalLogic
         {
             is_possibility_applicable: |_, _, _| true,
             is_necessity_applicable: |_, _, _| true,
+            is_blocking_applicable: |_| false,
             add_missing_graph_vertices: |logic, graph|
             {
                 if logic.is_reflexive { graph.add_missing_reflexive_vertices() }
@@ -0,0 +1,307 @@
+use std::any::Any;
+use std::collections::BTreeSet;
+use box_macro::bx;
+use crate::formula::Formula::{And, BiImply, Imply};
+use crate::formula::{Formula, FormulaExtras, PossibleWorld};
+use crate::formula::Sign::{Minus, Plus};
+use crate::graph::{Graph, GraphTernaryVertex};
+use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult, LogicRuleResultCollection};
+use crate::logic::common_modal_logic::ModalityRef;
+use crate::logic::first_degree_entailment::FirstDegreeEntailmentLogicRules;
+use crate::logic::first_degree_entailment::routley_star_negation_rule::RoutleyStarNegationRule;
+use crate::logic::rule_apply_factory::RuleApplyFactory;
+use crate::parser::token_types::TokenTypeID;
+use crate::semantics::Semantics;
+use crate::semantics::many_valued_logic_semantics::ManyValuedLogicSemantics;
+use crate::tree::node::ProofTreeNode;
+use crate::tree::node_factory::ProofTreeNodeID;
+use crate::tree::path::ProofTreePath;
+use crate::tree::subtree::ProofSubtree;
+
+//check out book chapters 10 and 24. R, E and T are not implemented: their frame conditions, eg: contraction,
+//Rxyz ⇒ ∃w(Rxyw ∧ Rwyz), need a new world for each vertex, thus their tableaux are infinite
+pub struct RelevantLogic
+{
+    pub name : LogicName,
+    pub has_contraposition_constraint : bool,
+}
+
+#[allow(non_snake_case)]
+impl RelevantLogic
+{
+    pub fn B() -> RelevantLogic { RelevantLogic { name:LogicName::of("BRelevantLogic"), has_contraposition_constraint:false } }
+    pub fn DW() -> RelevantLogic { RelevantLogic { name:LogicName::of("DWRelevantLogic"), has_contraposition_constraint:true } }
+}
+
+impl Logic for RelevantLogic
+{
+    fn get_name(&self) -> LogicName { self.name.clone() }
+    fn as_any(&self) -> &dyn Any { self }
+
+    fn get_semantics(&self) -> Box<dyn Semantics>
+    {
+        return Box::new(ManyValuedLogicSemantics::with_four_values());
+    }
+
+    fn get_parser_syntax(&self) -> Vec<TokenTypeID>
+    {
+        return vec!
+        [
+            TokenTypeID::AtomicWithoutArgs,
+            TokenTypeID::Non, TokenTypeID::And, TokenTypeID::Or,
+            TokenTypeID::Imply, TokenTypeID::BiImply,
+            TokenTypeID::OpenParenthesis, TokenTypeID::ClosedParenthesis
+        ]
+    }
+
+    fn get_rules(&self) -> LogicRuleCollection
+    {
+        return LogicRuleCollection::of(vec!
+        [
+            Box::new(RoutleyStarNegationRule {}),
+            Box::new(FirstDegreeEntailmentLogicRules {}),
+            Box::new(RelevantImplicationRules {}),
+        ])
+    }
+
+    fn get_modality_ref(&self) -> Option<ModalityRef> { None }
+}
+
+impl RelevantLogic
+{
+    fn add_missing_graph_ternary_vertices(&self, graph : &mut Graph)
+    {
+        //0 is the only normal world
+        graph.add_missing_normal_world_ternary_vertices(PossibleWorld::zero());
+
+        if self.has_contraposition_constraint
+        {
+            graph.add_missing_contraposed_ternary_vertices();
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RelevantImplicationReapplicationData
+{
+    pub input_antecedent : Formula,
+    pub input_consequent : Formula,
+    pub input_possible_world : PossibleWorld,
+    pub input_spawner_node_id : ProofTreeNodeID,
+    pub input_leafs_node_ids : Vec<ProofTreeNodeID>,
+    pub already_iterated_vertices : BTreeSet<GraphTernaryVertex>,
+}
+
+struct RelevantImplicationRules {}
+impl LogicRule for RelevantImplicationRules
+{
+    fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
+    {
+        return match node.formula.as_ref()
+        {
+            Imply(box p, box q, extras) if extras.sign == Plus =>
+            {
+                return self.apply_plus_implication(factory, node, p, q, extras);
+            }
+
+            Imply(box p, box q, extras) if extras.sign == Minus =>
+            {
+                return self.apply_minus_implication(factory, node, p, q, extras);
+            }
+
+            BiImply(box p, box q, extras) =>
+            {
+                let p_imply_q = Imply(bx!(p.clone()), bx!(q.clone()), extras.with_sign(Plus));
+                let q_imply_p = Imply(bx!(q.clone()), bx!(p.clone()), extras.with_sign(Plus));
+                let conjunction = And(bx!(p_imply_q), bx!(q_imply_p), extras.clone());
+                let conjunction_node = factory.new_node(conjunction);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(conjunction_node));
+            }
+
+            _ => LogicRuleResult::Empty
+        }
+    }
+}
+
+impl RelevantImplicationRules
+{
+    fn initialize_graph_if_needed(&self, factory : &mut RuleApplyFactory)
+    {
+        if factory.modality_graph.ternary_vertices().next().is_none()
+        {
+            let logic_pointer = factory.get_logic().clone();
+            let logic = logic_pointer.cast_to::<RelevantLogic>().unwrap();
+
+            if factory.modality_graph.is_empty()
+            {
+                factory.modality_graph.add_node_with_star_world();
+            }
+
+            logic.add_missing_graph_ternary_vertices(factory.modality_graph);
+
+            factory.modality_graph.flush_log();
+        }
+    }
+
+    fn apply_plus_implication(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        p : &Formula, q : &Formula, extras : &FormulaExtras,
+    ) -> LogicRuleResult
+    {
+        self.initialize_graph_if_needed(factory);
+
+        let paths = factory.tree.get_paths_that_goes_through_node(node);
+        let leaf_node_ids = paths.iter().map(|path| path.get_leaf_node_id()).collect();
+
+        let mut reapplication_data = RelevantImplicationReapplicationData
+        {
+            input_antecedent: p.clone(),
+            input_consequent: q.clone(),
+            input_possible_world: extras.possible_world,
+            input_spawner_node_id: node.id,
+            input_leafs_node_ids: leaf_node_ids,
+            already_iterated_vertices: BTreeSet::new(),
+        };
+
+        let result = self.reapply_implication(factory, &mut reapplication_data, paths, &BTreeSet::new());
+        factory.modality_graph.push_relevant_implication_reapplication(reapplication_data);
+
+        return result;
+    }
+
+    fn apply_minus_implication(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        p : &Formula, q : &Formula, extras : &FormulaExtras,
+    ) -> LogicRuleResult
+    {
+        self.initialize_graph_if_needed(factory);
+
+        let logic_pointer = factory.get_logic().clone();
+        let logic = logic_pointer.cast_to::<RelevantLogic>().unwrap();
+
+        let old_possible_worlds = factory.modality_graph.nodes()
+            .cloned().collect::<BTreeSet<PossibleWorld>>();
+
+        let current_world = extras.possible_world;
+        let (antecedent_world, consequent_world) = if current_world == PossibleWorld::zero()
+        {
+            //on the normal world, R0yz iff y=z
+            let forked_world = factory.modality_graph.add_node_with_star_world();
+            (forked_world, forked_world)
+        }
+        else
+        {
+            let antecedent_world = factory.modality_graph.add_node_with_star_world();
+            let consequent_world = factory.modality_graph.add_node_with_star_world();
+            let vertex = GraphTernaryVertex::new(current_world, antecedent_world, consequent_world);
+            factory.modality_graph.add_ternary_vertices(vec![vertex]);
+            (antecedent_world, consequent_world)
+        };
+
+        logic.add_missing_graph_ternary_vertices(factory.modality_graph);
+
+        let new_possible_worlds = factory.modality_graph.nodes().cloned()
+            .filter(|possible_world| !old_possible_worlds.contains(possible_world))
+            .collect::<BTreeSet<PossibleWorld>>();
+
+        let plus_p = p.in_world(antecedent_world).with_sign(Plus);
+        let plus_p_node = factory.new_node(plus_p);
+
+        let minus_q = q.in_world(consequent_world).with_sign(Minus);
+        let minus_q_node = factory.new_node(minus_q);
+
+        let comment = Formula::Comment(factory.modality_graph.flush_log());
+        let comment_node = factory.new_node(comment);
+
+        let subtree = ProofSubtree::with_middle_vertical_nodes(vec![comment_node, plus_p_node, minus_q_node]);
+        let mut results = LogicRuleResultCollection::with(LogicRuleResult::Subtree(subtree));
+
+        self.reapply_implications_after_new_worlds(factory, node, &new_possible_worlds, &mut results);
+
+        return results.joined();
+    }
+
+    fn reapply_implications_after_new_worlds(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        new_possible_worlds : &BTreeSet<PossibleWorld>,
+        output_results : &mut LogicRuleResultCollection,
+    )
+    {
+        let mut reusable_reapplications : Vec<RelevantImplicationReapplicationData> = vec![];
+
+        while let Some(mut reapplication) = factory.modality_graph.pop_relevant_implication_reapplication()
+        {
+            //implication reapplication should happen only if we're on one of some specific paths
+            let paths = factory.tree.get_paths_that_goes_through_node(node).into_iter()
+                .filter(|path| reapplication.input_leafs_node_ids.iter()
+                    .any(|leaf_node_id| path.contains_node_with_id(*leaf_node_id)))
+                .collect::<Vec<ProofTreePath>>();
+
+            let output_from_implication = self.reapply_implication(factory, &mut reapplication, paths, new_possible_worlds);
+            output_results.push(output_from_implication);
+
+            reusable_reapplications.push(reapplication);
+        }
+
+        for reapplication in reusable_reapplications
+        {
+            factory.modality_graph.push_relevant_implication_reapplication(reapplication);
+        }
+    }
+
+    fn reapply_implication(&self,
+        factory : &mut RuleApplyFactory,
+        reapplication_data : &mut RelevantImplicationReapplicationData,
+        paths : Vec<ProofTreePath>,
+        new_possible_worlds : &BTreeSet<PossibleWorld>,
+    ) -> LogicRuleResult
+    {
+        let vertices = factory.modality_graph.ternary_vertices()
+            .filter(|vertex| vertex.x == reapplication_data.input_possible_world)
+            .filter(|vertex| !reapplication_data.already_iterated_vertices.contains(vertex))
+            .cloned().collect::<Vec<GraphTernaryVertex>>();
+
+        if vertices.is_empty() || paths.is_empty() { return LogicRuleResult::Empty }
+
+        factory.set_spawner_node_id(Some(reapplication_data.input_spawner_node_id));
+
+        let mut output_subtrees : Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
+
+        for path in paths
+        {
+            let mut possible_worlds_on_path = path.nodes.iter()
+                .map(|node| node.formula.get_possible_world())
+                .chain(new_possible_worlds.iter().cloned())
+                .collect::<BTreeSet<PossibleWorld>>();
+
+            let star_worlds_on_path = possible_worlds_on_path.iter()
+                .filter_map(|possible_world| factory.modality_graph.get_star_world(*possible_world))
+                .collect::<BTreeSet<PossibleWorld>>();
+            possible_worlds_on_path.extend(star_worlds_on_path);
+
+            //foreach Rxyz: A→B,+x gives A,-y | B,+z
+            let mut output_nodes_on_path : Vec<(ProofTreeNode, ProofTreeNode)> = Vec::new();
+            for vertex in &vertices
+            {
+                if possible_worlds_on_path.contains(&vertex.y) && possible_worlds_on_path.contains(&vertex.z)
+                {
+                    let minus_p = reapplication_data.input_antecedent.in_world(vertex.y).with_sign(Minus);
+                    let minus_p_node = factory.new_node(minus_p);
+
+                    let plus_q = reapplication_data.input_consequent.in_world(vertex.z).with_sign(Plus);
+                    let plus_q_node = factory.new_node(plus_q);
+
+                    output_nodes_on_path.push((minus_p_node, plus_q_node));
+                }
+            }
+
+            let subtree = ProofSubtree::with_nested_left_right_nodes(output_nodes_on_path, factory.tree_node_factory);
+            output_subtrees.push((path.get_leaf_node_id(), subtree));
+        }
+
+        reapplication_data.already_iterated_vertices.extend(vertices);
+
+        return LogicRuleResult::Subtrees(output_subtrees);
+    }
+}
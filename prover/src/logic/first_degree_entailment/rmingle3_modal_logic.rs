@@ -1,10 +1,11 @@
 use std::any::Any;
+use std::collections::BTreeSet;
 use std::rc::Rc;
 use box_macro::bx;
 use crate::formula::Formula::{And, Imply, Non};
 use crate::formula::Sign::{Minus, Plus};
 use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult};
-use crate::logic::common_modal_logic::{Modality, ModalLogicRules, ModalityRef};
+use crate::logic::common_modal_logic::{FrameCondition, Modality, ModalLogicRules, ModalityRef};
 use crate::logic::first_degree_entailment::FirstDegreeEntailmentLogicRules;
 use crate::logic::first_degree_entailment::generic_biimply_fde_rule::GenericBiImplyAsConjunctionRule;
 use crate::logic::rule_apply_factory::RuleApplyFactory;
@@ -33,6 +34,20 @@ impl RMingle3ModalLogic
     pub fn RM3_S5() -> RMingle3ModalLogic { RMingle3ModalLogic { name:LogicName::of("RMingle3+S5ModalLogic"), is_reflexive:true, is_symmetric:true, is_transitive:true }}
 }
 
+impl RMingle3ModalLogic
+{
+    pub fn with_frame_conditions(frame_conditions : &BTreeSet<FrameCondition>) -> RMingle3ModalLogic
+    {
+        return RMingle3ModalLogic
+        {
+            name: FrameCondition::get_logic_name(Some("RMingle3"), frame_conditions),
+            is_reflexive: frame_conditions.contains(&FrameCondition::Reflexive),
+            is_symmetric: frame_conditions.contains(&FrameCondition::Symmetric),
+            is_transitive: frame_conditions.contains(&FrameCondition::Transitive),
+        }
+    }
+}
+
 impl Logic for RMingle3ModalLogic
 {
     fn get_name(&self) -> LogicName { self.name.clone() }
@@ -83,6 +98,7 @@ impl RMingle3ModalLogic
         {
             is_possibility_applicable: |_, _, _| true,
             is_necessity_applicable: |_, _, _| true,
+            is_blocking_applicable: |_| false,
             add_missing_graph_vertices: |logic, graph|
             {
                 if logic.is_reflexive { graph.add_missing_reflexive_vertices() }
@@ -99,7 +115,7 @@ impl LogicRule for RMingle3ImplicationRules
 {
     fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             Imply(box p, box q, extras) if extras.sign == Plus =>
             {
@@ -0,0 +1,44 @@
+use std::any::Any;
+use crate::logic::{Logic, LogicName, LogicRuleCollection};
+use crate::logic::common_modal_logic::ModalityRef;
+use crate::logic::first_degree_entailment::FirstDegreeEntailmentLogicRules;
+use crate::logic::first_degree_entailment::routley_star_negation_rule::RoutleyStarNegationRule;
+use crate::parser::token_types::TokenTypeID;
+use crate::semantics::Semantics;
+use crate::semantics::many_valued_logic_semantics::ManyValuedLogicSemantics;
+
+//check out book chapter 8 (the Routley star)
+pub struct RoutleyStarFirstDegreeEntailmentLogic {}
+
+impl Logic for RoutleyStarFirstDegreeEntailmentLogic
+{
+    fn get_name(&self) -> LogicName { LogicName::of("RoutleyStarFirstDegreeEntailmentLogic") }
+    fn as_any(&self) -> &dyn Any { self }
+
+    fn get_semantics(&self) -> Box<dyn Semantics>
+    {
+        return Box::new(ManyValuedLogicSemantics::with_routley_star());
+    }
+
+    fn get_parser_syntax(&self) -> Vec<TokenTypeID>
+    {
+        return vec!
+        [
+            TokenTypeID::AtomicWithoutArgs,
+            TokenTypeID::Non, TokenTypeID::And, TokenTypeID::Or,
+            TokenTypeID::OpenParenthesis, TokenTypeID::ClosedParenthesis
+        ]
+    }
+
+    fn get_rules(&self) -> LogicRuleCollection
+    {
+        return LogicRuleCollection::of(vec!
+        [
+            //negation is decomposed on the star world, before the FDE negation rules get a chance
+            Box::new(RoutleyStarNegationRule {}),
+            Box::new(FirstDegreeEntailmentLogicRules {}),
+        ])
+    }
+
+    fn get_modality_ref(&self) -> Option<ModalityRef> { None }
+}
@@ -0,0 +1,52 @@
+use crate::formula::Formula::Non;
+use crate::formula::{Formula, PossibleWorld};
+use crate::formula::Sign::Minus;
+use crate::logic::{LogicRule, LogicRuleResult};
+use crate::logic::rule_apply_factory::RuleApplyFactory;
+use crate::tree::node::ProofTreeNode;
+use crate::tree::subtree::ProofSubtree;
+
+//check out book chapters 8 and 10
+pub struct RoutleyStarNegationRule {}
+impl LogicRule for RoutleyStarNegationRule
+{
+    fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
+    {
+        return match node.formula.as_ref()
+        {
+            Non(box p, extras) =>
+            {
+                //¬A is true at x iff A is not true at x*
+                let star_world = self.get_star_world(factory, extras.possible_world);
+                let p_in_star_world = p.in_world(star_world).with_sign(extras.sign * Minus);
+                let p_in_star_world_node = factory.new_node(p_in_star_world);
+
+                //the log is not empty only if the star world was just created
+                let log = factory.modality_graph.flush_log();
+                if log.is_empty()
+                {
+                    return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(p_in_star_world_node));
+                }
+
+                let comment_node = factory.new_node(Formula::Comment(log));
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_vertical_nodes(vec![comment_node, p_in_star_world_node]));
+            }
+
+            _ => LogicRuleResult::Empty
+        }
+    }
+}
+
+impl RoutleyStarNegationRule
+{
+    fn get_star_world(&self, factory : &mut RuleApplyFactory, world : PossibleWorld) -> PossibleWorld
+    {
+        if factory.modality_graph.is_empty()
+        {
+            factory.modality_graph.add_node_with_star_world();
+        }
+
+        factory.modality_graph.add_star_world(world);
+        return factory.modality_graph.get_star_world(world).unwrap();
+    }
+}
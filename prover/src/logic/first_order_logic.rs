@@ -39,7 +39,11 @@ pub enum FirstOrderLogicDomainType
 #[derive(Eq, PartialEq, Hash, Copy, Clone)]
 pub struct VariableDomainFlags
 {
-    pub has_domain_increasing_constraint : bool
+    pub has_domain_increasing_constraint : bool,
+
+    //without the negativity constraint (positive free logic), atomic predicates,
+    //including identity, may be true of objects that do not exist
+    pub has_negativity_constraint : bool,
 }
 
 impl Default for FirstOrderLogicDomainType
@@ -47,6 +51,19 @@ impl Default for FirstOrderLogicDomainType
     fn default() -> Self { ConstantDomain }
 }
 
+impl FirstOrderLogicDomainType
+{
+    pub fn has_negativity_constraint(&self) -> bool
+    {
+        return matches!(self, VariableDomain(VariableDomainFlags { has_negativity_constraint:true, .. }));
+    }
+
+    pub fn has_domain_increasing_constraint(&self) -> bool
+    {
+        return matches!(self, VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:true, .. }));
+    }
+}
+
 impl Display for FirstOrderLogicDomainType
 {
     fn fmt(&self, f : &mut Formatter<'_>) -> std::fmt::Result
@@ -54,8 +71,10 @@ impl Display for FirstOrderLogicDomainType
         return write!(f, "{}", match self
         {
             ConstantDomain => "ConstantDomain",
-            VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:false }) => "VariableDomain-DomainIncreasingConstraint",
-            VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:true }) => "VariableDomain+DomainIncreasingConstraint",
+            VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:false, has_negativity_constraint:true }) => "VariableDomain-DomainIncreasingConstraint",
+            VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:true, has_negativity_constraint:true }) => "VariableDomain+DomainIncreasingConstraint",
+            VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:false, has_negativity_constraint:false }) => "VariableDomain-DomainIncreasingConstraint-NegativityConstraint",
+            VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:true, has_negativity_constraint:false }) => "VariableDomain+DomainIncreasingConstraint-NegativityConstraint",
         });
     }
 }
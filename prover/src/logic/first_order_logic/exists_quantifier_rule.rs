@@ -2,8 +2,8 @@ use std::collections::BTreeSet;
 use box_macro::bx;
 use smol_str::{format_smolstr, SmolStr, StrExt, ToSmolStr};
 use FirstOrderLogicDomainType::VariableDomain;
-use crate::formula::Formula::{And, Atomic, BiImply, Comment, Conditional, DefinitelyExists, Equals, Exists, ForAll, GreaterOrEqualThan, Imply, InFuture, InPast, LessThan, Necessary, Non, Or, Possible, StrictImply};
-use crate::formula::{AtomicFormulaExtras, Formula, FormulaExtras, PredicateArgument, PredicateArguments, FIRST_OBJECT_NAME, LAST_OBJECT_NAME};
+use crate::formula::Formula::{AgentNecessary, AgentPossible, And, At, Atomic, BiImply, Bind, Comment, CommonKnowledge, Conditional, DefinitelyExists, DistributedKnowledge, Equals, EveryoneKnows, Exists, ForAll, GreaterOrEqualThan, Imply, InFuture, InPast, LessThan, Necessary, Next, Nominal, Non, Or, Possible, ProgramNecessary, ProgramPossible, Since, StrictImply, Until};
+use crate::formula::{AtomicFormulaExtras, Formula, FormulaExtras, FunctionTerm, PredicateArgument, PredicateArguments, FIRST_OBJECT_NAME, LAST_OBJECT_NAME};
 use crate::formula::Sign::{Minus, Plus};
 use crate::logic::first_order_logic::{FirstOrderLogic, FirstOrderLogicDomainType};
 use crate::logic::{LogicRule, LogicRuleResult};
@@ -17,7 +17,7 @@ impl LogicRule for ExistsQuantifierRule
 {
     fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             Non(box Exists(x, box p, _), extras) =>
             {
@@ -100,12 +100,12 @@ impl ExistsQuantifierRule
         let mut already_used_names = BTreeSet::<SmolStr>::new();
         
         let mut formulas_in_path = factory.tree.get_paths_that_goes_through_node(node).into_iter()
-            .flat_map(|path| path.nodes.into_iter().map(|node| node.formula))
+            .flat_map(|path| path.nodes.into_iter().map(|node| node.formula.as_ref().clone()))
             .collect::<Vec<Formula>>();
 
         for pending_node in pending_nodes
         {
-            formulas_in_path.push(pending_node.formula.clone());
+            formulas_in_path.push(pending_node.formula.as_ref().clone());
         }
 
         for formula in formulas_in_path
@@ -120,6 +120,11 @@ impl ExistsQuantifierRule
             {
                 already_used_names.insert(predicate_argument.variable_name.clone());
                 already_used_names.insert(predicate_argument.object_name.clone());
+
+                if let Some(function_term) = &predicate_argument.function_term
+                {
+                    already_used_names.insert(function_term.function_name.clone());
+                }
             }
         }
 
@@ -138,7 +143,8 @@ impl Formula
 
     pub fn instantiated(&self, x : &PredicateArgument, object_name_factory : &Box<dyn Fn() -> SmolStr>, extras : &FormulaExtras) -> (Formula, Option<PredicateArgument>)
     {
-        let instantiated_p = self.instantiate_impl(x, object_name_factory).with_sign(extras.sign);
+        let instantiated_p = self.instantiate_impl(x, object_name_factory)
+            .with_sign(extras.sign).with_fuzzy_tags(extras.fuzzy_tags.clone());
         let instantiated_x = instantiated_p.get_all_predicate_arguments().into_iter()
             .find(|y| y.variable_name == x.variable_name && y.is_instantiated());
 
@@ -161,6 +167,11 @@ impl Formula
                 });
             }
 
+            Equals(y, z, extras) if y.function_term.is_some() || z.function_term.is_some() =>
+            {
+                return Equals(y.instantiated(x, object_name_factory), z.instantiated(x, object_name_factory), extras.clone());
+            }
+
             Equals(y, z, extras) =>
             {
                 if !y.is_instantiated() && x.variable_name == y.variable_name && y.variable_name == z.variable_name
@@ -207,12 +218,25 @@ impl Formula
             BiImply(p, q, extras) => { BiImply(instantiated_box(p), instantiated_box(q), extras.clone()) }
             StrictImply(p, q, extras) => { StrictImply(instantiated_box(p), instantiated_box(q), extras.clone()) }
             Conditional(p, q, extras) => { Conditional(instantiated_box(p), instantiated_box(q), extras.clone()) }
+            Until(p, q, extras) => { Until(instantiated_box(p), instantiated_box(q), extras.clone()) }
+            Since(p, q, extras) => { Since(instantiated_box(p), instantiated_box(q), extras.clone()) }
             Exists(x, p, extras) => { Exists(x.clone(), instantiated_box(p), extras.clone()) }
             ForAll(x, p, extras) => { ForAll(x.clone(), instantiated_box(p), extras.clone()) }
             Possible(p, extras) => { Possible(instantiated_box(p), extras.clone()) }
             Necessary(p, extras) => { Necessary(instantiated_box(p), extras.clone()) }
             InPast(p, extras) => { InPast(instantiated_box(p), extras.clone()) }
             InFuture(p, extras) => { InFuture(instantiated_box(p), extras.clone()) }
+            Next(p, extras) => { Next(instantiated_box(p), extras.clone()) }
+            AgentPossible(agent, p, extras) => { AgentPossible(agent.clone(), instantiated_box(p), extras.clone()) }
+            AgentNecessary(agent, p, extras) => { AgentNecessary(agent.clone(), instantiated_box(p), extras.clone()) }
+            EveryoneKnows(agents, p, extras) => { EveryoneKnows(agents.clone(), instantiated_box(p), extras.clone()) }
+            CommonKnowledge(agents, p, extras) => { CommonKnowledge(agents.clone(), instantiated_box(p), extras.clone()) }
+            DistributedKnowledge(agents, p, extras) => { DistributedKnowledge(agents.clone(), instantiated_box(p), extras.clone()) }
+            Nominal(i, extras) => { Nominal(i.clone(), extras.clone()) }
+            At(i, p, extras) => { At(i.clone(), instantiated_box(p), extras.clone()) }
+            Bind(i, p, extras) => { Bind(i.clone(), instantiated_box(p), extras.clone()) }
+            ProgramNecessary(program, p, extras) => { ProgramNecessary(program.clone(), instantiated_box(p), extras.clone()) }
+            ProgramPossible(program, p, extras) => { ProgramPossible(program.clone(), instantiated_box(p), extras.clone()) }
             LessThan(x, y, extras) => { LessThan(x.clone(), y.clone(), extras.clone()) }
             GreaterOrEqualThan(x, y, extras) => { GreaterOrEqualThan(x.clone(), y.clone(), extras.clone()) }
             Comment(payload) => { Comment(payload.clone()) }
@@ -224,28 +248,37 @@ impl PredicateArguments
 {
     pub fn instantiated(&self, x : &PredicateArgument, object_name_factory : &Box<dyn Fn() -> SmolStr>) -> PredicateArguments
     {
-        let mut instantiated_args : Vec<PredicateArgument> = vec![];
-        for arg in self.iter()
+        return PredicateArguments::new(self.iter().map(|arg| arg.instantiated(x, object_name_factory)).collect());
+    }
+}
+
+impl PredicateArgument
+{
+    fn instantiated(&self, x : &PredicateArgument, object_name_factory : &Box<dyn Fn() -> SmolStr>) -> PredicateArgument
+    {
+        if let Some(function_term) = &self.function_term
         {
-            if arg.variable_name != x.variable_name
-            {
-                //we need to instantiate x and this is y. skip.
-                instantiated_args.push(arg.clone());
-            }
-            else if arg.is_instantiated()
-            {
-                //we need to instantiate x and this is already instantiated (a:x). keep the instantiation (a:x).
-                instantiated_args.push(arg.clone());
-            }
-            else
-            {
-                //instantiate x into a:x
-                let mut instantiated_arg = arg.clone();
-                instantiated_arg.object_name = (*object_name_factory)();
-                instantiated_args.push(instantiated_arg);
-            }
+            //instantiate x inside the function term, f(x) into f(a:x)
+            let function_name = function_term.function_name.clone();
+            let args = function_term.args.iter().map(|arg| arg.instantiated(x, object_name_factory)).collect();
+            return PredicateArgument::with_function_term(FunctionTerm { function_name, args });
+        }
+
+        if self.variable_name != x.variable_name
+        {
+            //we need to instantiate x and this is y. skip.
+            return self.clone();
+        }
+
+        if self.is_instantiated()
+        {
+            //we need to instantiate x and this is already instantiated (a:x). keep the instantiation (a:x).
+            return self.clone();
         }
 
-        return PredicateArguments::new(instantiated_args);
+        //instantiate x into a:x
+        let mut instantiated_arg = self.clone();
+        instantiated_arg.object_name = (*object_name_factory)();
+        return instantiated_arg;
     }
 }
@@ -1,7 +1,7 @@
 use std::collections::BTreeSet;
 use box_macro::bx;
 use itertools::Itertools;
-use smol_str::SmolStr;
+use smol_str::{format_smolstr, SmolStr};
 use FirstOrderLogicDomainType::VariableDomain;
 use crate::formula::Formula::{DefinitelyExists, Exists, ForAll, Non};
 use crate::formula::{Formula, FormulaExtras, PossibleWorld, PredicateArgument};
@@ -12,18 +12,23 @@ use crate::logic::first_order_logic::FirstOrderLogicDomainType::ConstantDomain;
 use crate::logic::first_order_logic::predicate_args_with_equivalences::create_equality_formulas_filtering_lambda;
 use crate::logic::{LogicRule, LogicRuleResult};
 use crate::logic::rule_apply_factory::RuleApplyFactory;
+use crate::problem::ProofLimit;
 use crate::tree::node::ProofTreeNode;
 use crate::tree::node_factory::ProofTreeNodeID;
 use crate::tree::path::ProofTreePath;
 use crate::tree::subtree::ProofSubtree;
 
+//with function symbols the Herbrand universe is infinite, eg: a, f(a), f(f(a)), ...
+//thus it is built only up to this depth, the ground terms that are already on the path are always used
+const MAX_FUNCTION_TERM_DEPTH : usize = 2;
+
 pub struct ForAllQuantifierRule {}
 
 impl LogicRule for ForAllQuantifierRule
 {
     fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             Non(box ForAll(x, box p, _), extras) =>
             {
@@ -147,8 +152,8 @@ impl ForAllQuantifierRule
         let logic_pointer = factory.get_logic().clone();
         let logic = logic_pointer.cast_to::<FirstOrderLogic>().unwrap();
 
-        let all_formulas_on_path = path.nodes.iter().cloned()
-            .map(|node| node.formula).collect_vec();
+        let all_formulas_on_path = path.nodes.iter()
+            .map(|node| node.formula.as_ref().clone()).collect_vec();
 
         let all_args_on_path = all_formulas_on_path.iter()
             .flat_map(|formula| formula.get_all_predicate_arguments().into_iter())
@@ -157,12 +162,39 @@ impl ForAllQuantifierRule
         let args_that_definitely_exists = get_args_that_definitely_exists(&all_formulas_on_path, extras.possible_world);
         let variable_domain_check = |a : &&PredicateArgument| args_that_definitely_exists.iter().any(|d| d==*a);
 
-        let object_names = all_args_on_path.iter()
+        let mut object_names = all_args_on_path.iter()
             .filter(|a| a.is_instantiated() || a.is_free_object())
             .filter(|a| logic.domain_type == ConstantDomain || variable_domain_check(a))
             .map(|a| a.object_name.clone())
             .collect::<BTreeSet<SmolStr>>();
 
+        //the terms on the path are used first, the herbrand universe is used once the quantifier is applied again
+        let was_already_applied_on_path = path.nodes.iter()
+            .filter_map(|path_node| factory.tree.get_node_with_id(path_node.id))
+            .any(|path_node| path_node.spawner_node_id == Some(node.id));
+
+        if logic.domain_type == ConstantDomain && was_already_applied_on_path
+        {
+            let function_symbols = all_args_on_path.iter()
+                .filter_map(|a| a.function_term.as_ref())
+                .map(|function_term| (function_term.function_name.clone(), function_term.args.len()))
+                .collect::<BTreeSet<(SmolStr, usize)>>();
+
+            let objects = all_args_on_path.iter()
+                .filter(|a| (a.is_instantiated() || a.is_free_object()) && a.get_term_depth() == 0)
+                .map(|a| a.object_name.clone())
+                .collect::<BTreeSet<SmolStr>>();
+
+            let max_term_depth = factory.problem_flags.limits.max_term_depth.unwrap_or(MAX_FUNCTION_TERM_DEPTH);
+            object_names.append(&mut get_herbrand_universe(&objects, &function_symbols, max_term_depth));
+
+            if !objects.is_empty() && !function_symbols.is_empty()
+            {
+                //deeper terms were left out, thus an open path is not a countermodel
+                factory.set_reached_limit(ProofLimit::MaxTermDepth);
+            }
+        }
+
         for object_name in object_names
         {
             let (binded_p, binded_x) = p.binded(x, object_name, extras);
@@ -171,6 +203,34 @@ impl ForAllQuantifierRule
     }
 }
 
+//the ground terms built from the objects and the function symbols, up to the max depth, eg: a, f(a), f(f(a))
+fn get_herbrand_universe(objects : &BTreeSet<SmolStr>, function_symbols : &BTreeSet<(SmolStr, usize)>, max_term_depth : usize) -> BTreeSet<SmolStr>
+{
+    let mut terms = objects.iter().cloned().collect_vec();
+    let mut number_of_shallower_terms = 0usize;
+
+    for _depth in 1..=max_term_depth
+    {
+        let mut deeper_terms : Vec<SmolStr> = vec![];
+        for (function_name, arity) in function_symbols
+        {
+            //at least one of the args must be one of the terms added on the previous depth
+            let args_combinations = (0..*arity).map(|_| terms.iter()).multi_cartesian_product()
+                .filter(|args| args.iter().any(|arg| terms[number_of_shallower_terms..].contains(arg)));
+
+            for args in args_combinations
+            {
+                deeper_terms.push(format_smolstr!("{}({})", function_name, args.iter().join(",")));
+            }
+        }
+
+        number_of_shallower_terms = terms.len();
+        terms.append(&mut deeper_terms);
+    }
+
+    return terms.into_iter().collect();
+}
+
 pub fn get_args_that_definitely_exists(all_formulas_on_path : &Vec<Formula>, possible_world : PossibleWorld) -> BTreeSet<PredicateArgument>
 {
     let filter_map_callback = |formula : &Formula|
@@ -1,10 +1,9 @@
 use std::collections::BTreeSet;
 use crate::formula::Formula::{DefinitelyExists, Equals, Non};
-use crate::formula::{Formula, FormulaExtras, PredicateArgument};
+use crate::formula::{Formula, FormulaExtras, PredicateArgument, PredicateArguments};
 use crate::formula::Sign::{Minus, Plus};
-use crate::logic::first_order_logic::{FirstOrderLogic, VariableDomainFlags};
+use crate::logic::first_order_logic::FirstOrderLogic;
 use crate::logic::{Logic, LogicRule, LogicRuleResult, LogicRuleResultCollection};
-use crate::logic::first_order_logic::FirstOrderLogicDomainType::VariableDomain;
 use crate::logic::first_order_logic::FirstOrderLogicIdentityType::NecessaryIdentity;
 use crate::logic::first_order_logic::forall_quantifier_rule::get_args_that_definitely_exists;
 use crate::logic::first_order_logic::predicate_args_with_equivalences::create_equality_owned_formulas_filtering_lambda;
@@ -22,7 +21,7 @@ impl LogicRule for HelperQuantifierRules
         let logic_pointer = factory.get_logic().clone();
         let logic = logic_pointer.cast_to::<FirstOrderLogic>().unwrap();
 
-        match &node.formula
+        match node.formula.as_ref()
         {
             Equals(_, _, extras) if extras.sign == Plus =>
             {
@@ -41,7 +40,12 @@ impl LogicRule for HelperQuantifierRules
                 if x == y
                 {
                     //this is an object that is not equal to self ~(x=x)? forcing contradiction by stating x=x.
-                    results.push(self.create_subtree_with_x_equals_to_x_node(factory, node, x, extras));
+                    results.push(self.create_subtree_with_x_equals_to_y_node(factory, node, x, x, extras));
+                }
+                else if self.are_congruent_function_terms(factory, node, x, y)
+                {
+                    //~(f(a)=f(b)), but a=b? forcing contradiction by stating f(a)=f(b).
+                    results.push(self.create_subtree_with_x_equals_to_y_node(factory, node, x, y, extras));
                 }
 
                 if logic.get_name().is_modal_logic() && (logic.identity_type == NecessaryIdentity || logic.get_name().is_intuitionistic_logic())
@@ -68,7 +72,12 @@ impl LogicRule for HelperQuantifierRules
                 if x == y
                 {
                     //this is an object that is not equal to self (x=x)-? forcing contradiction by stating (x=x)+.
-                    results.push(self.create_subtree_with_x_equals_to_x_node(factory, node, x, extras));
+                    results.push(self.create_subtree_with_x_equals_to_y_node(factory, node, x, x, extras));
+                }
+                else if self.are_congruent_function_terms(factory, node, x, y)
+                {
+                    //(f(a)=f(b))-, but a=b? forcing contradiction by stating (f(a)=f(b))+.
+                    results.push(self.create_subtree_with_x_equals_to_y_node(factory, node, x, y, extras));
                 }
 
                 if logic.get_name().is_modal_logic() && (logic.identity_type == NecessaryIdentity || logic.get_name().is_intuitionistic_logic())
@@ -80,8 +89,7 @@ impl LogicRule for HelperQuantifierRules
 
             DefinitelyExists(_, extras) if extras.sign == Plus =>
             {
-                if logic.get_name().is_modal_logic() && logic.domain_type ==
-                    VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:true })
+                if logic.get_name().is_modal_logic() && logic.domain_type.has_domain_increasing_constraint()
                 {
                     //inherit 𝔈x to all possible worlds by stating □𝔈x
                     results.push(node.inherit_on_all_adjacent_possible_worlds(logic, factory));
@@ -90,8 +98,7 @@ impl LogicRule for HelperQuantifierRules
 
             Non(box DefinitelyExists(_, _), extras) if extras.sign == Plus =>
             {
-                if logic.get_name().is_modal_logic() && logic.domain_type ==
-                    VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:true })
+                if logic.get_name().is_modal_logic() && logic.domain_type.has_domain_increasing_constraint()
                 {
                     //inherit !𝔈x to all possible worlds by stating □!𝔈x
                     results.push(node.inherit_on_all_adjacent_possible_worlds(logic, factory));
@@ -100,8 +107,7 @@ impl LogicRule for HelperQuantifierRules
 
             Non(box DefinitelyExists(_, _), extras) if extras.sign == Minus =>
             {
-                if logic.get_name().is_modal_logic() && logic.domain_type ==
-                    VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:true })
+                if logic.get_name().is_modal_logic() && logic.domain_type.has_domain_increasing_constraint()
                 {
                     //inherit !𝔈x- to all possible worlds by stating □!𝔈x-
                     results.push(node.inherit_on_all_adjacent_possible_worlds(logic, factory));
@@ -110,8 +116,7 @@ impl LogicRule for HelperQuantifierRules
 
             DefinitelyExists(_, extras) if extras.sign == Minus =>
             {
-                if logic.get_name().is_modal_logic() && logic.domain_type ==
-                    VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:true })
+                if logic.get_name().is_modal_logic() && logic.domain_type.has_domain_increasing_constraint()
                 {
                     //inherit 𝔈x- to all possible worlds by stating □𝔈x-
                     results.push(node.inherit_on_all_adjacent_possible_worlds(logic, factory));
@@ -127,18 +132,19 @@ impl LogicRule for HelperQuantifierRules
 
 impl HelperQuantifierRules
 {
-    fn create_subtree_with_x_equals_to_x_node(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode, x : &PredicateArgument, extras : &FormulaExtras) -> LogicRuleResult
+    fn create_subtree_with_x_equals_to_y_node(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode, x : &PredicateArgument, y : &PredicateArgument, extras : &FormulaExtras) -> LogicRuleResult
     {
         let logic_pointer = factory.get_logic().clone();
         let logic = logic_pointer.cast_to::<FirstOrderLogic>().unwrap();
 
-        let x_equals_x = Equals(x.clone(), x.clone(), extras.with_sign(Plus));
-        let x_equals_x_node = factory.new_node(x_equals_x);
+        let x_equals_y = Equals(x.clone(), y.clone(), extras.with_sign(Plus));
+        let x_equals_y_node = factory.new_node(x_equals_y);
 
-        if matches!(logic.domain_type, VariableDomain(..))
+        if logic.domain_type.has_negativity_constraint()
         {
+            //with the negativity constraint, x=x is true only if x exists
             let all_formulas_on_path = factory.tree.get_paths_that_goes_through_node(node).into_iter()
-                .flat_map(|path| path.nodes.into_iter().map(|node| node.formula))
+                .flat_map(|path| path.nodes.into_iter().map(|node| node.formula.as_ref().clone()))
                 .collect::<Vec<Formula>>();
 
             let args_that_definitely_exists = get_args_that_definitely_exists(&all_formulas_on_path, extras.possible_world);
@@ -148,13 +154,24 @@ impl HelperQuantifierRules
             }
         }
 
-        return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(x_equals_x_node));
+        return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(x_equals_y_node));
+    }
+
+    //f(a) and f(b) are equal on every path, given the equalities of their arguments, eg: a=b
+    fn are_congruent_function_terms(&self, factory : &RuleApplyFactory, node : &ProofTreeNode, x : &PredicateArgument, y : &PredicateArgument) -> bool
+    {
+        if x.function_term.is_none() || y.function_term.is_none() { return false }
+
+        let x_as_args = PredicateArguments::new(vec![x.clone()]);
+        let paths = factory.tree.get_paths_that_goes_through_node(node);
+        return !paths.is_empty() && paths.iter().all(|path|
+            x_as_args.with_equivalences(path, node.formula.get_possible_world())[0].iter().any(|z| z==y));
     }
 
     fn create_subtree_with_missing_transitive_nodes(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode, extras : &FormulaExtras) -> LogicRuleResult
     {
         let equalities = factory.tree.get_paths_that_goes_through_node(node).into_iter()
-            .flat_map(|path| path.nodes.into_iter().map(|node| node.formula))
+            .flat_map(|path| path.nodes.into_iter().map(|node| node.formula.as_ref().clone()))
             .filter(|formula| formula.get_possible_world() == extras.possible_world)
             .filter_map(create_equality_owned_formulas_filtering_lambda())
             .collect::<BTreeSet<(PredicateArgument, PredicateArgument)>>();
@@ -24,7 +24,7 @@ impl LogicRule for IntuitionisticQuantifierRules
     {
         let modality = factory.get_logic().get_modality_ref().unwrap();
 
-        match &node.formula
+        match node.formula.as_ref()
         {
             ForAll(_, _, extras)
             if extras.sign == Plus && !self.was_modality_already_applied(factory, node) =>
@@ -1,8 +1,7 @@
 use std::collections::BTreeSet;
 use itertools::Itertools;
-use crate::formula::{Formula, PossibleWorld, PredicateArgument, PredicateArguments, Sign};
+use crate::formula::{Formula, FunctionTerm, PossibleWorld, PredicateArgument, PredicateArguments, Sign};
 use crate::formula::Formula::{Equals, Non};
-use crate::logic::first_order_logic::FirstOrderLogicDomainType::ConstantDomain;
 use crate::logic::first_order_logic::forall_quantifier_rule::get_args_that_definitely_exists;
 use crate::tree::path::ProofTreePath;
 
@@ -22,7 +21,7 @@ impl PredicateArguments
         if self.is_empty() { return vec![] };
 
         let all_formulas_on_path = path.nodes.iter()
-            .map(|node| node.formula.clone()).collect_vec();
+            .map(|node| node.formula.as_ref().clone()).collect_vec();
 
         let all_equivalences_on_path = all_formulas_on_path.iter()
             .filter(|formula| formula.get_possible_world() == possible_world)
@@ -31,26 +30,75 @@ impl PredicateArguments
 
         let args_that_definitely_exists = get_args_that_definitely_exists(&all_formulas_on_path, possible_world);
 
-        let mut args_with_equivalences: Vec<BTreeSet<PredicateArgument>> = vec![];
+        //terms built by congruence are bounded by the deepest term on the path, eg: a=f(a) does not yield f(f(f(a)))
+        let max_term_depth = if self.iter().any(|x| x.get_term_depth() > 0)
+        {
+            all_formulas_on_path.iter()
+                .flat_map(|formula| formula.get_all_predicate_arguments().into_iter())
+                .map(|arg| arg.get_term_depth()).max().unwrap_or_default()
+        }
+        else { 0 };
 
-        for x in self.iter()
+        let equivalences_on_path = EquivalencesOnPath
         {
-            let mut x_equivalence_set: BTreeSet<PredicateArgument> = BTreeSet::new();
-            x_equivalence_set.insert(x.deinstantiated());
-
-            let mut equivalent_ys = all_equivalences_on_path.iter()
-                .filter(|(y, z)| x==*y || x==*z)
-                .map(|(y, z)| if x==*y { (*z).clone() } else { (*y).clone() })
-                .filter(|a| path.domain_type == ConstantDomain ||
-                    args_that_definitely_exists.iter().any(|d| d==a))
-                .map(|arg| arg.deinstantiated())
-                .collect::<BTreeSet<PredicateArgument>>();
-            x_equivalence_set.append(&mut equivalent_ys);
-
-            args_with_equivalences.push(x_equivalence_set);
+            all_equivalences_on_path, args_that_definitely_exists, max_term_depth,
+            has_negativity_constraint: path.domain_type.has_negativity_constraint(),
+        };
+
+        return self.iter().map(|x| equivalences_on_path.get_equivalences(x)).collect();
+    }
+}
+
+struct EquivalencesOnPath<'a>
+{
+    all_equivalences_on_path : BTreeSet<(&'a PredicateArgument, &'a PredicateArgument)>,
+    args_that_definitely_exists : BTreeSet<PredicateArgument>,
+    has_negativity_constraint : bool,
+    max_term_depth : usize,
+}
+
+impl EquivalencesOnPath<'_>
+{
+    fn get_equivalences(&self, x : &PredicateArgument) -> BTreeSet<PredicateArgument>
+    {
+        let mut x_equivalence_set = self.get_direct_equivalences(x);
+
+        //congruence over function terms: if a=b then f(a)=f(b)
+        if let Some(function_term) = x.deinstantiated().function_term
+        {
+            let args_equivalences = function_term.args.iter()
+                .map(|arg| self.get_equivalences(arg).into_iter().collect_vec())
+                .collect_vec();
+
+            for args in args_equivalences.into_iter().map(|equivalences| equivalences.into_iter()).multi_cartesian_product()
+            {
+                let function_name = function_term.function_name.clone();
+                let y = PredicateArgument::with_function_term(FunctionTerm { function_name, args });
+                if y.get_term_depth() <= self.max_term_depth
+                {
+                    x_equivalence_set.append(&mut self.get_direct_equivalences(&y));
+                }
+            }
         }
 
-        return args_with_equivalences;
+        return x_equivalence_set;
+    }
+
+    fn get_direct_equivalences(&self, x : &PredicateArgument) -> BTreeSet<PredicateArgument>
+    {
+        let mut x_equivalence_set: BTreeSet<PredicateArgument> = BTreeSet::new();
+        x_equivalence_set.insert(x.deinstantiated());
+
+        let mut equivalent_ys = self.all_equivalences_on_path.iter()
+            .filter(|(y, z)| x==*y || x==*z)
+            .map(|(y, z)| if x==*y { (*z).clone() } else { (*y).clone() })
+            .filter(|a| !self.has_negativity_constraint ||
+                self.args_that_definitely_exists.iter().any(|d| d==a))
+            .map(|arg| arg.deinstantiated())
+            .collect::<BTreeSet<PredicateArgument>>();
+        x_equivalence_set.append(&mut equivalent_ys);
+
+        return x_equivalence_set;
     }
 }
 
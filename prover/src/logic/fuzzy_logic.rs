@@ -1,9 +1,10 @@
 use std::any::Any;
 use std::collections::BTreeSet;
 use box_macro::bx;
+use itertools::Itertools;
 use smol_str::{format_smolstr, SmolStr, ToSmolStr};
-use crate::formula::Formula::{And, Atomic, BiImply, GreaterOrEqualThan, Imply, LessThan, Non, Or};
-use crate::formula::{FuzzyTag, FuzzyTags};
+use crate::formula::Formula::{And, Atomic, BiImply, Comment, DefinitelyExists, Equals, Exists, ForAll, GreaterOrEqualThan, Imply, LessThan, Non, Or};
+use crate::formula::{FormulaExtras, FuzzyTag, FuzzyTags, PredicateArguments};
 use crate::formula::Sign::{Minus, Plus};
 use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult};
 use crate::logic::common_modal_logic::ModalityRef;
@@ -14,6 +15,8 @@ use crate::semantics::Semantics;
 use crate::tree::node::ProofTreeNode;
 use crate::tree::subtree::ProofSubtree;
 
+pub mod t_norm_fuzzy_logic;
+
 pub struct LukasiewiczFuzzyLogic {}
 
 impl Logic for LukasiewiczFuzzyLogic
@@ -48,56 +51,89 @@ impl Logic for LukasiewiczFuzzyLogic
     fn get_modality_ref(&self) -> Option<ModalityRef> { None }
 }
 
+//+A,x means that A is at most x away from truth (1-v(A) <= x), while -A,x means more than x away.
+//since 1-v(¬A) = v(A), "at least x away" is written as +¬A,1-x, so every rule works on + formulas only.
+//the tags of a quantified formula are shared by all its instances: ∀ is the supremum of distances, ∃ the infimum
 struct LukasiewiczFuzzyLogicRules {}
 
 impl LogicRule for LukasiewiczFuzzyLogicRules
 {
     fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
-            Non(box Non(box p, _), extras) =>
+            LessThan(..) | GreaterOrEqualThan(..) | Comment(..) => LogicRuleResult::Empty,
+
+            //quantifiers, existence and identity are handled by first order logic rules
+            Exists(..) | ForAll(..) | DefinitelyExists(..) | Equals(..) => LogicRuleResult::Empty,
+
+            p if p.get_sign() == Minus =>
             {
-                let tagged_p = p.with_sign(extras.sign).with_fuzzy_tags(extras.fuzzy_tags.clone());
-                let tagged_p_node = factory.new_node(tagged_p.clone());
+                let new_tag = self.create_new_fuzzy_tag(factory, node);
+
+                let non_p = Non(bx!(p.with_sign(Plus).with_fuzzy_tags(FuzzyTags::empty())), p.get_extras().with_sign(Plus));
+                let non_p_tags = self.one_minus(&FuzzyTags::new(vec![new_tag.clone()]));
+                let non_p_node = factory.new_node(non_p.with_fuzzy_tags(non_p_tags));
 
-                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(tagged_p_node));
+                let x_less_than_new_tag = LessThan(p.get_fuzzy_tags(), FuzzyTags::new(vec![new_tag]), FormulaExtras::empty());
+                let x_less_than_new_tag_node = factory.new_node(x_less_than_new_tag);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_vertical_nodes(vec![non_p_node, x_less_than_new_tag_node]));
             }
 
-            And(box p, box q, extras) if extras.sign == Plus =>
+            Atomic(p_name, extras) =>
             {
-                let plus_q = q.with_sign(Plus).with_fuzzy_tags(extras.fuzzy_tags.clone());
-                let plus_q_node = factory.new_node(plus_q);
+                let x = extras.fuzzy_tags.clone();
+                let mu = FuzzyTags::new(vec![FuzzyTag::new(get_mu_tag_name(p_name, &extras.predicate_args))]);
+
+                let x_greater_than_mu = GreaterOrEqualThan(x, mu, FormulaExtras::empty());
+                let x_greater_than_mu_node = factory.new_node(x_greater_than_mu);
 
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(x_greater_than_mu_node));
+            }
+
+            Non(box Atomic(p_name, atomic_extras), extras) =>
+            {
+                let x = extras.fuzzy_tags.clone();
+                let mu = FuzzyTags::new(vec![FuzzyTag::new(get_mu_tag_name(p_name, &atomic_extras.predicate_args))]);
+
+                let x_greater_than_one_minus_mu = GreaterOrEqualThan(x, self.one_minus(&mu), FormulaExtras::empty());
+                let x_greater_than_one_minus_mu_node = factory.new_node(x_greater_than_one_minus_mu);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(x_greater_than_one_minus_mu_node));
+            }
+
+            Non(box Non(box p, _), extras) =>
+            {
                 let plus_p = p.with_sign(Plus).with_fuzzy_tags(extras.fuzzy_tags.clone());
-                let plus_p_node = factory.new_node_with_subnode(plus_p, plus_q_node);
+                let plus_p_node = factory.new_node(plus_p);
 
                 return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(plus_p_node));
             }
 
-            And(box p, box q, extras) if extras.sign == Minus =>
+            And(box p, box q, extras) =>
             {
-                let minus_p = p.with_sign(Minus).with_fuzzy_tags(extras.fuzzy_tags.clone());
-                let minus_p_node = factory.new_node(minus_p);
+                let plus_q = q.with_sign(Plus).with_fuzzy_tags(extras.fuzzy_tags.clone());
+                let plus_q_node = factory.new_node(plus_q);
 
-                let minus_q = q.with_sign(Minus).with_fuzzy_tags(extras.fuzzy_tags.clone());
-                let minus_q_node = factory.new_node(minus_q);
+                let plus_p = p.with_sign(Plus).with_fuzzy_tags(extras.fuzzy_tags.clone());
+                let plus_p_node = factory.new_node_with_subnode(plus_p, plus_q_node);
 
-                return LogicRuleResult::Subtree(ProofSubtree::with_left_right_nodes(minus_p_node, minus_q_node));
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(plus_p_node));
             }
 
             Non(box And(box p, box q, _), extras) =>
             {
                 let non_p = Non(bx!(p.clone()), extras.clone());
-                let non_q = Non(bx!(q.clone()), extras.clone());
+                let non_p_node = factory.new_node(non_p);
 
-                let non_p_or_non_q = Or(bx!(non_p), bx!(non_q), extras.clone());
-                let non_p_or_non_q_node = factory.new_node(non_p_or_non_q);
+                let non_q = Non(bx!(q.clone()), extras.clone());
+                let non_q_node = factory.new_node(non_q);
 
-                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(non_p_or_non_q_node));
+                return LogicRuleResult::Subtree(ProofSubtree::with_left_right_nodes(non_p_node, non_q_node));
             }
 
-            Or(box p, box q, extras) if extras.sign == Plus =>
+            Or(box p, box q, extras) =>
             {
                 let plus_p = p.with_sign(Plus).with_fuzzy_tags(extras.fuzzy_tags.clone());
                 let plus_p_node = factory.new_node(plus_p);
@@ -108,64 +144,50 @@ impl LogicRule for LukasiewiczFuzzyLogicRules
                 return LogicRuleResult::Subtree(ProofSubtree::with_left_right_nodes(plus_p_node, plus_q_node));
             }
 
-            Or(box p, box q, extras) if extras.sign == Minus =>
-            {
-                let minus_q = q.with_sign(Minus).with_fuzzy_tags(extras.fuzzy_tags.clone());
-                let minus_q_node = factory.new_node(minus_q);
-
-                let minus_p = p.with_sign(Minus).with_fuzzy_tags(extras.fuzzy_tags.clone());
-                let minus_p_node = factory.new_node_with_subnode(minus_p, minus_q_node);
-
-                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(minus_p_node));
-            }
-
             Non(box Or(box p, box q, _), extras) =>
             {
-                let non_p = Non(bx!(p.clone()), extras.clone());
                 let non_q = Non(bx!(q.clone()), extras.clone());
+                let non_q_node = factory.new_node(non_q);
 
-                let non_p_and_non_q = And(bx!(non_p), bx!(non_q), extras.clone());
-                let non_p_and_non_q_node = factory.new_node(non_p_and_non_q);
+                let non_p = Non(bx!(p.clone()), extras.clone());
+                let non_p_node = factory.new_node_with_subnode(non_p, non_q_node);
 
-                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(non_p_and_non_q_node));
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(non_p_node));
             }
 
-            Imply(box p, box q, extras) if extras.sign == Plus =>
+            Imply(box p, box q, extras) =>
             {
+                //1-v(p→q) = max(0, v(p)-v(q)) <= x, that is, v(p) <= α and 1-v(q) <= x+1-α, for some α
                 let new_tag = self.create_new_fuzzy_tag(factory, node);
 
-                let minus_p_tags = FuzzyTags::empty().plus(new_tag.clone());
-                let minus_p = p.with_sign(Minus).with_fuzzy_tags(minus_p_tags);
-                let minus_p_node = factory.new_node(minus_p);
-
-                let plus_q_tags = extras.fuzzy_tags.plus(new_tag);
+                let plus_q_tags = self.add(&extras.fuzzy_tags, &FuzzyTags::new(vec![new_tag.clone()]));
                 let plus_q = q.with_sign(Plus).with_fuzzy_tags(plus_q_tags);
                 let plus_q_node = factory.new_node(plus_q);
 
-                return LogicRuleResult::Subtree(ProofSubtree::with_left_right_nodes(minus_p_node, plus_q_node));
+                let non_p_tags = self.one_minus(&FuzzyTags::new(vec![new_tag]));
+                let non_p = Non(bx!(p.clone()), extras.with_fuzzy_tags(non_p_tags));
+                let non_p_node = factory.new_node_with_subnode(non_p, plus_q_node);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(non_p_node));
             }
 
-            Imply(box p, box q, extras) if extras.sign == Minus =>
+            Non(box Imply(box p, box q, _), extras) =>
             {
+                //max(0, v(p)-v(q)) >= 1-x, that is, either x >= 1, or 1-v(p) <= α and v(q) <= x-α, for some α
+                let x = extras.fuzzy_tags.clone();
                 let new_tag = self.create_new_fuzzy_tag(factory, node);
 
-                let minus_q_tags = extras.fuzzy_tags.plus(new_tag.clone());
-                let minus_q = q.with_sign(Minus).with_fuzzy_tags(minus_q_tags);
-                let minus_q_node = factory.new_node(minus_q);
+                let x_greater_than_one = GreaterOrEqualThan(x.clone(), FuzzyTags::new(vec![FuzzyTag::one()]), FormulaExtras::empty());
+                let x_greater_than_one_node = factory.new_node(x_greater_than_one);
 
-                let plus_p_tags = FuzzyTags::empty().plus(new_tag);
-                let plus_p = p.with_sign(Plus).with_fuzzy_tags(plus_p_tags);
-                let plus_p_node = factory.new_node_with_subnode(plus_p, minus_q_node);
+                let non_q_tags = self.subtract(&x, &FuzzyTags::new(vec![new_tag.clone()]));
+                let non_q = Non(bx!(q.clone()), extras.with_fuzzy_tags(non_q_tags));
+                let non_q_node = factory.new_node(non_q);
 
-                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(plus_p_node));
-            }
-
-            Non(box p_imply_q@Imply(..), extras) =>
-            {
-                let p_imply_q_minus = p_imply_q.with_sign(extras.sign * Minus);
-                let p_imply_q_minus_node = factory.new_node(p_imply_q_minus);
+                let plus_p = p.with_sign(Plus).with_fuzzy_tags(FuzzyTags::new(vec![new_tag]));
+                let plus_p_node = factory.new_node_with_subnode(plus_p, non_q_node);
 
-                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(p_imply_q_minus_node));
+                return LogicRuleResult::Subtree(ProofSubtree::with_left_right_nodes(x_greater_than_one_node, plus_p_node));
             }
 
             BiImply(box p, box q, extras) =>
@@ -189,93 +211,85 @@ impl LogicRule for LukasiewiczFuzzyLogicRules
                 return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(non_conjunction_node));
             }
 
-            Atomic(p_name, extras) if extras.sign == Plus =>
-            {
-                let mu_tag = FuzzyTag::new(format_smolstr!("µ:{}", p_name));
-                let new_extras = extras.with_fuzzy_tags(extras.fuzzy_tags.plus(mu_tag.clone()));
-
-                let x = extras.fuzzy_tags.clone();
-                let y = FuzzyTags::new(vec![mu_tag]);
-                let x_greater_than_y = GreaterOrEqualThan(x.clone(), y.clone(), new_extras.to_formula_extras());
-                let x_greater_than_y_node = factory.new_node(x_greater_than_y);
+            _ => LogicRuleResult::Empty
+        }
+    }
+}
 
-                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(x_greater_than_y_node));
-            }
+impl LukasiewiczFuzzyLogicRules
+{
+    pub fn create_new_fuzzy_tag(&self, factory : &RuleApplyFactory, node : &ProofTreeNode) -> FuzzyTag
+    {
+        return create_new_fuzzy_tags(factory, node, 1).remove(0);
+    }
 
-            Atomic(p_name, extras) if extras.sign == Minus =>
+    //x + y, simplifying terms like 0, 1 - 1 or α - α
+    fn add(&self, x : &FuzzyTags, y : &FuzzyTags) -> FuzzyTags
+    {
+        let mut output_tags : Vec<FuzzyTag> = vec![];
+        for tag in x.iter().chain(y.iter()).filter(|tag| **tag != FuzzyTag::zero())
+        {
+            if let Some(index) = output_tags.iter().position(|output_tag| output_tag == tag && output_tag.sign != tag.sign)
             {
-                let mu_tag = FuzzyTag::new(format_smolstr!("µ:{}", p_name));
-                let new_extras = extras.with_fuzzy_tags(extras.fuzzy_tags.plus(mu_tag.clone()));
-
-                let x = extras.fuzzy_tags.clone();
-                let y = FuzzyTags::new(vec![mu_tag]);
-                let x_less_than_y = LessThan(x.clone(), y.clone(), new_extras.to_formula_extras());
-                let x_less_than_y_node = factory.new_node(x_less_than_y);
-
-                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(x_less_than_y_node));
+                output_tags.remove(index);
             }
-
-            Non(box Atomic(p_name, _), extras) if extras.sign == Plus =>
+            else
             {
-                let minus_mu_tag = FuzzyTag { object_name:format_smolstr!("µ:{}", p_name), sign:Minus };
-                let one_minus_mu_tags = vec![FuzzyTag::one(), minus_mu_tag];
-                let new_extras = extras.with_fuzzy_tags(extras.fuzzy_tags.plus_vec(&one_minus_mu_tags));
-
-                let x = extras.fuzzy_tags.clone();
-                let y = FuzzyTags::new(one_minus_mu_tags);
-                let x_greater_than_y = GreaterOrEqualThan(x.clone(), y.clone(), new_extras);
-                let x_greater_than_y_node = factory.new_node(x_greater_than_y);
-
-                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(x_greater_than_y_node));
+                output_tags.push(tag.clone());
             }
+        }
 
-            Non(box Atomic(p_name, _), extras) if extras.sign == Minus =>
-            {
-                let minus_mu_tag = FuzzyTag { object_name:format_smolstr!("µ:{}", p_name), sign:Minus };
-                let one_minus_mu_tags = vec![FuzzyTag::one(), minus_mu_tag];
-                let new_extras = extras.with_fuzzy_tags(extras.fuzzy_tags.plus_vec(&one_minus_mu_tags));
-
-                let x = extras.fuzzy_tags.clone();
-                let y = FuzzyTags::new(one_minus_mu_tags);
-                let x_less_than_y = LessThan(x.clone(), y.clone(), new_extras);
-                let x_less_than_y_node = factory.new_node(x_less_than_y);
+        return FuzzyTags::new(output_tags);
+    }
 
-                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(x_less_than_y_node));
-            }
+    fn subtract(&self, x : &FuzzyTags, y : &FuzzyTags) -> FuzzyTags
+    {
+        let minus_y = y.iter().map(|tag| FuzzyTag { object_name:tag.object_name.clone(), sign:tag.sign * Minus }).collect();
+        return self.add(x, &FuzzyTags::new(minus_y));
+    }
 
-            _ => LogicRuleResult::Empty
-        }
+    fn one_minus(&self, x : &FuzzyTags) -> FuzzyTags
+    {
+        return self.subtract(&FuzzyTags::new(vec![FuzzyTag::one()]), x);
     }
 }
 
-impl LukasiewiczFuzzyLogicRules
+//on first order logic, every instance of a predicate has its own truth value, eg: µ:P[a], µ:P[b]
+fn get_mu_tag_name(p_name : &SmolStr, predicate_args : &PredicateArguments) -> SmolStr
 {
-    pub fn create_new_fuzzy_tag(&self, factory : &RuleApplyFactory, node : &ProofTreeNode) -> FuzzyTag
-    {
-        let used_names = factory.tree.get_paths_that_goes_through_node(node).into_iter()
-            .flat_map(|path| path.nodes.into_iter().map(|node| node.formula))
-            .flat_map(|formula| formula.get_fuzzy_tags().into_iter())
-            .map(|fuzzy_tag| fuzzy_tag.object_name)
-            .collect::<BTreeSet<SmolStr>>();
-
-        let mut char = 'α';
-        let mut aux = 0u64;
-        loop
-        {
-            let name = if aux==0 { char.to_smolstr() }
-            else { format_smolstr!("{}{}", char, aux) };
+    if predicate_args.is_empty() { return format_smolstr!("µ:{}", p_name) };
 
-            if !used_names.contains(&name)
-            {
-                return FuzzyTag::new(name);
-            }
+    let object_names = predicate_args.iter().map(|arg| arg.object_name.clone()).join(",");
+    return format_smolstr!("µ:{}[{}]", p_name, object_names);
+}
 
-            char = match char
-            {
-                'α' => 'β', 'β' => 'γ', 'γ' => 'ρ', 'ρ' => 'σ',
-                'σ' => 'τ', 'τ' => 'φ', 'φ' => 'ψ', 'ψ' => 'ω',
-                _ => { aux += 1; 'α' }
-            };
+fn create_new_fuzzy_tags(factory : &RuleApplyFactory, node : &ProofTreeNode, number_of_tags : usize) -> Vec<FuzzyTag>
+{
+    let used_names = factory.tree.get_paths_that_goes_through_node(node).into_iter()
+        .flat_map(|path| path.nodes.into_iter().map(|node| node.formula))
+        .flat_map(|formula| formula.get_fuzzy_tags().into_iter())
+        .map(|fuzzy_tag| fuzzy_tag.object_name)
+        .collect::<BTreeSet<SmolStr>>();
+
+    let mut new_tags = Vec::new();
+    let mut char = 'α';
+    let mut aux = 0u64;
+    loop
+    {
+        let name = if aux==0 { char.to_smolstr() }
+        else { format_smolstr!("{}{}", char, aux) };
+
+        if !used_names.contains(&name)
+        {
+            new_tags.push(FuzzyTag::new(name));
+            if new_tags.len() == number_of_tags { return new_tags }
         }
+
+        char = match char
+        {
+            'α' => 'β', 'β' => 'γ', 'γ' => 'ρ', 'ρ' => 'σ',
+            'σ' => 'τ', 'τ' => 'φ', 'φ' => 'ψ', 'ψ' => 'ω',
+            _ => { aux += 1; 'α' }
+        };
     }
 }
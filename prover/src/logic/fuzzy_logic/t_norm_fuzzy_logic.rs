@@ -0,0 +1,279 @@
+use std::any::Any;
+use box_macro::bx;
+use smol_str::format_smolstr;
+use crate::formula::Formula::{And, Atomic, BiImply, Comment, GreaterOrEqualThan, Imply, LessThan, Non, Or};
+use crate::formula::{Formula, FormulaExtras, FuzzyTag, FuzzyTags};
+use crate::formula::Sign::{Minus, Plus};
+use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult};
+use crate::logic::common_modal_logic::ModalityRef;
+use crate::logic::fuzzy_logic::create_new_fuzzy_tags;
+use crate::logic::rule_apply_factory::RuleApplyFactory;
+use crate::parser::token_types::TokenTypeID;
+use crate::semantics::fuzzy_logic_semantics::FuzzyLogicSemantics;
+use crate::semantics::Semantics;
+use crate::tree::node::ProofTreeNode;
+use crate::tree::subtree::ProofSubtree;
+
+//check out book chapter 11: ∧ and ∨ are min and max, like on Lukasiewicz, but → is the residuum of
+//the Gödel (minimum) or of the Product t-norm and ¬A is A→0. Fuzzy tags measure the distance from truth:
+//on Gödel it is 1-v, on Product it is -log(v), where v=0 is mapped to 1 and positive values to [0,1),
+//that's fine since the constraints between positive values are invariant to scaling.
+pub struct TNormFuzzyLogic
+{
+    pub is_product : bool
+}
+
+impl TNormFuzzyLogic
+{
+    pub fn godel() -> TNormFuzzyLogic { TNormFuzzyLogic { is_product:false } }
+    pub fn product() -> TNormFuzzyLogic { TNormFuzzyLogic { is_product:true } }
+}
+
+impl Logic for TNormFuzzyLogic
+{
+    fn get_name(&self) -> LogicName
+    {
+        return if self.is_product { LogicName::of("ProductFuzzyLogic") }
+        else { LogicName::of("GodelFuzzyLogic") };
+    }
+
+    fn as_any(&self) -> &dyn Any { self }
+
+    fn get_semantics(&self) -> Box<dyn Semantics>
+    {
+        return Box::new(FuzzyLogicSemantics {});
+    }
+
+    fn get_parser_syntax(&self) -> Vec<TokenTypeID>
+    {
+        return vec!
+        [
+            TokenTypeID::AtomicWithoutArgs,
+            TokenTypeID::Non, TokenTypeID::And, TokenTypeID::Or,
+            TokenTypeID::Imply, TokenTypeID::BiImply,
+            TokenTypeID::OpenParenthesis, TokenTypeID::ClosedParenthesis
+        ];
+    }
+
+    fn get_rules(&self) -> LogicRuleCollection
+    {
+        return LogicRuleCollection::of(vec!
+        [
+            Box::new(TNormFuzzyLogicRules { is_product:self.is_product })
+        ]);
+    }
+
+    fn get_modality_ref(&self) -> Option<ModalityRef> { None }
+}
+
+//unlike on Lukasiewicz, +A,x means that A is exactly x away from truth, while -A,x means more than x away
+struct TNormFuzzyLogicRules
+{
+    is_product : bool
+}
+
+impl LogicRule for TNormFuzzyLogicRules
+{
+    fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
+    {
+        return match node.formula.as_ref()
+        {
+            LessThan(..) | GreaterOrEqualThan(..) | Comment(..) => LogicRuleResult::Empty,
+
+            p if p.get_sign() == Minus =>
+            {
+                let alpha = self.create_new_fuzzy_tag(factory, node);
+
+                let plus_p = p.with_sign(Plus).with_fuzzy_tags(alpha.clone());
+                let plus_p_node = factory.new_node(plus_p);
+
+                let x_less_than_alpha_node = factory.new_node(self.less_than(&p.get_fuzzy_tags(), &alpha));
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_vertical_nodes(vec![plus_p_node, x_less_than_alpha_node]));
+            }
+
+            Atomic(p_name, extras) =>
+            {
+                let x = extras.fuzzy_tags.clone();
+                let mu = FuzzyTags::new(vec![FuzzyTag::new(format_smolstr!("µ:{}", p_name))]);
+
+                let nodes = self.equals(&x, &mu).into_iter().map(|formula| factory.new_node(formula)).collect();
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_vertical_nodes(nodes));
+            }
+
+            Non(box p, extras) =>
+            {
+                let x = extras.fuzzy_tags.clone();
+                let alpha = self.create_new_fuzzy_tag(factory, node);
+                let plus_p_node = factory.new_node(p.with_sign(Plus).with_fuzzy_tags(alpha.clone()));
+
+                let left_node = self.new_vertical_nodes(factory, vec!
+                [
+                    self.greater_or_equal_than(&alpha, &self.one()),
+                    self.greater_or_equal_than(&self.zero(), &x),
+                ]);
+
+                let right_node = self.new_vertical_nodes(factory, vec!
+                [
+                    self.less_than(&alpha, &self.one()),
+                    self.greater_or_equal_than(&x, &self.one()),
+                ]);
+
+                let branches = ProofSubtree::with_left_right_nodes(left_node, right_node);
+                return LogicRuleResult::Subtree(self.new_subtree_with_branches(vec![plus_p_node], branches));
+            }
+
+            And(box p, box q, extras) =>
+            {
+                let x = extras.fuzzy_tags.clone();
+                let [alpha, beta] = self.create_new_fuzzy_tags_pair(factory, node);
+
+                let nodes = self.new_nodes(factory, vec!
+                [
+                    p.with_sign(Plus).with_fuzzy_tags(alpha.clone()),
+                    q.with_sign(Plus).with_fuzzy_tags(beta.clone()),
+                    self.greater_or_equal_than(&x, &alpha),
+                    self.greater_or_equal_than(&x, &beta),
+                ]);
+
+                let left_node = factory.new_node(self.greater_or_equal_than(&alpha, &x));
+                let right_node = factory.new_node(self.greater_or_equal_than(&beta, &x));
+
+                let branches = ProofSubtree::with_left_right_nodes(left_node, right_node);
+                return LogicRuleResult::Subtree(self.new_subtree_with_branches(nodes, branches));
+            }
+
+            Or(box p, box q, extras) =>
+            {
+                let x = extras.fuzzy_tags.clone();
+                let [alpha, beta] = self.create_new_fuzzy_tags_pair(factory, node);
+
+                let nodes = self.new_nodes(factory, vec!
+                [
+                    p.with_sign(Plus).with_fuzzy_tags(alpha.clone()),
+                    q.with_sign(Plus).with_fuzzy_tags(beta.clone()),
+                    self.greater_or_equal_than(&alpha, &x),
+                    self.greater_or_equal_than(&beta, &x),
+                ]);
+
+                let left_node = factory.new_node(self.greater_or_equal_than(&x, &alpha));
+                let right_node = factory.new_node(self.greater_or_equal_than(&x, &beta));
+
+                let branches = ProofSubtree::with_left_right_nodes(left_node, right_node);
+                return LogicRuleResult::Subtree(self.new_subtree_with_branches(nodes, branches));
+            }
+
+            Imply(box p, box q, extras) =>
+            {
+                let x = extras.fuzzy_tags.clone();
+                let [alpha, beta] = self.create_new_fuzzy_tags_pair(factory, node);
+
+                let nodes = self.new_nodes(factory, vec!
+                [
+                    p.with_sign(Plus).with_fuzzy_tags(alpha.clone()),
+                    q.with_sign(Plus).with_fuzzy_tags(beta.clone()),
+                ]);
+
+                //v(p) <= v(q), so v(p→q) = 1
+                let left_node = self.new_vertical_nodes(factory, vec!
+                [
+                    self.greater_or_equal_than(&alpha, &beta),
+                    self.greater_or_equal_than(&self.zero(), &x),
+                ]);
+
+                let branches = if !self.is_product
+                {
+                    //v(p) > v(q), so v(p→q) = v(q)
+                    let mut right_formulas = vec![self.less_than(&alpha, &beta)];
+                    right_formulas.append(&mut self.equals(&x, &beta));
+                    let right_node = self.new_vertical_nodes(factory, right_formulas);
+
+                    ProofSubtree::with_left_right_nodes(left_node, right_node)
+                }
+                else
+                {
+                    //v(p) > v(q) = 0, so v(p→q) = 0
+                    let middle_node = self.new_vertical_nodes(factory, vec!
+                    [
+                        self.less_than(&alpha, &beta),
+                        self.greater_or_equal_than(&beta, &self.one()),
+                        self.greater_or_equal_than(&x, &self.one()),
+                    ]);
+
+                    //v(p) > v(q) > 0, so v(p→q) = v(q)/v(p), that is -log(v(q)) - (-log(v(p)))
+                    let mut right_formulas = vec![self.less_than(&alpha, &beta), self.less_than(&beta, &self.one())];
+                    right_formulas.append(&mut self.equals(&x.plus_vec(&alpha.iter().cloned().collect()), &beta));
+                    let right_node = self.new_vertical_nodes(factory, right_formulas);
+
+                    ProofSubtree::with_left_middle_right_nodes(left_node, middle_node, right_node)
+                };
+
+                return LogicRuleResult::Subtree(self.new_subtree_with_branches(nodes, branches));
+            }
+
+            BiImply(box p, box q, extras) =>
+            {
+                let p_imply_q = Imply(bx!(p.clone()), bx!(q.clone()), extras.clone());
+                let q_imply_p = Imply(bx!(q.clone()), bx!(p.clone()), extras.clone());
+                let conjunction = And(bx!(p_imply_q), bx!(q_imply_p), extras.clone());
+                let conjunction_node = factory.new_node(conjunction);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(conjunction_node));
+            }
+
+            _ => LogicRuleResult::Empty
+        }
+    }
+}
+
+impl TNormFuzzyLogicRules
+{
+    fn create_new_fuzzy_tag(&self, factory : &RuleApplyFactory, node : &ProofTreeNode) -> FuzzyTags
+    {
+        return FuzzyTags::new(create_new_fuzzy_tags(factory, node, 1));
+    }
+
+    fn create_new_fuzzy_tags_pair(&self, factory : &RuleApplyFactory, node : &ProofTreeNode) -> [FuzzyTags; 2]
+    {
+        let mut fuzzy_tags = create_new_fuzzy_tags(factory, node, 2);
+        let beta = FuzzyTags::new(vec![fuzzy_tags.remove(1)]);
+        let alpha = FuzzyTags::new(fuzzy_tags);
+        return [alpha, beta];
+    }
+
+    fn zero(&self) -> FuzzyTags { FuzzyTags::new(vec![FuzzyTag::zero()]) }
+    fn one(&self) -> FuzzyTags { FuzzyTags::new(vec![FuzzyTag::one()]) }
+
+    fn less_than(&self, x : &FuzzyTags, y : &FuzzyTags) -> Formula
+    {
+        return LessThan(x.clone(), y.clone(), FormulaExtras::empty());
+    }
+
+    fn greater_or_equal_than(&self, x : &FuzzyTags, y : &FuzzyTags) -> Formula
+    {
+        return GreaterOrEqualThan(x.clone(), y.clone(), FormulaExtras::empty());
+    }
+
+    fn equals(&self, x : &FuzzyTags, y : &FuzzyTags) -> Vec<Formula>
+    {
+        return vec![self.greater_or_equal_than(x, y), self.greater_or_equal_than(y, x)];
+    }
+
+    fn new_nodes(&self, factory : &mut RuleApplyFactory, formulas : Vec<Formula>) -> Vec<ProofTreeNode>
+    {
+        return formulas.into_iter().map(|formula| factory.new_node(formula)).collect();
+    }
+
+    fn new_vertical_nodes(&self, factory : &mut RuleApplyFactory, formulas : Vec<Formula>) -> ProofTreeNode
+    {
+        let nodes = self.new_nodes(factory, formulas);
+        return *ProofSubtree::with_middle_vertical_nodes(nodes).middle.unwrap();
+    }
+
+    fn new_subtree_with_branches(&self, mut nodes : Vec<ProofTreeNode>, branches : ProofSubtree) -> ProofSubtree
+    {
+        let last_node = nodes.last_mut().unwrap();
+        (last_node.left, last_node.middle, last_node.right) = (branches.left, branches.middle, branches.right);
+        return ProofSubtree::with_middle_vertical_nodes(nodes);
+    }
+}
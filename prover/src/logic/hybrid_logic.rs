@@ -0,0 +1,257 @@
+use std::any::Any;
+use std::rc::Rc;
+use box_macro::bx;
+use smol_str::{SmolStr, ToSmolStr};
+use crate::formula::{Formula, FormulaExtras, PossibleWorld};
+use crate::formula::Formula::{At, Bind, Comment, Nominal, Non};
+use crate::graph::Graph;
+use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult};
+use crate::logic::common_modal_logic::{Modality, ModalLogicRules, ModalityRef};
+use crate::logic::propositional_logic::PropositionalLogicRules;
+use crate::logic::rule_apply_factory::RuleApplyFactory;
+use crate::parser::token_types::TokenTypeID;
+use crate::semantics::binary_logic_semantics::BinaryLogicSemantics;
+use crate::semantics::Semantics;
+use crate::tree::node::ProofTreeNode;
+use crate::tree::node_factory::ProofTreeNodeID;
+use crate::tree::path::ProofTreePath;
+use crate::tree::subtree::ProofSubtree;
+
+//K extended with nominals, atomic formulas true at exactly one world, with @_i A, true if A is true at the world named i,
+//and with ↓_i A, true if A is true once the current world is named i. Worlds named by the same nominal are merged.
+pub struct HybridLogic {}
+
+impl Logic for HybridLogic
+{
+    fn get_name(&self) -> LogicName { LogicName::of("HybridLogic") }
+    fn as_any(&self) -> &dyn Any { self }
+
+    fn get_semantics(&self) -> Box<dyn Semantics>
+    {
+        return Box::new(BinaryLogicSemantics {});
+    }
+
+    fn get_parser_syntax(&self) -> Vec<TokenTypeID>
+    {
+        return vec!
+        [
+            TokenTypeID::Nominal, TokenTypeID::AtomicWithoutArgs,
+            TokenTypeID::Non, TokenTypeID::And, TokenTypeID::Or,
+            TokenTypeID::Imply, TokenTypeID::BiImply,
+            TokenTypeID::Necessary, TokenTypeID::Possible,
+            TokenTypeID::At, TokenTypeID::Bind,
+            TokenTypeID::OpenParenthesis, TokenTypeID::ClosedParenthesis
+        ]
+    }
+
+    fn get_rules(&self) -> LogicRuleCollection
+    {
+        let modality = Rc::new(self.get_modality());
+        let base_rules = LogicRuleCollection::of(vec!
+        [
+            Box::new(PropositionalLogicRules {}),
+            Box::new(ModalLogicRules::new(modality.clone())),
+        ]);
+
+        let wrapper_rule = HybridLogicRules::wrap(base_rules, modality);
+        return LogicRuleCollection::of(vec![bx!(wrapper_rule)]);
+    }
+
+    fn get_modality_ref(&self) -> Option<ModalityRef>
+    {
+        return Some(ModalityRef::new(self.get_modality()));
+    }
+}
+
+impl HybridLogic
+{
+    pub fn get_modality(&self) -> Modality<HybridLogic>
+    {
+        return Modality
+        {
+            is_possibility_applicable: |_, _, _| true,
+            is_necessity_applicable: |_, _, _| true,
+            is_blocking_applicable: |_| false,
+            add_missing_graph_vertices: |_, _| {},
+        };
+    }
+}
+
+struct HybridLogicRules
+{
+    base_rules : LogicRuleCollection,
+    modality : Rc<Modality<HybridLogic>>,
+}
+
+impl HybridLogicRules
+{
+    fn wrap(base_rules : LogicRuleCollection, modality : Rc<Modality<HybridLogic>>) -> HybridLogicRules
+    {
+        return HybridLogicRules { base_rules, modality };
+    }
+}
+
+impl LogicRule for HybridLogicRules
+{
+    fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
+    {
+        if self.is_world_merged_on_all_paths(factory, node)
+        {
+            return self.move_to_identical_world(factory, node);
+        }
+
+        return match node.formula.as_ref()
+        {
+            //@ and ↓ are self-dual: ¬@_i A is @_i ¬A and ¬↓_i A is ↓_i ¬A
+            Non(box At(i, box p, _), extras) =>
+            {
+                let at_non_p = At(i.clone(), bx!(Non(bx!(p.clone()), extras.clone())), extras.clone());
+                let at_non_p_node = factory.new_node(at_non_p);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(at_non_p_node));
+            }
+
+            Non(box Bind(i, box p, _), extras) =>
+            {
+                let bind_non_p = Bind(i.clone(), bx!(Non(bx!(p.clone()), extras.clone())), extras.clone());
+                let bind_non_p_node = factory.new_node(bind_non_p);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(bind_non_p_node));
+            }
+
+            At(i, box p, extras) => self.apply_at(factory, node, i, &p.with_sign(extras.sign)),
+
+            Bind(i, box p, extras) =>
+            {
+                //the current world gets a name of its own, eg: the nominal w3 names the world w3
+                let current_world = extras.possible_world;
+                let world_nominal = current_world.to_smolstr();
+
+                let nominal = Nominal(world_nominal.clone(), FormulaExtras::empty()).in_world(current_world);
+                let nominal_node = factory.new_node(nominal);
+
+                let p_with_world_nominal = p.with_renamed_nominal(i, &world_nominal).with_sign(extras.sign).in_world(current_world);
+                let p_with_world_nominal_node = factory.new_node(p_with_world_nominal);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_vertical_nodes(vec![nominal_node, p_with_world_nominal_node]));
+            }
+
+            Nominal(i, extras) => self.apply_nominal(factory, node, i, extras.possible_world),
+
+            _ => self.base_rules.apply(factory, node)
+        }
+    }
+}
+
+impl HybridLogicRules
+{
+    fn apply_at(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode, i : &SmolStr, p : &Formula) -> LogicRuleResult
+    {
+        self.modality.initialize_graph_if_needed(factory);
+
+        let mut output_subtrees : Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
+
+        for path in self.get_open_paths_that_goes_through_node(factory, node)
+        {
+            let mut output_formulas : Vec<Formula> = Vec::new();
+
+            let named_world = match factory.modality_graph.get_world_named_by_nominal(&path, i)
+            {
+                Some(named_world) => named_world,
+                None =>
+                {
+                    //no world is named i on this path yet, thus i names a new world
+                    let new_world = factory.modality_graph.nodes().max().unwrap().fork();
+                    factory.modality_graph.add_node(new_world);
+                    output_formulas.push(Nominal(i.clone(), FormulaExtras::empty()).in_world(new_world));
+                    new_world
+                }
+            };
+
+            output_formulas.push(p.in_world(named_world));
+
+            let output_nodes = output_formulas.into_iter()
+                .filter(|formula| !path.nodes.iter().any(|node| *node.formula == *formula))
+                .map(|formula| factory.new_node(formula))
+                .collect::<Vec<ProofTreeNode>>();
+
+            if !output_nodes.is_empty()
+            {
+                output_subtrees.push((path.get_leaf_node_id(), ProofSubtree::with_middle_vertical_nodes(output_nodes)));
+            }
+        }
+
+        if output_subtrees.is_empty() { return LogicRuleResult::Empty }
+        return LogicRuleResult::Subtrees(output_subtrees);
+    }
+
+    //i at w, but i already names v: v and w are the same world, the bigger one is merged into the smaller one
+    fn apply_nominal(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode, i : &SmolStr, possible_world : PossibleWorld) -> LogicRuleResult
+    {
+        self.modality.initialize_graph_if_needed(factory);
+
+        let mut output_subtrees : Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
+
+        for path in self.get_open_paths_that_goes_through_node(factory, node)
+        {
+            let current_world = factory.modality_graph.get_identical_world_on_path(&path, possible_world);
+            if let Some(named_world) = factory.modality_graph.get_world_named_by_nominal(&path, i) && named_world != current_world
+            {
+                let (j, k) = (named_world.min(current_world), named_world.max(current_world));
+                let output_nodes = factory.add_branch_world_identity(&path, j, k);
+                output_subtrees.push((path.get_leaf_node_id(), ProofSubtree::with_middle_vertical_nodes(output_nodes)));
+            }
+        }
+
+        if output_subtrees.is_empty() { return LogicRuleResult::Empty }
+        return LogicRuleResult::Subtrees(output_subtrees);
+    }
+
+    //formulas reaching a world after it was merged into another world are moved there
+    fn is_world_merged_on_all_paths(&self, factory : &RuleApplyFactory, node : &ProofTreeNode) -> bool
+    {
+        if matches!(*node.formula, Comment(..)) || !factory.modality_graph.has_branch_vertices() { return false }
+
+        let possible_world = node.formula.get_possible_world();
+        let paths = factory.tree.get_paths_that_goes_through_node(node);
+        return !paths.is_empty() && paths.iter().all(|path|
+            factory.modality_graph.get_identical_world_on_path(path, possible_world) != possible_world);
+    }
+
+    fn move_to_identical_world(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
+    {
+        let mut output_subtrees : Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
+
+        for path in self.get_open_paths_that_goes_through_node(factory, node)
+        {
+            let identical_world = factory.modality_graph.get_identical_world_on_path(&path, node.formula.get_possible_world());
+            let formula_in_identical_world = node.formula.in_world(identical_world);
+            if !path.nodes.iter().any(|node| *node.formula == formula_in_identical_world)
+            {
+                let output_node = factory.new_node(formula_in_identical_world);
+                output_subtrees.push((path.get_leaf_node_id(), ProofSubtree::with_middle_node(output_node)));
+            }
+        }
+
+        if output_subtrees.is_empty() { return LogicRuleResult::Empty }
+        return LogicRuleResult::Subtrees(output_subtrees);
+    }
+
+    fn get_open_paths_that_goes_through_node(&self, factory : &RuleApplyFactory, node : &ProofTreeNode) -> Vec<ProofTreePath>
+    {
+        return factory.tree.get_paths_that_goes_through_node(node).into_iter()
+            .filter(|path| path.nodes.iter().all(|node| !node.is_contradictory))
+            .collect();
+    }
+}
+
+impl Graph
+{
+    //the first world named i on the path, or the world into which it was merged
+    pub fn get_world_named_by_nominal(&self, path : &ProofTreePath, i : &SmolStr) -> Option<PossibleWorld>
+    {
+        return path.nodes.iter()
+            .find(|node| matches!(node.formula.as_ref(), Nominal(j, _) if j == i))
+            .map(|node| self.get_identical_world_on_path(path, node.formula.get_possible_world()));
+    }
+}
@@ -2,21 +2,38 @@ use std::any::Any;
 use std::rc::Rc;
 use box_macro::bx;
 use crate::formula::Formula::{And, Atomic, Imply, Non, Or};
+use crate::formula::FormulaExtras;
 use crate::formula::Sign::{Minus, Plus};
-use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult};
+use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult, LogicRuleResultCollection};
 use crate::logic::common_modal_logic::{Modality, ModalLogicRules, ModalityRef};
 use crate::logic::rule_apply_factory::RuleApplyFactory;
 use crate::parser::token_types::TokenTypeID;
 use crate::semantics::Semantics;
 use crate::semantics::many_valued_logic_semantics::ManyValuedLogicSemantics;
+use crate::tree::arena::ProofTreeArenaNode;
 use crate::tree::node::ProofTreeNode;
 use crate::tree::subtree::ProofSubtree;
 
 //check out book chapters 6 and 20
-pub struct IntuitionisticLogic {}
+pub struct IntuitionisticLogic
+{
+    pub has_second_kind_tableaux : bool
+}
+
+impl IntuitionisticLogic
+{
+    pub fn first_kind() -> IntuitionisticLogic { IntuitionisticLogic { has_second_kind_tableaux:false } }
+    pub fn second_kind() -> IntuitionisticLogic { IntuitionisticLogic { has_second_kind_tableaux:true } }
+}
+
 impl Logic for IntuitionisticLogic
 {
-    fn get_name(&self) -> LogicName { LogicName::of("IntuitionisticLogic") }
+    fn get_name(&self) -> LogicName
+    {
+        return if !self.has_second_kind_tableaux { LogicName::of("IntuitionisticLogic") }
+        else { LogicName::of("SecondKindIntuitionisticLogic") };
+    }
+
     fn as_any(&self) -> &dyn Any { self }
 
     fn get_semantics(&self) -> Box<dyn Semantics>
@@ -41,7 +58,7 @@ impl Logic for IntuitionisticLogic
         return LogicRuleCollection::of(vec!
         [
             Box::new(ModalLogicRules::new(modality.clone())),
-            Box::new(IntuitionisticLogicRules::new(modality)),
+            Box::new(IntuitionisticLogicRules::new(modality, self.has_second_kind_tableaux)),
         ])
     }
 
@@ -59,6 +76,7 @@ impl IntuitionisticLogic
         {
             is_possibility_applicable: |_, _, _| true,
             is_necessity_applicable: |_, _, _| true,
+            is_blocking_applicable: |_| true,
             add_missing_graph_vertices: |_logic, graph|
             {
                 graph.add_missing_reflexive_vertices();
@@ -70,14 +88,15 @@ impl IntuitionisticLogic
 
 struct IntuitionisticLogicRules
 {
-    modality : Rc<Modality<IntuitionisticLogic>>
+    modality : Rc<Modality<IntuitionisticLogic>>,
+    has_second_kind_tableaux : bool,
 }
 
 impl IntuitionisticLogicRules
 {
-    fn new(modality : Rc<Modality<IntuitionisticLogic>>) -> IntuitionisticLogicRules
+    fn new(modality : Rc<Modality<IntuitionisticLogic>>, has_second_kind_tableaux : bool) -> IntuitionisticLogicRules
     {
-        return IntuitionisticLogicRules { modality };
+        return IntuitionisticLogicRules { modality, has_second_kind_tableaux };
     }
 }
 
@@ -85,7 +104,7 @@ impl LogicRule for IntuitionisticLogicRules
 {
     fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             And(box p, box q, extras) if extras.sign == Plus =>
             {
@@ -131,6 +150,22 @@ impl LogicRule for IntuitionisticLogicRules
                 return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(minus_p_node));
             }
 
+            Imply(box p, box q, extras) if extras.sign == Plus && self.has_second_kind_tableaux =>
+            {
+                if self.was_inherited_on_same_world(factory, node) { return LogicRuleResult::Empty }
+
+                let minus_p = p.with_sign(Minus);
+                let minus_p_node = factory.new_node(minus_p);
+
+                let plus_q = q.with_sign(Plus);
+                let plus_q_node = factory.new_node(plus_q);
+
+                let mut results = LogicRuleResultCollection::new();
+                results.push(LogicRuleResult::Subtree(ProofSubtree::with_left_right_nodes(minus_p_node, plus_q_node)));
+                results.push(self.inherit_on_accessible_worlds(factory, node, extras));
+                return results.joined();
+            }
+
             Imply(box p, box q, extras) if extras.sign == Plus =>
             {
                 let minus_p = p.with_sign(Minus);
@@ -149,6 +184,19 @@ impl LogicRule for IntuitionisticLogicRules
                 return self.modality.apply_possibility(factory, node, &plus_p_and_minus_q, &extras);
             }
 
+            Non(box p, extras) if extras.sign == Plus && self.has_second_kind_tableaux =>
+            {
+                if self.was_inherited_on_same_world(factory, node) { return LogicRuleResult::Empty }
+
+                let minus_p = p.with_sign(Minus);
+                let minus_p_node = factory.new_node(minus_p);
+
+                let mut results = LogicRuleResultCollection::new();
+                results.push(LogicRuleResult::Subtree(ProofSubtree::with_middle_node(minus_p_node)));
+                results.push(self.inherit_on_accessible_worlds(factory, node, extras));
+                return results.joined();
+            }
+
             Non(box p, extras) if extras.sign == Plus =>
             {
                 let minus_p = p.with_sign(Minus);
@@ -163,6 +211,11 @@ impl LogicRule for IntuitionisticLogicRules
                 return self.modality.apply_possibility(factory, node, &plus_p, &extras);
             }
 
+            Atomic(_, extras) if extras.sign == Plus && self.has_second_kind_tableaux =>
+            {
+                return self.inherit_on_accessible_worlds(factory, node, &extras.to_formula_extras());
+            }
+
             p_as_formula@Atomic(_, extras) if extras.sign == Plus =>
             {
                 if self.modality.was_necessity_already_applied(factory, p_as_formula) { return LogicRuleResult::Empty }
@@ -173,3 +226,36 @@ impl LogicRule for IntuitionisticLogicRules
         }
     }
 }
+
+impl IntuitionisticLogicRules
+{
+    //on the second kind of tableaux, every formula signed with + is carried over to all accessible worlds,
+    //then decomposed locally, on each world, without looking at the accessibility relation
+    fn inherit_on_accessible_worlds(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode, extras : &FormulaExtras) -> LogicRuleResult
+    {
+        //the accessibility relation is transitive, so the worlds accessible from an inherited copy
+        //are already covered by the original formula
+        if self.get_inherited_from_node(factory, node).is_some() { return LogicRuleResult::Empty }
+
+        return self.modality.apply_necessity(factory, node, &node.formula, extras);
+    }
+
+    fn was_inherited_on_same_world(&self, factory : &RuleApplyFactory, node : &ProofTreeNode) -> bool
+    {
+        //the accessibility relation is reflexive, the copy on the same world was already decomposed
+        return self.get_inherited_from_node(factory, node)
+            .is_some_and(|spawner_node| spawner_node.formula.get_possible_world() == node.formula.get_possible_world());
+    }
+
+    fn get_inherited_from_node<'a>(&self, factory : &'a RuleApplyFactory, node : &ProofTreeNode) -> Option<&'a ProofTreeArenaNode>
+    {
+        if let Some(spawner_node_id) = node.spawner_node_id &&
+            let Some(spawner_node) = factory.tree.get_node_with_id(spawner_node_id) &&
+            spawner_node.formula.with_stripped_extras() == node.formula.with_stripped_extras()
+        {
+            return Some(spawner_node);
+        }
+
+        return None;
+    }
+}
@@ -0,0 +1,352 @@
+use std::any::Any;
+use std::collections::HashSet;
+use std::rc::Rc;
+use box_macro::bx;
+use itertools::Itertools;
+use smol_str::SmolStr;
+use crate::formula::{Formula, FormulaExtras, PossibleWorld};
+use crate::formula::Formula::{And, Comment, Necessary, Next, Non, Or, Possible, Since, Until};
+use crate::graph::GraphVertex;
+use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult};
+use crate::logic::common_modal_logic::ModalityRef;
+use crate::logic::propositional_logic::PropositionalLogicRules;
+use crate::logic::rule_apply_factory::RuleApplyFactory;
+use crate::parser::token_types::TokenTypeID;
+use crate::semantics::binary_logic_semantics::EventualityLogicSemantics;
+use crate::semantics::Semantics;
+use crate::tree::node::ProofTreeNode;
+use crate::tree::node_factory::ProofTreeNodeID;
+use crate::tree::path::ProofTreePath;
+use crate::tree::subtree::ProofSubtree;
+
+//the possible worlds are the instants 0, 1, 2, ... of a single infinite trace: X P (next), P U Q (until), P S Q (since),
+//□P (always) and ◇P (eventually). Each branch is a lasso: when an instant repeats a previous one, it loops back
+pub struct LinearTemporalLogic {}
+
+impl Logic for LinearTemporalLogic
+{
+    fn get_name(&self) -> LogicName { LogicName::of("LinearTemporalLogic") }
+    fn as_any(&self) -> &dyn Any { self }
+
+    fn get_semantics(&self) -> Box<dyn Semantics>
+    {
+        return Box::new(EventualityLogicSemantics { is_eventuality_closed_by_negation:|formula| matches!(formula, Until(..) | Possible(..)) });
+    }
+
+    fn get_parser_syntax(&self) -> Vec<TokenTypeID>
+    {
+        return vec!
+        [
+            TokenTypeID::AtomicWithoutArgs,
+            TokenTypeID::Non, TokenTypeID::And, TokenTypeID::Or,
+            TokenTypeID::Imply, TokenTypeID::BiImply,
+            TokenTypeID::Necessary, TokenTypeID::Possible,
+            TokenTypeID::Next, TokenTypeID::Until, TokenTypeID::Since,
+            TokenTypeID::OpenParenthesis, TokenTypeID::ClosedParenthesis
+        ]
+    }
+
+    fn get_rules(&self) -> LogicRuleCollection
+    {
+        return LogicRuleCollection::of(vec!
+        [
+            Box::new(PropositionalLogicRules {}),
+            Box::new(LinearTemporalLogicRules {}),
+        ])
+    }
+
+    fn get_modality_ref(&self) -> Option<ModalityRef> { None }
+}
+
+struct LinearTemporalLogicRules {}
+impl LogicRule for LinearTemporalLogicRules
+{
+    fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
+    {
+        //eg: ◇P is both on the world and carried from the previous world by X◇P
+        if self.is_temporal_formula(&node.formula) && factory.was_already_applied_on_world(node) { return LogicRuleResult::Empty }
+
+        return match node.formula.as_ref()
+        {
+            Non(box Next(box p, _), extras) =>
+            {
+                //the trace is infinite, every instant has a next instant
+                let non_p = Non(bx!(p.clone()), extras.clone());
+                let next_non_p = Next(bx!(non_p), extras.clone());
+                let next_non_p_node = factory.new_node(next_non_p);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(next_non_p_node));
+            }
+
+            Next(box p, extras) =>
+            {
+                return self.apply_next(factory, node, p, extras);
+            }
+
+            Possible(box p, extras) =>
+            {
+                //◇P = P ∨ X◇P
+                let next_possible_p = Next(bx!(node.formula.as_ref().clone()), extras.clone());
+                let disjunction = Or(bx!(p.clone()), bx!(next_possible_p), extras.clone());
+                let disjunction_node = factory.new_node(disjunction);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(disjunction_node));
+            }
+
+            Necessary(box p, extras) =>
+            {
+                //□P = P ∧ X□P
+                let next_necessary_p = Next(bx!(node.formula.as_ref().clone()), extras.clone());
+                let conjunction = And(bx!(p.clone()), bx!(next_necessary_p), extras.clone());
+                let conjunction_node = factory.new_node(conjunction);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(conjunction_node));
+            }
+
+            Non(box Possible(box p, _), extras) =>
+            {
+                let non_p = Non(bx!(p.clone()), extras.clone());
+                let necessary_non_p = Necessary(bx!(non_p), extras.clone());
+                let necessary_non_p_node = factory.new_node(necessary_non_p);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(necessary_non_p_node));
+            }
+
+            Non(box Necessary(box p, _), extras) =>
+            {
+                let non_p = Non(bx!(p.clone()), extras.clone());
+                let possible_non_p = Possible(bx!(non_p), extras.clone());
+                let possible_non_p_node = factory.new_node(possible_non_p);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(possible_non_p_node));
+            }
+
+            Until(box p, box q, extras) =>
+            {
+                //P U Q = Q ∨ (P ∧ X(P U Q))
+                let next_until = Next(bx!(node.formula.as_ref().clone()), extras.clone());
+                let p_and_next_until = And(bx!(p.clone()), bx!(next_until), extras.clone());
+                let disjunction = Or(bx!(q.clone()), bx!(p_and_next_until), extras.clone());
+                let disjunction_node = factory.new_node(disjunction);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(disjunction_node));
+            }
+
+            Non(box until@Until(box p, box q, _), extras) =>
+            {
+                //¬(P U Q) = ¬Q ∧ (¬P ∨ X¬(P U Q))
+                let non_q = Non(bx!(q.clone()), extras.clone());
+                let non_p = Non(bx!(p.clone()), extras.clone());
+                let next_non_until = Next(bx!(Non(bx!(until.clone()), extras.clone())), extras.clone());
+                let disjunction = Or(bx!(non_p), bx!(next_non_until), extras.clone());
+                let conjunction = And(bx!(non_q), bx!(disjunction), extras.clone());
+                let conjunction_node = factory.new_node(conjunction);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(conjunction_node));
+            }
+
+            Since(box p, box q, extras) =>
+            {
+                let q_node = factory.new_node(q.clone());
+                if extras.possible_world == PossibleWorld::zero()
+                {
+                    //there is nothing before the first instant
+                    return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(q_node));
+                }
+
+                //P S Q = Q ∨ (P ∧ P S Q on the previous instant)
+                let previous_world = PossibleWorld { index:extras.possible_world.index-1 };
+                let previous_since_node = factory.new_node(node.formula.in_world(previous_world));
+                let p_node = factory.new_node_with_subnode(p.clone(), previous_since_node);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_left_right_nodes(q_node, p_node));
+            }
+
+            Non(box since@Since(box p, box q, _), extras) =>
+            {
+                let mut non_q_node = factory.new_node(Non(bx!(q.clone()), extras.clone()));
+                if extras.possible_world == PossibleWorld::zero()
+                {
+                    return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(non_q_node));
+                }
+
+                //¬(P S Q) = ¬Q ∧ (¬P ∨ ¬(P S Q) on the previous instant)
+                let previous_world = PossibleWorld { index:extras.possible_world.index-1 };
+                let non_since = Non(bx!(since.clone()), extras.clone()).in_world(previous_world);
+                non_q_node.left = Some(bx!(factory.new_node(Non(bx!(p.clone()), extras.clone()))));
+                non_q_node.right = Some(bx!(factory.new_node(non_since)));
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(non_q_node));
+            }
+
+            _ => LogicRuleResult::Empty
+        };
+    }
+}
+
+impl LinearTemporalLogicRules
+{
+    fn apply_next(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode, p : &Formula, extras : &FormulaExtras) -> LogicRuleResult
+    {
+        if factory.modality_graph.is_empty()
+        {
+            factory.modality_graph.add_node(PossibleWorld::zero());
+        }
+
+        let paths = factory.tree.get_paths_that_goes_through_node(node).into_iter()
+            .filter(|path| path.nodes.iter().all(|node| !node.is_contradictory))
+            .collect::<Vec<ProofTreePath>>();
+
+        let mut output_subtrees : Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
+
+        for path in paths
+        {
+            let output_nodes = match self.get_next_world_on_path(factory, &path, extras.possible_world)
+            {
+                //formulas added on a world after it moved on, eg: by the since rule, go straight to the next world
+                Some(next_world) if !self.is_formula_on_path(&path, &p.in_world(next_world)) =>
+                    vec![factory.new_node(p.in_world(next_world))],
+                Some(_) => vec![],
+                None => self.move_to_next_world(factory, &path, extras.possible_world),
+            };
+
+            if !output_nodes.is_empty()
+            {
+                output_subtrees.push((path.get_leaf_node_id(), ProofSubtree::with_middle_vertical_nodes(output_nodes)));
+            }
+        }
+
+        if output_subtrees.is_empty() { return LogicRuleResult::Empty }
+        return LogicRuleResult::Subtrees(output_subtrees);
+    }
+
+    fn get_next_world_on_path(&self, factory : &RuleApplyFactory, path : &ProofTreePath, world : PossibleWorld) -> Option<PossibleWorld>
+    {
+        let looping_vertex = factory.modality_graph.vertices_on_path(path).into_iter()
+            .find(|vertex| vertex.from == world && vertex.to <= world);
+        if let Some(vertex) = looping_vertex { return Some(vertex.to) }
+
+        let next_world = world.fork();
+        return path.nodes.iter()
+            .any(|node| node.formula.get_possible_world() == next_world)
+            .then_some(next_world);
+    }
+
+    fn move_to_next_world(&self, factory : &mut RuleApplyFactory, path : &ProofTreePath, world : PossibleWorld) -> Vec<ProofTreeNode>
+    {
+        let next_formulas = path.nodes.iter()
+            .filter_map(|node| match node.formula.as_ref()
+            {
+                Next(box p, extras) if extras.possible_world == world => Some(p.with_stripped_extras()),
+                _ => None
+            })
+            .unique()
+            .collect::<Vec<Formula>>();
+
+        //the trace can loop back to any previous world that already holds all the X P formulas of the world
+        let looping_world = (1..=world.index)
+            .map(|index| PossibleWorld { index })
+            .filter(|looping_world| self.can_loop_back(path, world, *looping_world, &next_formulas))
+            .find(|looping_world| self.find_unfulfilled_eventuality(path, world, *looping_world).is_none());
+
+        if let Some(looping_world) = looping_world
+        {
+            let mut comment_node = factory.new_node(Comment(SmolStr::default()));
+            factory.modality_graph.add_branch_vertices(comment_node.id, vec![GraphVertex::new(world, looping_world)]);
+            comment_node.formula = Rc::new(Comment(factory.modality_graph.flush_log()));
+            return vec![comment_node];
+        }
+
+        //a world repeating a previous world would only repeat the same loop again
+        if let Some(repeated_world) = self.find_repeated_world(path, world) &&
+            let Some(eventuality) = self.find_unfulfilled_eventuality(path, world, repeated_world.fork())
+        {
+            let comment = format!("{} repeats {}, {} is never fulfilled", world, repeated_world, eventuality);
+            let comment_node = factory.new_node(Comment(SmolStr::from(comment)));
+            let non_eventuality = Non(bx!(eventuality.clone()), eventuality.get_extras());
+            let non_eventuality_node = factory.new_node(non_eventuality);
+            return vec![comment_node, non_eventuality_node];
+        }
+
+        //all the X P formulas of the world are moved at once, the world is fully decomposed
+        let next_world = world.fork();
+        factory.modality_graph.add_node(next_world);
+        factory.modality_graph.add_vertex(GraphVertex::new(world, next_world));
+        let log = factory.modality_graph.flush_log();
+        let comment_node = factory.new_node(Comment(log));
+
+        let mut output_nodes = vec![comment_node];
+        for p in next_formulas
+        {
+            output_nodes.push(factory.new_node(p.in_world(next_world)));
+        }
+
+        return output_nodes;
+    }
+
+    fn can_loop_back(&self, path : &ProofTreePath, world : PossibleWorld, looping_world : PossibleWorld, next_formulas : &Vec<Formula>) -> bool
+    {
+        let formulas_on_looping_world = self.get_formulas_on_world(path, looping_world);
+        if !next_formulas.iter().all(|formula| formulas_on_looping_world.contains(formula)) { return false }
+
+        //on the loop, the previous world of the looping world is the world, it must also hold what P S Q needs in the past
+        let formulas_on_world = self.get_formulas_on_world(path, world);
+        let previous_world = PossibleWorld { index:looping_world.index-1 };
+        return self.get_formulas_on_world(path, previous_world).into_iter()
+            .filter(|formula| matches!(formula, Since(..) | Non(box Since(..), _)))
+            .all(|formula| formulas_on_world.contains(&formula));
+    }
+
+    fn find_repeated_world(&self, path : &ProofTreePath, world : PossibleWorld) -> Option<PossibleWorld>
+    {
+        let formulas_on_world = self.get_formulas_on_world(path, world);
+
+        return (0..world.index)
+            .map(|index| PossibleWorld { index })
+            .find(|previous_world| self.get_formulas_on_world(path, *previous_world) == formulas_on_world);
+    }
+
+    //the worlds of the loop repeat forever, P U Q and ◇Q are fulfilled only if Q holds somewhere on the loop
+    fn find_unfulfilled_eventuality(&self, path : &ProofTreePath, world : PossibleWorld, looping_world : PossibleWorld) -> Option<Formula>
+    {
+        let loop_worlds = (looping_world.index..=world.index)
+            .map(|index| PossibleWorld { index })
+            .collect::<Vec<PossibleWorld>>();
+
+        let formulas_on_loop = loop_worlds.iter()
+            .flat_map(|loop_world| self.get_formulas_on_world(path, *loop_world))
+            .collect::<HashSet<Formula>>();
+
+        return path.nodes.iter()
+            .filter(|node| loop_worlds.contains(&node.formula.get_possible_world()))
+            .find(|node| match node.formula.as_ref()
+            {
+                Until(_, box q, _) | Possible(box q, _) => !formulas_on_loop.contains(&q.with_stripped_extras()),
+                _ => false
+            })
+            .map(|node| node.formula.as_ref().clone());
+    }
+
+    fn is_temporal_formula(&self, formula : &Formula) -> bool
+    {
+        return match formula
+        {
+            Non(box p, _) => matches!(p, Next(..) | Possible(..) | Necessary(..) | Until(..) | Since(..)),
+            _ => matches!(formula, Next(..) | Possible(..) | Necessary(..) | Until(..) | Since(..)),
+        };
+    }
+
+    fn get_formulas_on_world(&self, path : &ProofTreePath, world : PossibleWorld) -> HashSet<Formula>
+    {
+        return path.nodes.iter()
+            .filter(|node| !matches!(*node.formula, Comment(..)))
+            .filter(|node| node.formula.get_possible_world() == world)
+            .map(|node| node.formula.with_stripped_extras())
+            .collect();
+    }
+
+    fn is_formula_on_path(&self, path : &ProofTreePath, formula : &Formula) -> bool
+    {
+        return path.nodes.iter().any(|node| *node.formula == *formula);
+    }
+}
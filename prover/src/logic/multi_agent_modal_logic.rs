@@ -0,0 +1,299 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use box_macro::bx;
+use smol_str::SmolStr;
+use crate::formula::Formula::{AgentNecessary, AgentPossible, Comment, CommonKnowledge, Non};
+use crate::formula::{Formula, PossibleWorld};
+use crate::graph::Graph;
+use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult, LogicRuleResultCollection};
+use crate::logic::common_modal_logic::{Modality, ModalityRef};
+use crate::logic::multi_agent_modal_logic::group_knowledge_rules::GroupKnowledgeRules;
+use crate::logic::normal_modal_logic::NormalModalLogic;
+use crate::logic::propositional_logic::PropositionalLogicRules;
+use crate::logic::rule_apply_factory::RuleApplyFactory;
+use crate::parser::token_types::TokenTypeID;
+use crate::semantics::binary_logic_semantics::EventualityLogicSemantics;
+use crate::semantics::Semantics;
+use crate::tree::node::ProofTreeNode;
+use crate::tree::subtree::ProofSubtree;
+
+mod group_knowledge_rules;
+
+pub const MULTI_AGENT_MODAL_LOGIC_NAME_SUFFIX : &str = "MultiAgentModalLogic";
+
+//epistemic logic: □_a P means "agent a knows (or believes) P", each agent having its own accessibility relation
+pub struct MultiAgentModalLogic
+{
+    pub name : LogicName,
+    //frame conditions of agents, eg: S5 for knowledge, KD45 for belief
+    pub default_agent_logic : NormalModalLogic,
+    pub agents_logics : BTreeMap<SmolStr, NormalModalLogic>,
+    //the description logic ALC is K with many agents written differently: worlds are individuals, agents are roles,
+    //atomic formulas are concepts, eg: ∃r.C is ◇_r C, ∀r.C is □_r C, C ⊓ D is C ∧ D, C ⊔ D is C ∨ D, C ⊑ D is C → D
+    pub is_description_logic : bool,
+}
+
+#[allow(non_snake_case)]
+impl MultiAgentModalLogic
+{
+    pub fn K() -> MultiAgentModalLogic { MultiAgentModalLogic { name:LogicName::of("KMultiAgentModalLogic"), default_agent_logic:NormalModalLogic::K(), agents_logics:BTreeMap::new(), is_description_logic:false } }
+    pub fn S5() -> MultiAgentModalLogic { MultiAgentModalLogic { name:LogicName::of("S5MultiAgentModalLogic"), default_agent_logic:NormalModalLogic::S5(), agents_logics:BTreeMap::new(), is_description_logic:false } }
+    pub fn KD45() -> MultiAgentModalLogic { MultiAgentModalLogic { name:LogicName::of("KD45MultiAgentModalLogic"), default_agent_logic:NormalModalLogic::KD45(), agents_logics:BTreeMap::new(), is_description_logic:false } }
+    pub fn ALC() -> MultiAgentModalLogic { MultiAgentModalLogic { name:LogicName::of("ALCDescriptionLogic"), default_agent_logic:NormalModalLogic::K(), agents_logics:BTreeMap::new(), is_description_logic:true } }
+}
+
+impl Logic for MultiAgentModalLogic
+{
+    fn get_name(&self) -> LogicName { self.name.clone() }
+    fn as_any(&self) -> &dyn Any { self }
+
+    fn get_semantics(&self) -> Box<dyn Semantics>
+    {
+        return Box::new(EventualityLogicSemantics { is_eventuality_closed_by_negation:|formula| matches!(formula, CommonKnowledge(..)) });
+    }
+
+    fn get_parser_syntax(&self) -> Vec<TokenTypeID>
+    {
+        if self.is_description_logic
+        {
+            return vec!
+            [
+                TokenTypeID::AtomicWithoutArgs, TokenTypeID::Non,
+                TokenTypeID::ConceptIntersection, TokenTypeID::ConceptUnion,
+                TokenTypeID::RoleExists, TokenTypeID::RoleForAll, TokenTypeID::Subsumption,
+                TokenTypeID::OpenParenthesis, TokenTypeID::ClosedParenthesis
+            ]
+        }
+
+        return vec!
+        [
+            TokenTypeID::AtomicWithoutArgs,
+            TokenTypeID::Non, TokenTypeID::And, TokenTypeID::Or,
+            TokenTypeID::Imply, TokenTypeID::BiImply,
+            TokenTypeID::AgentNecessary, TokenTypeID::AgentPossible,
+            TokenTypeID::EveryoneKnows, TokenTypeID::CommonKnowledge, TokenTypeID::DistributedKnowledge,
+            TokenTypeID::OpenParenthesis, TokenTypeID::ClosedParenthesis
+        ]
+    }
+
+    fn get_rules(&self) -> LogicRuleCollection
+    {
+        let modality = Rc::new(self.get_modality());
+        return LogicRuleCollection::of(vec!
+        [
+            Box::new(PropositionalLogicRules {}),
+            Box::new(MultiAgentModalLogicRules::new(modality.clone())),
+            Box::new(GroupKnowledgeRules::new(modality)),
+        ])
+    }
+
+    fn get_modality_ref(&self) -> Option<ModalityRef>
+    {
+        return Some(ModalityRef::new(self.get_modality()));
+    }
+}
+
+impl MultiAgentModalLogic
+{
+    //agents written before the logic name have their own frame conditions, eg: a=S5+b=KD45+KMultiAgentModalLogic
+    pub fn with_agents_logics(base_logic : MultiAgentModalLogic, agents_logics : BTreeMap<SmolStr, NormalModalLogic>) -> MultiAgentModalLogic
+    {
+        let agents_names = agents_logics.iter()
+            .map(|(agent, agent_logic)| format!("{}={}+", agent, agent_logic.get_name().to_string().trim_end_matches("ModalLogic")))
+            .collect::<String>();
+
+        return MultiAgentModalLogic
+        {
+            name: LogicName::of(format!("{}{}", agents_names, base_logic.name).as_str()),
+            default_agent_logic: base_logic.default_agent_logic,
+            agents_logics,
+            is_description_logic: base_logic.is_description_logic,
+        }
+    }
+
+    pub fn get_agent_logic(&self, agent : &SmolStr) -> &NormalModalLogic
+    {
+        return self.agents_logics.get(agent).unwrap_or(&self.default_agent_logic);
+    }
+
+    pub fn get_modality(&self) -> Modality<MultiAgentModalLogic>
+    {
+        return Modality
+        {
+            is_possibility_applicable: |_, _, _| true,
+            is_necessity_applicable: |_, _, _| true,
+            //the terminological axioms are appended on every new individual, without blocking the tableau might not end
+            is_blocking_applicable: |logic| logic.is_description_logic,
+            add_missing_graph_vertices: |logic, graph|
+            {
+                let agent = graph.agent();
+                if Graph::is_group_of_agents(&agent)
+                {
+                    graph.add_missing_intersection_vertices(&Graph::get_agents_of_group(&agent));
+                    return;
+                }
+
+                //the frame conditions of the agent whose relation is selected
+                let agent_logic = logic.get_agent_logic(&agent);
+                (agent_logic.get_modality().add_missing_graph_vertices)(agent_logic, graph);
+            }
+        }
+    }
+}
+
+struct MultiAgentModalLogicRules
+{
+    modality : Rc<Modality<MultiAgentModalLogic>>
+}
+
+impl MultiAgentModalLogicRules
+{
+    fn new(modality : Rc<Modality<MultiAgentModalLogic>>) -> MultiAgentModalLogicRules
+    {
+        return MultiAgentModalLogicRules { modality };
+    }
+}
+
+impl LogicRule for MultiAgentModalLogicRules
+{
+    fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
+    {
+        return match node.formula.as_ref()
+        {
+            Non(box AgentPossible(agent, box p, _), extras) =>
+            {
+                let non_p = Non(bx!(p.clone()), extras.clone());
+                let necessary_non_p = AgentNecessary(agent.clone(), bx!(non_p), extras.clone());
+                let necessary_non_p_node = factory.new_node(necessary_non_p);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(necessary_non_p_node));
+            }
+
+            Non(box AgentNecessary(agent, box p, _), extras) =>
+            {
+                let non_p = Non(bx!(p.clone()), extras.clone());
+                let possible_non_p = AgentPossible(agent.clone(), bx!(non_p), extras.clone());
+                let possible_non_p_node = factory.new_node(possible_non_p);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(possible_non_p_node));
+            }
+
+            AgentPossible(agent, box p, extras) =>
+            {
+                let mut results = LogicRuleResultCollection::new();
+                self.modality.select_agent_relation(factory, agent, &mut results);
+
+                let logic_pointer = factory.get_logic().clone();
+                let logic = logic_pointer.cast_to::<MultiAgentModalLogic>().unwrap();
+
+                let p_with_parent_sign = p.with_sign(extras.sign);
+                let (result, forked_world) = self.modality.apply_possibility_with_forked_world(factory, node, &p_with_parent_sign, extras);
+                results.push(result);
+
+                if let Some(forked_world) = forked_world && logic.is_description_logic
+                {
+                    results.push(self.modality.apply_terminological_axioms(factory, forked_world));
+                }
+
+                let world_on_path = forked_world.unwrap_or(extras.possible_world);
+                self.modality.add_missing_agents_graph_vertices(factory, node, world_on_path, &mut results);
+                return results.joined();
+            }
+
+            AgentNecessary(agent, box p, extras) =>
+            {
+                let mut results = LogicRuleResultCollection::new();
+                self.modality.select_agent_relation(factory, agent, &mut results);
+
+                let logic_pointer = factory.get_logic().clone();
+                let logic = logic_pointer.cast_to::<MultiAgentModalLogic>().unwrap();
+
+                let p_with_parent_sign = p.with_sign(extras.sign);
+                results.push(self.modality.apply_necessity(factory, node, &p_with_parent_sign, extras));
+
+                //check out book chapter 3 (the extendability rule), applied on the relation of this agent
+                let mut world_on_path = extras.possible_world;
+                if logic.get_agent_logic(agent).is_serial &&
+                    !self.modality.has_accessible_worlds_on_all_paths(factory, node, extras.possible_world)
+                {
+                    results.push(self.modality.apply_seriality(factory, node, extras));
+                    world_on_path = *factory.modality_graph.nodes().max().unwrap();
+                }
+
+                self.modality.add_missing_agents_graph_vertices(factory, node, world_on_path, &mut results);
+                return results.joined();
+            }
+
+            _ => LogicRuleResult::Empty
+        }
+    }
+}
+
+impl Modality<MultiAgentModalLogic>
+{
+    pub fn select_agent_relation(&self, factory : &mut RuleApplyFactory, agent : &SmolStr, output_results : &mut LogicRuleResultCollection)
+    {
+        //the relation of a group of agents is computed from the relations of its agents, these must be complete
+        if Graph::is_group_of_agents(agent)
+        {
+            for agent_of_group in Graph::get_agents_of_group(agent)
+            {
+                self.select_agent_relation(factory, &agent_of_group, output_results);
+            }
+        }
+
+        factory.modality_graph.select_agent_relation(agent);
+        if agent.is_empty() { return }
+
+        let logic_pointer = factory.get_logic().clone();
+        let logic = logic_pointer.cast_to::<MultiAgentModalLogic>().unwrap();
+        self.initialize_graph_if_needed(factory);
+        (self.add_missing_graph_vertices)(logic, factory.modality_graph);
+        self.push_graph_log_comment(factory, output_results);
+    }
+
+    //on description logic, the premises are terminological axioms (the TBox): they hold on every individual, not only on the first one
+    pub fn apply_terminological_axioms(&self, factory : &mut RuleApplyFactory, possible_world : PossibleWorld) -> LogicRuleResult
+    {
+        let premises_in_world = factory.tree.problem.premises.iter()
+            .map(|premise| premise.in_world(possible_world))
+            .collect::<Vec<Formula>>();
+
+        let premises_nodes = premises_in_world.into_iter()
+            .map(|premise| factory.new_node(premise))
+            .collect::<Vec<ProofTreeNode>>();
+
+        return LogicRuleResult::Subtree(ProofSubtree::with_middle_vertical_nodes(premises_nodes));
+    }
+
+    //the vertices added on the relation of an agent are written on the tree, as with the other modalities
+    pub fn push_graph_log_comment(&self, factory : &mut RuleApplyFactory, output_results : &mut LogicRuleResultCollection)
+    {
+        let log = factory.modality_graph.flush_log();
+        if log.is_empty() { return }
+
+        let comment_node = factory.new_node(Comment(log));
+        output_results.push(LogicRuleResult::Subtree(ProofSubtree::with_middle_node(comment_node)));
+    }
+
+    //vertices added on the relation of an agent might be missing on the relations of other agents or groups of agents,
+    //eg: on S5 each new world accesses itself, the necessities are reapplied on the new vertices
+    pub fn add_missing_agents_graph_vertices(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        world_on_path : PossibleWorld, output_results : &mut LogicRuleResultCollection,
+    )
+    {
+        let agents = factory.modality_graph.agents().into_iter()
+            .chain(factory.modality_graph.groups_of_agents())
+            .collect::<Vec<SmolStr>>();
+
+        for agent in agents
+        {
+            self.select_agent_relation(factory, &agent, output_results);
+            self.reapply_necessity_after_possibility(factory, node, world_on_path, output_results);
+        }
+
+        self.select_agent_relation(factory, &SmolStr::default(), output_results);
+    }
+}
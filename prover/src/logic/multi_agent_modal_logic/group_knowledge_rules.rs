@@ -0,0 +1,195 @@
+use std::rc::Rc;
+use box_macro::bx;
+use smol_str::SmolStr;
+use crate::formula::Formula::{AgentNecessary, AgentPossible, And, CommonKnowledge, DistributedKnowledge, EveryoneKnows, Non, Or};
+use crate::formula::{Formula, FormulaExtras};
+use crate::graph::{Graph, GraphVertex};
+use crate::logic::{LogicRule, LogicRuleResult, LogicRuleResultCollection};
+use crate::logic::common_modal_logic::Modality;
+use crate::logic::multi_agent_modal_logic::MultiAgentModalLogic;
+use crate::logic::rule_apply_factory::RuleApplyFactory;
+use crate::tree::node::ProofTreeNode;
+use crate::tree::node_factory::ProofTreeNodeID;
+use crate::tree::subtree::ProofSubtree;
+
+//E_G P: every agent of G knows P, C_G P: P is common knowledge of G, D_G P: P is distributed knowledge of G
+pub struct GroupKnowledgeRules
+{
+    modality : Rc<Modality<MultiAgentModalLogic>>
+}
+
+impl GroupKnowledgeRules
+{
+    pub fn new(modality : Rc<Modality<MultiAgentModalLogic>>) -> GroupKnowledgeRules
+    {
+        return GroupKnowledgeRules { modality };
+    }
+}
+
+impl LogicRule for GroupKnowledgeRules
+{
+    fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
+    {
+        return match node.formula.as_ref()
+        {
+            EveryoneKnows(agents, box p, extras) =>
+            {
+                //E_G P = □_a P ∧ □_b P ∧ ...
+                let necessary_p_nodes = agents.iter()
+                    .map(|agent| AgentNecessary(agent.clone(), bx!(p.clone()), extras.clone()))
+                    .map(|necessary_p| factory.new_node(necessary_p))
+                    .collect::<Vec<ProofTreeNode>>();
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_vertical_nodes(necessary_p_nodes));
+            }
+
+            Non(box EveryoneKnows(agents, box p, _), extras) =>
+            {
+                let disjunction = self.get_non_everyone_knows_disjunction(agents, p, extras);
+                let disjunction_node = factory.new_node(disjunction);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(disjunction_node));
+            }
+
+            CommonKnowledge(agents, box p, extras) =>
+            {
+                if factory.was_already_applied_on_world(node) { return LogicRuleResult::Empty }
+
+                //C_G P = E_G (P ∧ C_G P)
+                let p_and_common_knowledge = And(bx!(p.clone()), bx!(node.formula.as_ref().clone()), extras.clone());
+                let everyone_knows = EveryoneKnows(agents.clone(), bx!(p_and_common_knowledge), extras.clone());
+                let everyone_knows_node = factory.new_node(everyone_knows);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(everyone_knows_node));
+            }
+
+            Non(box common_knowledge@CommonKnowledge(agents, box p, _), extras) =>
+            {
+                if factory.was_already_applied_on_world(node) { return LogicRuleResult::Empty }
+
+                return self.apply_non_common_knowledge(factory, node, agents, p, common_knowledge, extras);
+            }
+
+            DistributedKnowledge(agents, box p, extras) =>
+            {
+                let group = Graph::get_group_of_agents_name(agents);
+                let mut results = LogicRuleResultCollection::new();
+                self.modality.select_agent_relation(factory, &group, &mut results);
+
+                let p_with_parent_sign = p.with_sign(extras.sign);
+                results.push(self.modality.apply_necessity(factory, node, &p_with_parent_sign, extras));
+
+                self.modality.add_missing_agents_graph_vertices(factory, node, extras.possible_world, &mut results);
+                return results.joined();
+            }
+
+            Non(box DistributedKnowledge(agents, box p, _), extras) =>
+            {
+                return self.apply_non_distributed_knowledge(factory, node, agents, p, extras);
+            }
+
+            _ => LogicRuleResult::Empty
+        }
+    }
+}
+
+impl GroupKnowledgeRules
+{
+    fn apply_non_common_knowledge(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        agents : &Vec<SmolStr>, p : &Formula, common_knowledge : &Formula,
+        extras : &FormulaExtras,
+    ) -> LogicRuleResult
+    {
+        let mut output_subtrees : Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
+        let eventuality_unfolding_formulas = self.get_eventuality_unfolding_formulas(agents, p);
+
+        for path in factory.tree.get_paths_that_goes_through_node(node)
+        {
+            //the eventuality ¬C_G P loops, it is never fulfilled on this branch, thus C_G P is added to close it
+            let subtree = if let Some(looping_world) = self.modality.find_looping_world(&path, node, &eventuality_unfolding_formulas)
+            {
+                let comment_node = factory.new_node(Formula::Comment(format!("loop on {}", looping_world).into()));
+                let common_knowledge_node = factory.new_node(common_knowledge.in_world(extras.possible_world).with_sign(extras.sign));
+                ProofSubtree::with_middle_vertical_nodes(vec![comment_node, common_knowledge_node])
+            }
+            else
+            {
+                //¬C_G P = ¬E_G (P ∧ C_G P)
+                let p_and_common_knowledge = And(bx!(p.clone()), bx!(common_knowledge.clone()), extras.clone());
+                let everyone_knows = EveryoneKnows(agents.clone(), bx!(p_and_common_knowledge), extras.clone());
+                let non_everyone_knows_node = factory.new_node(Non(bx!(everyone_knows), extras.clone()));
+                ProofSubtree::with_middle_node(non_everyone_knows_node)
+            };
+
+            output_subtrees.push((path.get_leaf_node_id(), subtree));
+        }
+
+        return LogicRuleResult::Subtrees(output_subtrees);
+    }
+
+    fn get_non_everyone_knows_disjunction(&self, agents : &Vec<SmolStr>, p : &Formula, extras : &FormulaExtras) -> Formula
+    {
+        //¬E_G P = ¬□_a P ∨ ¬□_b P ∨ ...
+        return agents.iter()
+            .map(|agent| Non(bx!(AgentNecessary(agent.clone(), bx!(p.clone()), extras.clone())), extras.clone()))
+            .reduce(|p, q| Or(bx!(p), bx!(q), extras.clone())).unwrap();
+    }
+
+    //formulas spawned on a world by unfolding the eventuality ¬C_G P once, they are implied by ¬C_G P or by the choice of a successor
+    fn get_eventuality_unfolding_formulas(&self, agents : &Vec<SmolStr>, p : &Formula) -> Vec<Formula>
+    {
+        let extras = FormulaExtras::empty();
+        let common_knowledge = CommonKnowledge(agents.clone(), bx!(p.clone()), extras.clone());
+        let p_and_common_knowledge = And(bx!(p.clone()), bx!(common_knowledge), extras.clone());
+        let non_p_and_common_knowledge = Non(bx!(p_and_common_knowledge.clone()), extras.clone());
+        let everyone_knows = EveryoneKnows(agents.clone(), bx!(p_and_common_knowledge.clone()), extras.clone());
+
+        let mut formulas = vec![Non(bx!(everyone_knows), extras.clone()), non_p_and_common_knowledge.clone()];
+
+        let mut disjunction = self.get_non_everyone_knows_disjunction(agents, &p_and_common_knowledge, &extras);
+        while let Or(box disjunction_head, box disjunction_tail, _) = disjunction
+        {
+            formulas.push(Or(bx!(disjunction_head.clone()), bx!(disjunction_tail.clone()), extras.clone()));
+            formulas.push(disjunction_tail);
+            disjunction = disjunction_head;
+        }
+        formulas.push(disjunction);
+
+        for agent in agents
+        {
+            formulas.push(AgentPossible(agent.clone(), bx!(non_p_and_common_knowledge.clone()), extras.clone()));
+        }
+
+        return formulas.iter().map(|formula| formula.with_stripped_extras()).collect();
+    }
+
+    fn apply_non_distributed_knowledge(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        agents : &Vec<SmolStr>, p : &Formula, extras : &FormulaExtras,
+    ) -> LogicRuleResult
+    {
+        let group = Graph::get_group_of_agents_name(agents);
+        let mut results = LogicRuleResultCollection::new();
+        self.modality.select_agent_relation(factory, &group, &mut results);
+
+        let non_p = Non(bx!(p.clone()), extras.clone());
+        let (result, forked_world) = self.modality.apply_possibility_with_forked_world(factory, node, &non_p, extras);
+        results.push(result);
+
+        //the new world is accessible for every agent of the group
+        if let Some(forked_world) = forked_world
+        {
+            for agent in agents
+            {
+                factory.modality_graph.select_agent_relation(agent);
+                factory.modality_graph.add_vertex(GraphVertex::new(extras.possible_world, forked_world));
+                self.modality.push_graph_log_comment(factory, &mut results);
+            }
+        }
+
+        let world_on_path = forked_world.unwrap_or(extras.possible_world);
+        self.modality.add_missing_agents_graph_vertices(factory, node, world_on_path, &mut results);
+        return results.joined();
+    }
+}
@@ -76,6 +76,7 @@ impl NonNormalModalLogic
         {
             is_possibility_applicable: self.create_is_possibility_applicable_lambda(),
             is_necessity_applicable: self.create_is_necessity_applicable_lambda(),
+            is_blocking_applicable: |_| false,
             add_missing_graph_vertices: |logic, graph|
             {
                 if logic.is_reflexive { graph.add_missing_reflexive_vertices() }
@@ -97,7 +98,7 @@ impl NonNormalModalLogic
             extras.possible_world == PossibleWorld::zero() ||
             factory.tree.get_path_that_goes_through_node(node).nodes.iter().any(|node|
                 node.formula.get_possible_world() == extras.possible_world &&
-                matches!(node.formula, Necessary(..) | StrictImply(..)));
+                matches!(*node.formula, Necessary(..) | StrictImply(..)));
     }
 
     fn create_is_necessity_applicable_lambda(&self) -> fn(&RuleApplyFactory, &ProofTreeNode, &FormulaExtras) -> bool
@@ -1,11 +1,17 @@
 use std::any::Any;
+use std::collections::BTreeSet;
 use std::rc::Rc;
-use crate::logic::{Logic, LogicName, LogicRuleCollection};
-use crate::logic::common_modal_logic::{Modality, ModalLogicRules, ModalityRef};
+use box_macro::bx;
+use crate::formula::Formula::{Imply, Necessary, Non, Possible, StrictImply};
+use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult, LogicRuleResultCollection};
+use crate::logic::common_modal_logic::{FrameCondition, Modality, ModalLogicRules, ModalityRef};
 use crate::logic::propositional_logic::PropositionalLogicRules;
+use crate::logic::rule_apply_factory::RuleApplyFactory;
 use crate::parser::token_types::TokenTypeID;
 use crate::semantics::binary_logic_semantics::BinaryLogicSemantics;
 use crate::semantics::Semantics;
+use crate::tree::node::ProofTreeNode;
+use crate::tree::subtree::ProofSubtree;
 
 //check out book chapters 2, 3, 14-17
 pub struct NormalModalLogic
@@ -14,16 +20,47 @@ pub struct NormalModalLogic
     pub is_reflexive : bool,
     pub is_symmetric : bool,
     pub is_transitive : bool,
+    pub is_serial : bool,
+    pub is_euclidean : bool,
+    //no infinite R-chains, the frame is also irreflexive, check out the Löb axiom □(□A ⊃ A) ⊃ □A
+    pub is_converse_well_founded : bool,
+    //□ is read as "it is obligatory that" and ◇ as "it is permitted that", written O and P
+    pub is_deontic : bool,
 }
 
 #[allow(non_snake_case)]
 impl NormalModalLogic
 {
-    pub fn K() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("KModalLogic"), is_reflexive:false, is_symmetric:false, is_transitive:false }}
-    pub fn T() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("TModalLogic"), is_reflexive:true, is_symmetric:false, is_transitive:false }}
-    pub fn B() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("BModalLogic"), is_reflexive:true, is_symmetric:true, is_transitive:false }}
-    pub fn S4() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("S4ModalLogic"), is_reflexive:true, is_symmetric:false, is_transitive:true }}
-    pub fn S5() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("S5ModalLogic"), is_reflexive:true, is_symmetric:true, is_transitive:true }}
+    pub fn K() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("KModalLogic"), is_reflexive:false, is_symmetric:false, is_transitive:false, is_serial:false, is_euclidean:false, is_converse_well_founded:false, is_deontic:false }}
+    pub fn D() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("DModalLogic"), is_reflexive:false, is_symmetric:false, is_transitive:false, is_serial:true, is_euclidean:false, is_converse_well_founded:false, is_deontic:false }}
+    pub fn T() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("TModalLogic"), is_reflexive:true, is_symmetric:false, is_transitive:false, is_serial:false, is_euclidean:false, is_converse_well_founded:false, is_deontic:false }}
+    pub fn B() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("BModalLogic"), is_reflexive:true, is_symmetric:true, is_transitive:false, is_serial:false, is_euclidean:false, is_converse_well_founded:false, is_deontic:false }}
+    pub fn K4() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("K4ModalLogic"), is_reflexive:false, is_symmetric:false, is_transitive:true, is_serial:false, is_euclidean:false, is_converse_well_founded:false, is_deontic:false }}
+    pub fn K5() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("K5ModalLogic"), is_reflexive:false, is_symmetric:false, is_transitive:false, is_serial:false, is_euclidean:true, is_converse_well_founded:false, is_deontic:false }}
+    pub fn K45() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("K45ModalLogic"), is_reflexive:false, is_symmetric:false, is_transitive:true, is_serial:false, is_euclidean:true, is_converse_well_founded:false, is_deontic:false }}
+    pub fn KD45() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("KD45ModalLogic"), is_reflexive:false, is_symmetric:false, is_transitive:true, is_serial:true, is_euclidean:true, is_converse_well_founded:false, is_deontic:false }}
+    pub fn S4() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("S4ModalLogic"), is_reflexive:true, is_symmetric:false, is_transitive:true, is_serial:false, is_euclidean:false, is_converse_well_founded:false, is_deontic:false }}
+    pub fn S5() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("S5ModalLogic"), is_reflexive:true, is_symmetric:true, is_transitive:true, is_serial:false, is_euclidean:false, is_converse_well_founded:false, is_deontic:false }}
+    pub fn SDL() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("StandardDeonticLogic"), is_reflexive:false, is_symmetric:false, is_transitive:false, is_serial:true, is_euclidean:false, is_converse_well_founded:false, is_deontic:true }}
+    pub fn GL() -> NormalModalLogic { NormalModalLogic { name:LogicName::of("GLModalLogic"), is_reflexive:false, is_symmetric:false, is_transitive:true, is_serial:false, is_euclidean:false, is_converse_well_founded:true, is_deontic:false }}
+}
+
+impl NormalModalLogic
+{
+    pub fn with_frame_conditions(frame_conditions : &BTreeSet<FrameCondition>) -> NormalModalLogic
+    {
+        return NormalModalLogic
+        {
+            name: FrameCondition::get_logic_name(None, frame_conditions),
+            is_reflexive: frame_conditions.contains(&FrameCondition::Reflexive),
+            is_symmetric: frame_conditions.contains(&FrameCondition::Symmetric),
+            is_transitive: frame_conditions.contains(&FrameCondition::Transitive),
+            is_serial: frame_conditions.contains(&FrameCondition::Serial),
+            is_euclidean: frame_conditions.contains(&FrameCondition::Euclidean),
+            is_converse_well_founded: false,
+            is_deontic: false,
+        }
+    }
 }
 
 impl Logic for NormalModalLogic
@@ -38,23 +75,41 @@ impl Logic for NormalModalLogic
 
     fn get_parser_syntax(&self) -> Vec<TokenTypeID>
     {
+        let (necessary, possible) =
+            if self.is_deontic { (TokenTypeID::Obligatory, TokenTypeID::Permitted) }
+            else { (TokenTypeID::Necessary, TokenTypeID::Possible) };
+
         return vec!
         [
             TokenTypeID::AtomicWithoutArgs,
             TokenTypeID::Non, TokenTypeID::And, TokenTypeID::Or,
             TokenTypeID::Imply, TokenTypeID::BiImply, TokenTypeID::StrictImply,
-            TokenTypeID::Necessary, TokenTypeID::Possible,
+            necessary, possible,
             TokenTypeID::OpenParenthesis, TokenTypeID::ClosedParenthesis
         ]
     }
 
     fn get_rules(&self) -> LogicRuleCollection
     {
-        return LogicRuleCollection::of(vec!
+        let modality = Rc::new(self.get_modality());
+        let mut rules = LogicRuleCollection::of(vec!
         [
             Box::new(PropositionalLogicRules {}),
-            Box::new(ModalLogicRules::new(Rc::new(self.get_modality()))),
-        ])
+            Box::new(ModalLogicRules::new(modality.clone())),
+        ]);
+
+        if self.is_serial
+        {
+            let wrapper_rule = SerialityRules::wrap(rules, modality);
+            rules = LogicRuleCollection::of(vec![bx!(wrapper_rule)]);
+        }
+        else if self.is_converse_well_founded
+        {
+            let wrapper_rule = LobRules::wrap(rules, modality);
+            rules = LogicRuleCollection::of(vec![bx!(wrapper_rule)]);
+        }
+
+        return rules;
     }
 
     fn get_modality_ref(&self) -> Option<ModalityRef>
@@ -71,12 +126,107 @@ impl NormalModalLogic
         {
             is_possibility_applicable: |_, _, _| true,
             is_necessity_applicable: |_, _, _| true,
+            is_blocking_applicable: |logic| logic.is_transitive && !logic.is_converse_well_founded,
             add_missing_graph_vertices: |logic, graph|
             {
                 if logic.is_reflexive { graph.add_missing_reflexive_vertices() }
                 if logic.is_symmetric { graph.add_missing_symmetric_vertices() }
+                if logic.is_euclidean { graph.add_missing_euclidean_vertices() }
                 if logic.is_transitive { graph.add_missing_transitive_vertices() }
             }
         }
     }
 }
+
+//check out book chapter 3 (the extendability rule)
+struct SerialityRules
+{
+    base_rules : LogicRuleCollection,
+    modality : Rc<Modality<NormalModalLogic>>,
+}
+
+impl SerialityRules
+{
+    fn wrap(base_rules : LogicRuleCollection, modality : Rc<Modality<NormalModalLogic>>) -> SerialityRules
+    {
+        return SerialityRules { base_rules, modality };
+    }
+}
+
+impl LogicRule for SerialityRules
+{
+    fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
+    {
+        return match node.formula.as_ref()
+        {
+            Necessary(_, extras) | StrictImply(_, _, extras)
+            if !self.modality.has_accessible_worlds_on_all_paths(factory, node, extras.possible_world) =>
+            {
+                //a necessity on a world without accessible worlds: first apply the necessity,
+                //then create an accessible world, which will receive all the necessities
+                let mut results = LogicRuleResultCollection::new();
+                results.push(self.base_rules.apply(factory, node));
+                results.push(self.modality.apply_seriality(factory, node, extras));
+                results.joined()
+            }
+
+            _ => self.base_rules.apply(factory, node)
+        }
+    }
+}
+
+//check out the Löb rule: ◇A is true at x iff A is true at some y with xRy, where y can be chosen as the last such world,
+//thus □¬A is true at y. This keeps the tableau finite on transitive frames: the same possibility cannot be expanded again
+struct LobRules
+{
+    base_rules : LogicRuleCollection,
+    modality : Rc<Modality<NormalModalLogic>>,
+}
+
+impl LobRules
+{
+    fn wrap(base_rules : LogicRuleCollection, modality : Rc<Modality<NormalModalLogic>>) -> LobRules
+    {
+        return LobRules { base_rules, modality };
+    }
+}
+
+impl LogicRule for LobRules
+{
+    fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
+    {
+        let (p, non_p, extras) = match node.formula.as_ref()
+        {
+            Possible(box p, extras) =>
+            {
+                let non_p = match p
+                {
+                    Non(box p, _) => p.with_sign(extras.sign),
+                    _ => Non(bx!(p.clone()), extras.clone()),
+                };
+
+                (p.with_sign(extras.sign), non_p, extras)
+            }
+
+            Non(box StrictImply(box p, box q, _), extras) =>
+            {
+                let p_imply_q = Imply(bx!(p.clone()), bx!(q.clone()), extras.clone());
+                let non_p_imply_q = Non(bx!(p_imply_q.clone()), extras.clone());
+
+                (non_p_imply_q, p_imply_q, extras)
+            }
+
+            _ => return self.base_rules.apply(factory, node)
+        };
+
+        let (result, forked_world) = self.modality.apply_possibility_with_forked_world(factory, node, &p, extras);
+        let Some(forked_world) = forked_world else { return result };
+
+        let mut results = LogicRuleResultCollection::with(result);
+        let necessary_non_p = Necessary(bx!(non_p), node.formula.get_extras()).in_world(forked_world);
+        let necessary_non_p_node = factory.new_node(necessary_non_p);
+        results.push(LogicRuleResult::Subtree(ProofSubtree::with_middle_node(necessary_non_p_node)));
+
+        return results.joined();
+    }
+}
@@ -0,0 +1,284 @@
+use std::any::Any;
+use std::rc::Rc;
+use box_macro::bx;
+use crate::formula::{Formula, FormulaExtras, Program};
+use crate::formula::Formula::{And, Imply, Non, Or, ProgramNecessary, ProgramPossible};
+use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult};
+use crate::logic::common_modal_logic::{Modality, ModalityRef};
+use crate::logic::propositional_logic::PropositionalLogicRules;
+use crate::logic::rule_apply_factory::RuleApplyFactory;
+use crate::parser::token_types::TokenTypeID;
+use crate::semantics::binary_logic_semantics::EventualityLogicSemantics;
+use crate::semantics::Semantics;
+use crate::tree::node::ProofTreeNode;
+use crate::tree::node_factory::ProofTreeNodeID;
+use crate::tree::subtree::ProofSubtree;
+
+//[α]A means "after every run of the program α, A holds", ⟨α⟩A means "after some run of the program α, A holds".
+//Programs are built from atomic programs, each with its own accessibility relation, with sequence α;β, choice α∪β,
+//test A? and iteration α*. Compound programs are reduced to atomic programs, eg: [α;β]A = [α][β]A.
+pub struct PropositionalDynamicLogic {}
+
+impl Logic for PropositionalDynamicLogic
+{
+    fn get_name(&self) -> LogicName { LogicName::of("PropositionalDynamicLogic") }
+    fn as_any(&self) -> &dyn Any { self }
+
+    fn get_semantics(&self) -> Box<dyn Semantics>
+    {
+        return Box::new(EventualityLogicSemantics { is_eventuality_closed_by_negation:|formula| matches!(formula, ProgramPossible(..)) });
+    }
+
+    fn get_parser_syntax(&self) -> Vec<TokenTypeID>
+    {
+        return vec!
+        [
+            TokenTypeID::AtomicWithoutArgs,
+            TokenTypeID::Non, TokenTypeID::And, TokenTypeID::Or,
+            TokenTypeID::Imply, TokenTypeID::BiImply,
+            TokenTypeID::ProgramNecessary, TokenTypeID::ProgramPossible,
+            TokenTypeID::OpenParenthesis, TokenTypeID::ClosedParenthesis
+        ]
+    }
+
+    fn get_rules(&self) -> LogicRuleCollection
+    {
+        let modality = Rc::new(self.get_modality());
+        return LogicRuleCollection::of(vec!
+        [
+            Box::new(PropositionalLogicRules {}),
+            Box::new(PropositionalDynamicLogicRules { modality }),
+        ]);
+    }
+
+    fn get_modality_ref(&self) -> Option<ModalityRef>
+    {
+        return Some(ModalityRef::new(self.get_modality()));
+    }
+}
+
+impl PropositionalDynamicLogic
+{
+    pub fn get_modality(&self) -> Modality<PropositionalDynamicLogic>
+    {
+        return Modality
+        {
+            is_possibility_applicable: |_, _, _| true,
+            is_necessity_applicable: |_, _, _| true,
+            is_blocking_applicable: |_| false,
+            add_missing_graph_vertices: |_, _| {},
+        };
+    }
+}
+
+struct PropositionalDynamicLogicRules
+{
+    modality : Rc<Modality<PropositionalDynamicLogic>>
+}
+
+impl LogicRule for PropositionalDynamicLogicRules
+{
+    fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
+    {
+        return match node.formula.as_ref()
+        {
+            Non(box ProgramNecessary(program, box p, _), extras) =>
+            {
+                let non_p = Non(bx!(p.clone()), extras.clone());
+                let possible_non_p = ProgramPossible(program.clone(), bx!(non_p), extras.clone());
+                let possible_non_p_node = factory.new_node(possible_non_p);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(possible_non_p_node));
+            }
+
+            Non(box ProgramPossible(program, box p, _), extras) =>
+            {
+                let non_p = Non(bx!(p.clone()), extras.clone());
+                let necessary_non_p = ProgramNecessary(program.clone(), bx!(non_p), extras.clone());
+                let necessary_non_p_node = factory.new_node(necessary_non_p);
+
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(necessary_non_p_node));
+            }
+
+            ProgramNecessary(program, box p, extras) => self.apply_program_necessity(factory, node, program, p, extras),
+
+            ProgramPossible(program, box p, extras) => self.apply_program_possibility(factory, node, program, p, extras),
+
+            _ => LogicRuleResult::Empty
+        }
+    }
+}
+
+impl PropositionalDynamicLogicRules
+{
+    fn apply_program_necessity(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        program : &Program, p : &Formula, extras : &FormulaExtras,
+    ) -> LogicRuleResult
+    {
+        let output_formula = match program
+        {
+            Program::AtomicProgram(name) =>
+            {
+                let p_with_parent_sign = p.with_sign(extras.sign);
+                return self.modality.apply_necessity_on_relation(factory, node, name, &p_with_parent_sign, extras);
+            }
+
+            //[α;β]A = [α][β]A
+            Program::Sequence(box a, box b) =>
+            {
+                let necessary_b_p = ProgramNecessary(b.clone(), bx!(p.clone()), extras.clone());
+                ProgramNecessary(a.clone(), bx!(necessary_b_p), extras.clone())
+            }
+
+            //[α∪β]A = [α]A ∧ [β]A
+            Program::Choice(box a, box b) =>
+            {
+                let necessary_a_p = ProgramNecessary(a.clone(), bx!(p.clone()), extras.clone());
+                let necessary_b_p = ProgramNecessary(b.clone(), bx!(p.clone()), extras.clone());
+                And(bx!(necessary_a_p), bx!(necessary_b_p), extras.clone())
+            }
+
+            //[B?]A = B → A
+            Program::Test(box q) =>
+            {
+                Imply(bx!(q.in_world(extras.possible_world)), bx!(p.clone()), extras.clone())
+            }
+
+            //[α*]A = A ∧ [α][α*]A
+            Program::Iteration(box a) =>
+            {
+                if factory.was_already_applied_on_world(node) { return LogicRuleResult::Empty }
+
+                let necessary_a_iteration = ProgramNecessary(a.clone(), bx!(node.formula.as_ref().clone()), extras.clone());
+                And(bx!(p.clone()), bx!(necessary_a_iteration), extras.clone())
+            }
+        };
+
+        let output_node = factory.new_node(output_formula);
+        return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(output_node));
+    }
+
+    fn apply_program_possibility(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        program : &Program, p : &Formula, extras : &FormulaExtras,
+    ) -> LogicRuleResult
+    {
+        let output_formula = match program
+        {
+            Program::AtomicProgram(name) =>
+            {
+                let p_with_parent_sign = p.with_sign(extras.sign);
+                return self.modality.apply_possibility_on_relation(factory, node, name, &p_with_parent_sign, extras);
+            }
+
+            //⟨α;β⟩A = ⟨α⟩⟨β⟩A
+            Program::Sequence(box a, box b) =>
+            {
+                let possible_b_p = ProgramPossible(b.clone(), bx!(p.clone()), extras.clone());
+                ProgramPossible(a.clone(), bx!(possible_b_p), extras.clone())
+            }
+
+            //⟨α∪β⟩A = ⟨α⟩A ∨ ⟨β⟩A
+            Program::Choice(box a, box b) =>
+            {
+                let possible_a_p = ProgramPossible(a.clone(), bx!(p.clone()), extras.clone());
+                let possible_b_p = ProgramPossible(b.clone(), bx!(p.clone()), extras.clone());
+                Or(bx!(possible_a_p), bx!(possible_b_p), extras.clone())
+            }
+
+            //⟨B?⟩A = B ∧ A
+            Program::Test(box q) =>
+            {
+                And(bx!(q.in_world(extras.possible_world)), bx!(p.clone()), extras.clone())
+            }
+
+            Program::Iteration(box a) =>
+            {
+                if factory.was_already_applied_on_world(node) { return LogicRuleResult::Empty }
+
+                return self.apply_iteration_possibility(factory, node, a, p, extras);
+            }
+        };
+
+        let output_node = factory.new_node(output_formula);
+        return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(output_node));
+    }
+
+    fn apply_iteration_possibility(&self,
+        factory : &mut RuleApplyFactory, node : &ProofTreeNode,
+        program : &Program, p : &Formula, extras : &FormulaExtras,
+    ) -> LogicRuleResult
+    {
+        let mut output_subtrees : Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
+        let eventuality_unfolding_formulas = self.get_eventuality_unfolding_formulas(program, p);
+
+        for path in factory.tree.get_paths_that_goes_through_node(node)
+        {
+            //the eventuality ⟨α*⟩A loops, A is reached from the looping world or never
+            let subtree = if let Some(looping_world) = self.modality.find_looping_world(&path, node, &eventuality_unfolding_formulas)
+            {
+                let comment_node = factory.new_node(Formula::Comment(format!("loop on {}", looping_world).into()));
+                let non_eventuality_node = factory.new_node(Non(bx!(node.formula.as_ref().clone()), extras.clone()));
+                ProofSubtree::with_middle_vertical_nodes(vec![comment_node, non_eventuality_node])
+            }
+            else
+            {
+                //⟨α*⟩A = A ∨ ⟨α⟩⟨α*⟩A
+                let possible_a_iteration = ProgramPossible(program.clone(), bx!(node.formula.as_ref().clone()), extras.clone());
+                let disjunction_node = factory.new_node(Or(bx!(p.clone()), bx!(possible_a_iteration), extras.clone()));
+                ProofSubtree::with_middle_node(disjunction_node)
+            };
+
+            output_subtrees.push((path.get_leaf_node_id(), subtree));
+        }
+
+        return LogicRuleResult::Subtrees(output_subtrees);
+    }
+
+    //formulas spawned on a world by unfolding the eventuality ⟨α*⟩A once, up to the atomic programs of α
+    fn get_eventuality_unfolding_formulas(&self, program : &Program, p : &Formula) -> Vec<Formula>
+    {
+        let extras = FormulaExtras::empty();
+        let eventuality = ProgramPossible(Program::Iteration(bx!(program.clone())), bx!(p.clone()), extras.clone());
+        let possible_eventuality = ProgramPossible(program.clone(), bx!(eventuality), extras.clone());
+
+        let mut formulas = vec![Or(bx!(p.clone()), bx!(possible_eventuality.clone()), extras.clone())];
+        self.get_program_possibility_unfolding_formulas(&possible_eventuality, &mut formulas);
+
+        return formulas.iter().map(|formula| formula.with_stripped_extras()).collect();
+    }
+
+    fn get_program_possibility_unfolding_formulas(&self, formula : &Formula, output : &mut Vec<Formula>)
+    {
+        output.push(formula.clone());
+
+        if let ProgramPossible(program, box p, extras) = formula
+        {
+            match program
+            {
+                Program::Sequence(box a, box b) =>
+                {
+                    let possible_b_p = ProgramPossible(b.clone(), bx!(p.clone()), extras.clone());
+                    self.get_program_possibility_unfolding_formulas(&ProgramPossible(a.clone(), bx!(possible_b_p), extras.clone()), output);
+                }
+
+                Program::Choice(box a, box b) =>
+                {
+                    let possible_a_p = ProgramPossible(a.clone(), bx!(p.clone()), extras.clone());
+                    let possible_b_p = ProgramPossible(b.clone(), bx!(p.clone()), extras.clone());
+                    output.push(Or(bx!(possible_a_p.clone()), bx!(possible_b_p.clone()), extras.clone()));
+                    self.get_program_possibility_unfolding_formulas(&possible_a_p, output);
+                    self.get_program_possibility_unfolding_formulas(&possible_b_p, output);
+                }
+
+                Program::Test(box q) =>
+                {
+                    output.push(And(bx!(q.clone()), bx!(p.clone()), extras.clone()));
+                }
+
+                Program::AtomicProgram(_) | Program::Iteration(_) => {}
+            }
+        }
+    }
+}
@@ -49,7 +49,7 @@ impl LogicRule for PropositionalLogicRules
 {
     fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             Non(box Non(box p, _), _) =>
             {
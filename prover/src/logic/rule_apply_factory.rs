@@ -3,7 +3,7 @@ use crate::formula::Formula;
 use crate::graph::Graph;
 use crate::logic::common_modal_logic::NecessityReapplicationData;
 use crate::logic::Logic;
-use crate::problem::ProblemFlags;
+use crate::problem::{ProblemFlags, ProofLimit};
 use crate::tree::node::ProofTreeNode;
 use crate::tree::node_factory::{ProofTreeNodeFactory, ProofTreeNodeID};
 use crate::tree::ProofTree;
@@ -14,6 +14,7 @@ pub struct RuleApplyFactory<'a>
     pub tree_node_factory : &'a mut ProofTreeNodeFactory,
     pub modality_graph : &'a mut Graph,
     pub problem_flags : &'a ProblemFlags,
+    pub limit_reached_by_rules : &'a mut Option<ProofLimit>,
 }
 
 impl <'a> RuleApplyFactory<'a>
@@ -33,6 +34,12 @@ impl <'a> RuleApplyFactory<'a>
         return self.tree_node_factory.new_node_with_subnode(formula, child);
     }
 
+    //a rule skipped some work because of a limit, eg: deep function terms, thus the proof might be incomplete
+    pub fn set_reached_limit(&mut self, limit : ProofLimit)
+    {
+        *self.limit_reached_by_rules = Some(limit);
+    }
+
     pub fn set_spawner_node_id(&mut self, spawner_node_id_option : Option<ProofTreeNodeID>)
     {
         if let Some(spawner_node_id) = spawner_node_id_option &&
@@ -1,8 +1,11 @@
 use std::any::Any;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
+use std::rc::Rc;
 use box_macro::bx;
+use smol_str::SmolStr;
 use crate::default_log_line_formatter;
-use crate::formula::Formula::{InFuture, InPast, Necessary, Non, Possible};
+use crate::formula::PossibleWorld;
+use crate::formula::Formula::{Comment, InFuture, InPast, Necessary, Non, Possible};
 use crate::graph::{Graph, GraphVertex};
 use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult};
 use crate::logic::common_modal_logic::{Modality, ModalityRef};
@@ -12,6 +15,8 @@ use crate::parser::token_types::TokenTypeID;
 use crate::semantics::binary_logic_semantics::BinaryLogicSemantics;
 use crate::semantics::Semantics;
 use crate::tree::node::ProofTreeNode;
+use crate::tree::node_factory::ProofTreeNodeID;
+use crate::tree::path::ProofTreePath;
 use crate::tree::subtree::ProofSubtree;
 
 //check out book chapters 3, 14-17
@@ -77,13 +82,8 @@ impl TemporalModalLogic
         {
             is_possibility_applicable: |_, _, _| true,
             is_necessity_applicable: |_, _, _| true,
-            add_missing_graph_vertices: |logic, graph|
-            {
-                if logic.is_extended
-                {
-                    graph.add_missing_temporal_convergence_vertices();
-                }
-            },
+            is_blocking_applicable: |_| false,
+            add_missing_graph_vertices: |_, _| {},
         };
     }
 }
@@ -105,7 +105,7 @@ impl LogicRule for TemporalModalLogicRules
 {
     fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             Non(box Possible(box InPast(box p, _), _), extras) =>
             {
@@ -183,59 +183,125 @@ impl LogicRule for TemporalModalLogicRules
                 return self.modality.apply_necessity(factory, node, p, extras);
             }
 
+            Comment(..) =>
+            {
+                let logic_pointer = factory.get_logic().clone();
+                let logic = logic_pointer.cast_to::<TemporalModalLogic>().unwrap();
+                if !logic.is_extended { return LogicRuleResult::Empty }
+
+                //frame facts are written on comment nodes, so convergence is checked after every one of them
+                return self.apply_convergence(factory, node);
+            }
+
             _ => LogicRuleResult::Empty
         };
     }
 }
 
-impl Graph
+impl TemporalModalLogicRules
 {
-    fn invert_all_vertices(&mut self)
+    //check out book section 3.6: on extended tense logic, time converges both forward and backward
+    //irj, irk => jrk | j=k | krj and jri, kri => jrk | j=k | krj, each branch having its own graph
+    fn apply_convergence(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
     {
-        let inverted_vertices = self.vertices()
-            .map(|vertex| GraphVertex::new(vertex.to, vertex.from))
-            .collect::<BTreeSet<GraphVertex>>();
+        let mut output_subtrees : Vec<(ProofTreeNodeID, ProofSubtree)> = Vec::new();
 
-        self.set_vertices(inverted_vertices);
+        let paths = factory.tree.get_paths_that_goes_through_node(node).into_iter()
+            .filter(|path| path.nodes.iter().all(|node| !node.is_contradictory))
+            .collect::<Vec<ProofTreePath>>();
+
+        for path in paths
+        {
+            if let Some((j, k)) = self.find_non_convergent_worlds(factory, &path)
+            {
+                let left_nodes = self.add_branch_vertex(factory, &path, GraphVertex::new(j, k));
+                let middle_nodes = factory.add_branch_world_identity(&path, j, k);
+                let right_nodes = self.add_branch_vertex(factory, &path, GraphVertex::new(k, j));
+
+                let [left, middle, right] = [left_nodes, middle_nodes, right_nodes]
+                    .map(|nodes| *ProofSubtree::with_middle_vertical_nodes(nodes).middle.unwrap());
+
+                let subtree = ProofSubtree::with_left_middle_right_nodes(left, middle, right);
+                output_subtrees.push((path.get_leaf_node_id(), subtree));
+            }
+        }
+
+        if output_subtrees.is_empty() { return LogicRuleResult::Empty }
+        return LogicRuleResult::Subtrees(output_subtrees);
     }
 
-    fn add_missing_temporal_convergence_vertices(&mut self)
+    fn find_non_convergent_worlds(&self, factory : &RuleApplyFactory, path : &ProofTreePath) -> Option<(PossibleWorld, PossibleWorld)>
     {
-        let mut forward_vertices_to_add : Vec<GraphVertex> = vec![];
-        let mut backward_vertices_to_add : Vec<GraphVertex> = vec![];
+        //worlds identical to another world were already merged into that world
+        let merged_possible_worlds = factory.modality_graph.world_identities_on_path(path).into_iter()
+            .map(|(_world, identical_world)| identical_world)
+            .collect::<BTreeSet<PossibleWorld>>();
+
+        let possible_worlds_on_path = path.nodes.iter()
+            .map(|node| node.formula.get_possible_world())
+            .filter(|possible_world| !merged_possible_worlds.contains(possible_world))
+            .collect::<BTreeSet<PossibleWorld>>();
+
+        let vertices = factory.modality_graph.vertices_on_path(path).into_iter()
+            .filter(|vertex| possible_worlds_on_path.contains(&vertex.from) && possible_worlds_on_path.contains(&vertex.to))
+            .collect::<BTreeSet<GraphVertex>>();
+
+        let are_convergent = |j : PossibleWorld, k : PossibleWorld|
+            j == k || vertices.contains(&GraphVertex::new(j, k)) || vertices.contains(&GraphVertex::new(k, j));
 
-        for i_vertex in self.vertices()
+        for i_vertex in &vertices
         {
-            for j_vertex in self.vertices()
+            for j_vertex in &vertices
             {
-                if i_vertex != j_vertex && i_vertex.from == j_vertex.from
+                if i_vertex.from == j_vertex.from && !are_convergent(i_vertex.to, j_vertex.to)
                 {
-                    let forward_convergent_vertex = GraphVertex::new(i_vertex.to, j_vertex.to);
-                    let alternate_forward_convergent_vertex = GraphVertex::new(j_vertex.to, i_vertex.to);
-                    if !forward_vertices_to_add.contains(&alternate_forward_convergent_vertex)
-                    {
-                        forward_vertices_to_add.push(forward_convergent_vertex);
-                    }
+                    return Some((i_vertex.to, j_vertex.to));
                 }
 
-                if i_vertex != j_vertex && i_vertex.to == j_vertex.to
+                if i_vertex.to == j_vertex.to && !are_convergent(i_vertex.from, j_vertex.from)
                 {
-                    let backward_convergent_vertex = GraphVertex::new(i_vertex.from, j_vertex.from);
-                    let alternate_backward_convergent_vertex = GraphVertex::new(j_vertex.from, i_vertex.from);
-                    if !backward_vertices_to_add.contains(&alternate_backward_convergent_vertex)
-                    {
-                        backward_vertices_to_add.push(backward_convergent_vertex);
-                    }
+                    return Some((i_vertex.from, j_vertex.from));
                 }
             }
         }
 
-        self.set_log_line_formatter(bx!(|v| format!("{}φ{}\n", v.from, v.to)));
-        self.add_vertices(forward_vertices_to_add);
+        return None;
+    }
 
-        self.set_log_line_formatter(bx!(|v| format!("{}β{}\n", v.from, v.to)));
-        self.add_vertices(backward_vertices_to_add);
+    fn add_branch_vertex(&self, factory : &mut RuleApplyFactory, path : &ProofTreePath, vertex : GraphVertex) -> Vec<ProofTreeNode>
+    {
+        let mut comment_node = factory.new_node(Comment(SmolStr::default()));
+        factory.modality_graph.add_branch_vertices(comment_node.id, vec![vertex.clone()]);
+        comment_node.formula = Rc::new(Comment(factory.modality_graph.flush_log()));
 
-        self.set_log_line_formatter(default_log_line_formatter!());
+        let mut output_nodes = vec![comment_node];
+        output_nodes.append(&mut factory.reapply_necessities_on_branch_vertices(path, vec![vertex]));
+        return output_nodes;
+    }
+}
+
+impl Graph
+{
+    fn invert_all_vertices(&mut self)
+    {
+        let inverted_vertices = self.vertices()
+            .map(|vertex| vertex.inverted())
+            .collect::<BTreeSet<GraphVertex>>();
+
+        let inverted_branch_vertices = self.branch_vertices()
+            .map(|(node_id, vertices)| (*node_id, vertices.iter().map(|vertex| vertex.inverted()).collect()))
+            .collect::<BTreeMap<ProofTreeNodeID, BTreeSet<GraphVertex>>>();
+
+        self.set_vertices(inverted_vertices);
+        self.set_branch_vertices(inverted_branch_vertices);
+        self.invert_necessity_reapplications();
+    }
+}
+
+impl GraphVertex
+{
+    fn inverted(&self) -> GraphVertex
+    {
+        return GraphVertex::new(self.to, self.from);
     }
 }
@@ -0,0 +1,348 @@
+use std::any::Any;
+use std::collections::BTreeSet;
+use std::rc::Rc;
+use anyhow::{anyhow, Context, Result};
+use smol_str::{format_smolstr, SmolStr, ToSmolStr};
+use itertools::Itertools;
+use crate::formula::Formula::{And, Atomic, BiImply, Comment, Imply, Non, Or};
+use crate::formula::{Formula, FuzzyTag, FuzzyTags};
+use crate::logic::{Logic, LogicName, LogicRule, LogicRuleCollection, LogicRuleResult};
+use crate::logic::common_modal_logic::ModalityRef;
+use crate::logic::rule_apply_factory::RuleApplyFactory;
+use crate::parser::token_types::TokenTypeID;
+use crate::semantics::truth_matrix_logic_semantics::TruthMatrixLogicSemantics;
+use crate::semantics::Semantics;
+use crate::tree::node::ProofTreeNode;
+use crate::tree::subtree::ProofSubtree;
+
+pub const TRUTH_MATRIX_LOGIC_NAME_SUFFIX : &str = "MatrixLogic";
+pub const MAX_NUMBER_OF_TRUTH_VALUES : u8 = 16;
+
+//check out book chapter 7: a finite many-valued logic defined by its truth tables and designated values.
+//truth values are indices into the truth_values vector, tables are indexed by the values of the operands.
+pub struct TruthMatrix
+{
+    pub truth_values : Vec<SmolStr>,
+    pub designated_values : BTreeSet<usize>,
+    pub non : Vec<usize>,
+    pub and : Vec<Vec<usize>>,
+    pub or : Vec<Vec<usize>>,
+    pub imply : Vec<Vec<usize>>,
+}
+
+impl TruthMatrix
+{
+    pub fn number_of_truth_values(&self) -> usize
+    {
+        return self.truth_values.len();
+    }
+
+    pub fn all_values(&self) -> BTreeSet<usize>
+    {
+        return (0..self.number_of_truth_values()).collect();
+    }
+
+    pub fn non_designated_values(&self) -> BTreeSet<usize>
+    {
+        return self.all_values().difference(&self.designated_values).cloned().collect();
+    }
+
+    //p ≡ q is (p ⊃ q) ∧ (q ⊃ p) on every matrix
+    pub fn bi_imply(&self, x : usize, y : usize) -> usize
+    {
+        return self.and[self.imply[x][y]][self.imply[y][x]];
+    }
+
+    //the truth values a formula can take, given the values of its operands
+    pub fn get_range(&self, formula : &Formula) -> BTreeSet<usize>
+    {
+        let all_values = self.all_values();
+        let binary_range = |table : &dyn Fn(usize, usize) -> usize| all_values.iter()
+            .flat_map(|x| all_values.iter().map(move |y| table(*x, *y)))
+            .collect::<BTreeSet<usize>>();
+
+        return match formula
+        {
+            Non(..) => self.non.iter().cloned().collect(),
+            And(..) => binary_range(&|x, y| self.and[x][y]),
+            Or(..) => binary_range(&|x, y| self.or[x][y]),
+            Imply(..) => binary_range(&|x, y| self.imply[x][y]),
+            BiImply(..) => binary_range(&|x, y| self.bi_imply(x, y)),
+            _ => all_values.clone(),
+        };
+    }
+
+    //formulas are tagged with the set of truth values they may take, premises (without tags) are designated
+    pub fn get_values(&self, formula : &Formula) -> BTreeSet<usize>
+    {
+        let fuzzy_tags = formula.get_fuzzy_tags();
+        if fuzzy_tags.is_empty() { return self.designated_values.clone() };
+
+        return fuzzy_tags.iter()
+            .filter_map(|tag| self.truth_values.iter().position(|value| *value == tag.object_name))
+            .collect();
+    }
+
+    pub fn to_fuzzy_tags(&self, values : &BTreeSet<usize>) -> FuzzyTags
+    {
+        return FuzzyTags::new(values.iter().map(|value| FuzzyTag::new(self.truth_values[*value].clone())).collect());
+    }
+
+    fn from_functions(truth_values : Vec<SmolStr>, designated_values : BTreeSet<usize>,
+        non : impl Fn(usize) -> usize,
+        and : impl Fn(usize, usize) -> usize,
+        or : impl Fn(usize, usize) -> usize,
+        imply : impl Fn(usize, usize) -> usize,
+    ) -> TruthMatrix
+    {
+        let n = truth_values.len();
+        let table = |f : &dyn Fn(usize, usize) -> usize| (0..n).map(|x| (0..n).map(|y| f(x, y)).collect()).collect();
+
+        return TruthMatrix
+        {
+            non: (0..n).map(&non).collect(),
+            and: table(&and), or: table(&or), imply: table(&imply),
+            truth_values, designated_values,
+        };
+    }
+}
+
+pub struct TruthMatrixLogic
+{
+    pub name : LogicName,
+    pub matrix : Rc<TruthMatrix>,
+}
+
+impl TruthMatrixLogic
+{
+    //Łn: values k/(n-1), ¬x = 1-x, x ⊃ y = min(1, 1-x+y), only 1 is designated
+    pub fn lukasiewicz(n : usize) -> TruthMatrixLogic
+    {
+        let top = n-1;
+        let gcd = |mut x : usize, mut y : usize| { while y != 0 { (x, y) = (y, x % y) }; x };
+        let truth_values = (0..n).map(|k| match k
+        {
+            0 => SmolStr::new("0"),
+            _ if k == top => SmolStr::new("1"),
+            _ => format_smolstr!("{}/{}", k / gcd(k, top), top / gcd(k, top)),
+        }).collect();
+
+        let matrix = TruthMatrix::from_functions(truth_values, BTreeSet::from([top]),
+            |x| top-x, |x, y| x.min(y), |x, y| x.max(y),
+            |x, y| (top+y-x).min(top));
+
+        return TruthMatrixLogic { name:LogicName::of(format!("Lukasiewicz{}{}", n, TRUTH_MATRIX_LOGIC_NAME_SUFFIX).as_str()), matrix:Rc::new(matrix) };
+    }
+
+    //Postn: values 0..n-1, cyclic negation ¬x = x+1 mod n, x ⊃ y = ¬x ∨ y, only n-1 is designated
+    pub fn post(n : usize) -> TruthMatrixLogic
+    {
+        let top = n-1;
+        let truth_values = (0..n).map(|k| k.to_smolstr()).collect();
+
+        let matrix = TruthMatrix::from_functions(truth_values, BTreeSet::from([top]),
+            |x| (x+1) % n, |x, y| x.min(y), |x, y| x.max(y),
+            |x, y| ((x+1) % n).max(y));
+
+        return TruthMatrixLogic { name:LogicName::of(format!("Post{}{}", n, TRUTH_MATRIX_LOGIC_NAME_SUFFIX).as_str()), matrix:Rc::new(matrix) };
+    }
+
+    //K3, LP and RM3 share the values 0 < i < 1, ¬x = 1-x, ∧ as min and ∨ as max
+    fn three_valued(name : &str, designated_values : BTreeSet<usize>, imply : impl Fn(usize, usize) -> usize) -> TruthMatrixLogic
+    {
+        let truth_values = vec![SmolStr::new("0"), SmolStr::new("i"), SmolStr::new("1")];
+        let matrix = TruthMatrix::from_functions(truth_values, designated_values,
+            |x| 2-x, |x, y| x.min(y), |x, y| x.max(y), imply);
+
+        return TruthMatrixLogic { name:LogicName::of(format!("{}{}", name, TRUTH_MATRIX_LOGIC_NAME_SUFFIX).as_str()), matrix:Rc::new(matrix) };
+    }
+
+    pub fn kleene() -> TruthMatrixLogic
+    {
+        return Self::three_valued("Kleene", BTreeSet::from([2]), |x, y| (2-x).max(y));
+    }
+
+    pub fn priest_lp() -> TruthMatrixLogic
+    {
+        return Self::three_valued("LP", BTreeSet::from([1, 2]), |x, y| (2-x).max(y));
+    }
+
+    pub fn rmingle3() -> TruthMatrixLogic
+    {
+        return Self::three_valued("RMingle3", BTreeSet::from([1, 2]), |x, y| if x <= y { (2-x).max(y) } else { (2-x).min(y) });
+    }
+
+    //Belnap's bilattice FOUR: f < n,b < t on the truth order, n and b being incomparable, t and b are designated
+    pub fn belnap() -> TruthMatrixLogic
+    {
+        let (f, b, t) = (0usize, 2usize, 3usize);
+        let truth_values = vec![SmolStr::new("f"), SmolStr::new("n"), SmolStr::new("b"), SmolStr::new("t")];
+
+        let meet = move |x : usize, y : usize| if x == y { x } else if x == t { y } else if y == t { x } else { f };
+        let join = move |x : usize, y : usize| if x == y { x } else if x == f { y } else if y == f { x } else { t };
+        let non = move |x : usize| if x == t { f } else if x == f { t } else { x };
+
+        let matrix = TruthMatrix::from_functions(truth_values, BTreeSet::from([b, t]),
+            non, meet, join, move |x, y| join(non(x), y));
+
+        return TruthMatrixLogic { name:LogicName::of(format!("Belnap{}", TRUTH_MATRIX_LOGIC_NAME_SUFFIX).as_str()), matrix:Rc::new(matrix) };
+    }
+
+    //logics that are not in the catalog, eg: Lukasiewicz5MatrixLogic, Post4MatrixLogic
+    pub fn with_name(name : &str) -> Result<TruthMatrixLogic>
+    {
+        let name_without_suffix = name.strip_suffix(TRUTH_MATRIX_LOGIC_NAME_SUFFIX)
+            .context(format!("{} does not end with {}!", name, TRUTH_MATRIX_LOGIC_NAME_SUFFIX))?;
+
+        let (family, number_of_truth_values) = name_without_suffix.split_at(name_without_suffix.find(|c : char| c.is_ascii_digit()).unwrap_or(name_without_suffix.len()));
+        let number_of_truth_values = number_of_truth_values.parse::<usize>()
+            .context(format!("Invalid number of truth values on {}!", name))?;
+
+        //the tables hold n² values and every formula may branch on each of them
+        let number_of_truth_values = u8::try_from(number_of_truth_values).ok()
+            .filter(|number_of_truth_values| (2..=MAX_NUMBER_OF_TRUTH_VALUES).contains(number_of_truth_values))
+            .context(format!("{} should have between 2 and {} truth values!", name, MAX_NUMBER_OF_TRUTH_VALUES))?;
+
+        return match family
+        {
+            "Lukasiewicz" => Ok(Self::lukasiewicz(number_of_truth_values as usize)),
+            "Post" => Ok(Self::post(number_of_truth_values as usize)),
+            _ => Err(anyhow!("Invalid truth matrix logic {}, expected Lukasiewicz or Post!", family)),
+        };
+    }
+}
+
+impl Logic for TruthMatrixLogic
+{
+    fn get_name(&self) -> LogicName { self.name.clone() }
+    fn as_any(&self) -> &dyn Any { self }
+
+    fn get_semantics(&self) -> Box<dyn Semantics>
+    {
+        return Box::new(TruthMatrixLogicSemantics { matrix:self.matrix.clone() });
+    }
+
+    fn get_parser_syntax(&self) -> Vec<TokenTypeID>
+    {
+        return vec!
+        [
+            TokenTypeID::AtomicWithoutArgs,
+            TokenTypeID::Non, TokenTypeID::And, TokenTypeID::Or,
+            TokenTypeID::Imply, TokenTypeID::BiImply,
+            TokenTypeID::OpenParenthesis, TokenTypeID::ClosedParenthesis
+        ];
+    }
+
+    fn get_rules(&self) -> LogicRuleCollection
+    {
+        return LogicRuleCollection::of(vec!
+        [
+            Box::new(TruthMatrixLogicRules { matrix:self.matrix.clone() })
+        ]);
+    }
+
+    fn get_modality_ref(&self) -> Option<ModalityRef> { None }
+}
+
+//a formula tagged with the values S branches on every row of the truth table that evaluates to a value in S
+struct TruthMatrixLogicRules
+{
+    matrix : Rc<TruthMatrix>
+}
+
+impl LogicRule for TruthMatrixLogicRules
+{
+    fn apply(&self, factory : &mut RuleApplyFactory, node : &ProofTreeNode) -> LogicRuleResult
+    {
+        let values = self.matrix.get_values(&node.formula);
+        if values == self.matrix.all_values() { return LogicRuleResult::Empty };
+
+        return match node.formula.as_ref()
+        {
+            Atomic(..) => LogicRuleResult::Empty,
+
+            Non(box p, _) =>
+            {
+                let p_values = self.matrix.all_values().into_iter()
+                    .filter(|x| values.contains(&self.matrix.non[*x]))
+                    .collect::<BTreeSet<usize>>();
+                if p_values.is_empty() { return LogicRuleResult::Empty };
+
+                let p_node = factory.new_node(self.with_values(p, &p_values));
+                return LogicRuleResult::Subtree(ProofSubtree::with_middle_node(p_node));
+            }
+
+            And(box p, box q, _) => self.apply_binary_operator(factory, p, q, &values, |x, y| self.matrix.and[x][y]),
+            Or(box p, box q, _) => self.apply_binary_operator(factory, p, q, &values, |x, y| self.matrix.or[x][y]),
+            Imply(box p, box q, _) => self.apply_binary_operator(factory, p, q, &values, |x, y| self.matrix.imply[x][y]),
+            BiImply(box p, box q, _) => self.apply_binary_operator(factory, p, q, &values, |x, y| self.matrix.bi_imply(x, y)),
+
+            _ => LogicRuleResult::Empty
+        }
+    }
+}
+
+impl TruthMatrixLogicRules
+{
+    fn apply_binary_operator(&self,
+        factory : &mut RuleApplyFactory, p : &Formula, q : &Formula,
+        values : &BTreeSet<usize>, table : impl Fn(usize, usize) -> usize,
+    ) -> LogicRuleResult
+    {
+        //for each value x of p, the values of q that evaluate to a value in the set
+        let rows = self.matrix.all_values().into_iter()
+            .map(|x| (x, self.matrix.all_values().into_iter().filter(|y| values.contains(&table(x, *y))).collect()))
+            .filter(|(_, q_values) : &(usize, BTreeSet<usize>)| !q_values.is_empty())
+            .collect::<Vec<(usize, BTreeSet<usize>)>>();
+        if rows.is_empty() { return LogicRuleResult::Empty };
+
+        return LogicRuleResult::Subtree(self.new_branches(factory, p, q, &rows));
+    }
+
+    //up to three rows are split as x1 | x2 | x3, more rows are nested as x1 | x2 | (x3 | ... | xn)
+    fn new_branches(&self, factory : &mut RuleApplyFactory, p : &Formula, q : &Formula, rows : &[(usize, BTreeSet<usize>)]) -> ProofSubtree
+    {
+        let mut branches = rows.iter().take(2)
+            .map(|(x, q_values)| self.new_branch(factory, p, q, *x, q_values))
+            .collect::<Vec<ProofTreeNode>>();
+
+        return match rows.len()
+        {
+            1 => ProofSubtree::with_middle_node(branches.remove(0)),
+            2 => ProofSubtree::with_left_right_nodes(branches.remove(0), branches.remove(0)),
+            3 =>
+            {
+                let right_node = self.new_branch(factory, p, q, rows[2].0, &rows[2].1);
+                ProofSubtree::with_left_middle_right_nodes(branches.remove(0), branches.remove(0), right_node)
+            }
+            _ =>
+            {
+                //an atomic p is known to take one of the remaining values, a compound p is already decomposed
+                let other_p_values = rows[2..].iter().map(|(x, _)| *x).collect::<BTreeSet<usize>>();
+                let mut right_node = if let Atomic(..) = p { factory.new_node(self.with_values(p, &other_p_values)) }
+                else { factory.new_node(Comment(format_smolstr!("{} ∈ {{{}}}", p, other_p_values.iter().map(|x| &self.matrix.truth_values[*x]).join(", ")))) };
+
+                let other_branches = self.new_branches(factory, p, q, &rows[2..]);
+                (right_node.left, right_node.middle, right_node.right) = (other_branches.left, other_branches.middle, other_branches.right);
+                ProofSubtree::with_left_middle_right_nodes(branches.remove(0), branches.remove(0), right_node)
+            }
+        };
+    }
+
+    //p is x and q takes one of the values that evaluate, along with x, to a value in the set
+    fn new_branch(&self, factory : &mut RuleApplyFactory, p : &Formula, q : &Formula, x : usize, q_values : &BTreeSet<usize>) -> ProofTreeNode
+    {
+        let mut nodes = vec![factory.new_node(self.with_values(p, &BTreeSet::from([x])))];
+        if *q_values != self.matrix.all_values()
+        {
+            nodes.push(factory.new_node(self.with_values(q, q_values)));
+        }
+
+        return *ProofSubtree::with_middle_vertical_nodes(nodes).middle.unwrap();
+    }
+
+    fn with_values(&self, formula : &Formula, values : &BTreeSet<usize>) -> Formula
+    {
+        return formula.with_fuzzy_tags(self.matrix.to_fuzzy_tags(values));
+    }
+}
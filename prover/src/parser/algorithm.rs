@@ -1,5 +1,6 @@
 use std::rc::Rc;
 use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
 use smol_str::ToSmolStr;
 use crate::codeloc;
 use crate::formula::{AtomicFormulaExtras, Formula};
@@ -34,12 +35,18 @@ struct LogicalExpressionParserImpl<'a>
     state : &'a mut LogicalExpressionParserState,
 }
 
-const REPLACE_TABLE : [&str; 52] =
+const REPLACE_TABLE : [&str; 56] =
 [
     "∀", " ∀", "∃", " ∃", "(", " ( ", ")", " ) ", ", ", ",", "◇", " ◇ ", "□", " □ ",
     "~", " ~ ", "¬", " ¬ ", "!", " ! ", "&", " & ", "∧", " ∧ ", "|", " | ", "∨", " ∨ ",
     "→", " → ", "⇒", " ⇒ ", "⊃", " ⊃ ", "⥽", " ⥽ ", "↔", " ↔ ", "⇔", " ⇔ ", "≡", " ≡ ",
-    "ᶠ", " ᶠ ", "ᵖ", " ᵖ ", "ᐅ", " ᐅ ", " = ", "=", "𝔈", " 𝔈"
+    "ᶠ", " ᶠ ", "ᵖ", " ᵖ ", "ᐅ", " ᐅ ", " = ", "=", "𝔈", " 𝔈", "◇ _", "◇_", "□ _", "□_"
+];
+
+//the dot ends the role of a restriction, eg: ∃r.C
+const DESCRIPTION_LOGIC_REPLACE_TABLE : [&str; 8] =
+[
+    "⊓", " ⊓ ", "⊔", " ⊔ ", "⊑", " ⊑ ", ".", ". "
 ];
 
 impl <'a> LogicalExpressionParserImpl<'a>
@@ -48,15 +55,51 @@ impl <'a> LogicalExpressionParserImpl<'a>
     {
         let token_types = TokenType::get_types().context(codeloc!())?;
 
-        let mut prepared_text = text.clone();
-        for i in (0..REPLACE_TABLE.len()).step_by(2)
+        let legal_syntax_in_this_logic = logic.get_parser_syntax();
+
+        //programs of dynamic logic, eg: [a;(b∪c)*], are single words, parsed later by their own grammar
+        let mut prepared_text = String::new();
+        for (text_piece, is_program) in Self::split_programs(text, &legal_syntax_in_this_logic)
         {
-            prepared_text = prepared_text.replace(REPLACE_TABLE[i], REPLACE_TABLE[i+1]);
+            if is_program
+            {
+                let program_without_spaces = text_piece.split_whitespace().collect::<String>();
+                prepared_text = format!("{} {} ", prepared_text, program_without_spaces);
+            }
+            else
+            {
+                //function terms, eg: f(x,a), are glued to their neighbours, eg: P[f(x,a)] or f(x,a)=b
+                for (text_subpiece, is_function_term) in Self::split_function_terms(&text_piece, &legal_syntax_in_this_logic)
+                {
+                    if is_function_term
+                    {
+                        let function_term_without_spaces = text_subpiece.split_whitespace().collect::<String>();
+                        prepared_text.push_str(function_term_without_spaces.as_str());
+                        continue;
+                    }
+
+                    let mut prepared_text_piece = text_subpiece;
+                    for i in (0..REPLACE_TABLE.len()).step_by(2)
+                    {
+                        prepared_text_piece = prepared_text_piece.replace(REPLACE_TABLE[i], REPLACE_TABLE[i+1]);
+                    }
+
+                    if legal_syntax_in_this_logic.contains(&TokenTypeID::RoleExists)
+                    {
+                        for i in (0..DESCRIPTION_LOGIC_REPLACE_TABLE.len()).step_by(2)
+                        {
+                            prepared_text_piece = prepared_text_piece.replace(DESCRIPTION_LOGIC_REPLACE_TABLE[i], DESCRIPTION_LOGIC_REPLACE_TABLE[i+1]);
+                        }
+                    }
+
+                    prepared_text.push_str(prepared_text_piece.as_str());
+                }
+            }
         }
 
         let tokens : Vec<Token> = prepared_text.split(" ")
             .map(|word| word.trim()).filter(|word| !word.is_empty())
-            .flat_map(|word| Self::get_tokens(word, &token_types))
+            .flat_map(|word| Self::get_tokens(word, &token_types, &legal_syntax_in_this_logic))
             .collect();
 
         if tokens.is_empty()
@@ -64,7 +107,6 @@ impl <'a> LogicalExpressionParserImpl<'a>
             return Err(anyhow!("Empty formula {}", text));
         }
 
-        let legal_syntax_in_this_logic = logic.get_parser_syntax();
         for token in &tokens
         {
             if !legal_syntax_in_this_logic.contains(&token.type_id)
@@ -85,7 +127,91 @@ impl <'a> LogicalExpressionParserImpl<'a>
         return Ok(result_formula);
     }
 
-    fn get_tokens(word : &str, token_types : &Vec<TokenType>) -> Vec<Token>
+    fn split_programs(text : &String, legal_syntax_in_this_logic : &Vec<TokenTypeID>) -> Vec<(String, bool)>
+    {
+        if !legal_syntax_in_this_logic.contains(&TokenTypeID::ProgramNecessary)
+        {
+            return vec![(text.clone(), false)];
+        }
+
+        let mut text_pieces : Vec<(String, bool)> = Vec::new();
+        let mut current_text_piece = String::new();
+        let mut depth = 0;
+        for character in text.chars()
+        {
+            if depth == 0 && matches!(character, '[' | '⟨')
+            {
+                text_pieces.push((current_text_piece, false));
+                current_text_piece = String::new();
+            }
+
+            current_text_piece.push(character);
+
+            if matches!(character, '[' | '⟨') { depth += 1 }
+            else if matches!(character, ']' | '⟩') && depth > 0
+            {
+                depth -= 1;
+                if depth == 0
+                {
+                    text_pieces.push((current_text_piece, true));
+                    current_text_piece = String::new();
+                }
+            }
+        }
+
+        text_pieces.push((current_text_piece, false));
+        return text_pieces;
+    }
+
+    fn split_function_terms(text : &String, legal_syntax_in_this_logic : &Vec<TokenTypeID>) -> Vec<(String, bool)>
+    {
+        if !legal_syntax_in_this_logic.contains(&TokenTypeID::AtomicWithArgs)
+        {
+            return vec![(text.clone(), false)];
+        }
+
+        let mut text_pieces : Vec<(String, bool)> = Vec::new();
+        let mut current_text_piece = String::new();
+        let characters = text.chars().collect::<Vec<char>>();
+        let mut index = 0;
+        while index < characters.len()
+        {
+            //a function term starts with a lowercase name followed by a parenthesis, eg: f(, but not with a quantified variable, eg: ∀x(
+            let is_start_of_name = characters[index].is_ascii_lowercase() &&
+                (index == 0 || !matches!(characters[index-1], 'A'..='Z' | 'a'..='z' | '0'..='9' | '_' | '∀' | '∃' | '𝔈'));
+            let end_of_name = (index..characters.len())
+                .find(|i| !matches!(characters[*i], 'A'..='Z' | 'a'..='z' | '0'..='9' | '_'))
+                .unwrap_or(characters.len());
+
+            if is_start_of_name && end_of_name < characters.len() && characters[end_of_name] == '('
+            {
+                let mut depth = 0;
+                let mut end_of_function_term = end_of_name;
+                for (i, character) in characters.iter().enumerate().skip(end_of_name)
+                {
+                    if *character == '(' { depth += 1 }
+                    else if *character == ')' { depth -= 1 }
+                    end_of_function_term = i;
+                    if depth == 0 { break }
+                }
+
+                text_pieces.push((current_text_piece, false));
+                text_pieces.push((characters[index..=end_of_function_term].iter().collect::<String>(), true));
+                current_text_piece = String::new();
+                index = end_of_function_term+1;
+            }
+            else
+            {
+                current_text_piece.push(characters[index]);
+                index += 1;
+            }
+        }
+
+        text_pieces.push((current_text_piece, false));
+        return text_pieces;
+    }
+
+    fn get_tokens(word : &str, token_types : &Vec<TokenType>, legal_syntax_in_this_logic : &Vec<TokenTypeID>) -> Vec<Token>
     {
         if word.is_empty()
         {
@@ -94,8 +220,10 @@ impl <'a> LogicalExpressionParserImpl<'a>
 
         let mut tokens : Vec<Token> = Vec::new();
 
+        //a word can match more token types, eg: P is an atomic formula, but also the deontic permission operator
         if let Some(full_match) = token_types.iter()
-            .find(|token_type| token_type.regex.is_match(word))
+            .filter(|token_type| token_type.regex.is_match(word))
+            .find_or_first(|token_type| legal_syntax_in_this_logic.contains(&token_type.id))
         {
             tokens.push(Token { type_id:full_match.id, value:word.to_string() })
         }
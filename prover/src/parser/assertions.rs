@@ -33,9 +33,9 @@ impl FirstOrderLogicParserAssertions
                 Self::run_assertions_impl(q, &variable_stack)?;
             }
 
-            Formula::Atomic(_, extras) =>
+            Formula::Atomic(..) =>
             {
-                for arg in extras.predicate_args.iter()
+                for arg in p.get_all_predicate_arguments()
                 {
                     if arg.is_variable() && arg.is_rigid_designator() &&
                         !variable_stack.contains(&arg.variable_name)
@@ -49,7 +49,17 @@ impl FirstOrderLogicParserAssertions
             Formula::Possible(box q, _) |
             Formula::Necessary(box q, _) |
             Formula::InPast(box q, _) |
-            Formula::InFuture(box q, _) =>
+            Formula::InFuture(box q, _) |
+            Formula::Next(box q, _) |
+            Formula::AgentPossible(_, box q, _) |
+            Formula::AgentNecessary(_, box q, _) |
+            Formula::EveryoneKnows(_, box q, _) |
+            Formula::CommonKnowledge(_, box q, _) |
+            Formula::DistributedKnowledge(_, box q, _) |
+            Formula::At(_, box q, _) |
+            Formula::Bind(_, box q, _) |
+            Formula::ProgramNecessary(_, box q, _) |
+            Formula::ProgramPossible(_, box q, _) =>
             {
                 Self::run_assertions_impl(q, variable_stack)?;
             }
@@ -59,7 +69,9 @@ impl FirstOrderLogicParserAssertions
             Formula::Imply(box q, box w, _) |
             Formula::BiImply(box q, box w, _) |
             Formula::StrictImply(box q, box w, _) |
-            Formula::Conditional(box q, box w, _) =>
+            Formula::Conditional(box q, box w, _) |
+            Formula::Until(box q, box w, _) |
+            Formula::Since(box q, box w, _) =>
             {
                 Self::run_assertions_impl(q, variable_stack)?;
                 Self::run_assertions_impl(w, variable_stack)?;
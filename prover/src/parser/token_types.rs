@@ -1,12 +1,17 @@
+use std::collections::BTreeSet;
+use std::rc::Rc;
 use regex::Regex;
 use anyhow::{anyhow, Context, Result};
 use box_macro::bx;
-use smol_str::ToSmolStr;
+use smol_str::{SmolStr, ToSmolStr};
 use strum_macros::{Display, EnumIter};
 use substring::Substring;
 use crate::codeloc;
-use crate::formula::{AtomicFormulaExtras, Formula, FormulaExtras, PredicateArgument, PredicateArguments};
+use crate::formula::{AtomicFormulaExtras, Formula, FormulaExtras, PredicateArgument, PredicateArguments, Program};
 use crate::formula::to_string::FormulaFormatOptions;
+use crate::logic::Logic;
+use crate::logic::propositional_dynamic_logic::PropositionalDynamicLogic;
+use crate::parser::algorithm::LogicalExpressionParser;
 use crate::parser::models::{OperatorPrecedence, TokenCategory, TokenType};
 
 #[derive(Eq, PartialEq, Hash, Clone, Copy, EnumIter, Display)]
@@ -16,6 +21,13 @@ pub enum TokenTypeID
     AtomicWithoutArgs, AtomicWithArgs,
     Non, And, Or, Imply, BiImply,
     Possible, Necessary, InPast, InFuture,
+    AgentPossible, AgentNecessary,
+    EveryoneKnows, CommonKnowledge, DistributedKnowledge,
+    Obligatory, Permitted,
+    ConceptIntersection, ConceptUnion, RoleExists, RoleForAll, Subsumption,
+    Next, Until, Since,
+    Nominal, At, Bind,
+    ProgramNecessary, ProgramPossible,
     StrictImply, Conditional,
     OpenParenthesis, ClosedParenthesis,
 }
@@ -92,9 +104,300 @@ impl TokenType
 
             TokenType
             {
-                //matches atomic formulas with args: P(x,y), ...
+                //matches possible for an agent: ◇_a P, ◇_b Q, ...
+                id: TokenTypeID::AgentPossible,
+                regex: Regex::new(r"^◇_[A-Za-z0-9]+$").context(codeloc!())?,
+                category: TokenCategory::UnaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |name, args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    let agent = Self::parse_agent(&name);
+                    return Ok(Formula::AgentPossible(agent, bx!(args[0].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches necessary for an agent: □_a P, □_b Q, ...
+                id: TokenTypeID::AgentNecessary,
+                regex: Regex::new(r"^□_[A-Za-z0-9]+$").context(codeloc!())?,
+                category: TokenCategory::UnaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |name, args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    let agent = Self::parse_agent(&name);
+                    return Ok(Formula::AgentNecessary(agent, bx!(args[0].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches everyone knows: E_{a,b} P, ...
+                id: TokenTypeID::EveryoneKnows,
+                regex: Regex::new(r"^E_\{[A-Za-z0-9]+(,[A-Za-z0-9]+)*\}$").context(codeloc!())?,
+                category: TokenCategory::UnaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |name, args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    let agents = Self::parse_group_of_agents(&name);
+                    return Ok(Formula::EveryoneKnows(agents, bx!(args[0].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches common knowledge: C_{a,b} P, ...
+                id: TokenTypeID::CommonKnowledge,
+                regex: Regex::new(r"^C_\{[A-Za-z0-9]+(,[A-Za-z0-9]+)*\}$").context(codeloc!())?,
+                category: TokenCategory::UnaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |name, args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    let agents = Self::parse_group_of_agents(&name);
+                    return Ok(Formula::CommonKnowledge(agents, bx!(args[0].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches distributed knowledge: D_{a,b} P, ...
+                id: TokenTypeID::DistributedKnowledge,
+                regex: Regex::new(r"^D_\{[A-Za-z0-9]+(,[A-Za-z0-9]+)*\}$").context(codeloc!())?,
+                category: TokenCategory::UnaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |name, args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    let agents = Self::parse_group_of_agents(&name);
+                    return Ok(Formula::DistributedKnowledge(agents, bx!(args[0].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches obligatory: O P, O Q, ... it is the necessity of deontic logics
+                id: TokenTypeID::Obligatory,
+                regex: Regex::new(r"^O$").context(codeloc!())?,
+                category: TokenCategory::UnaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |_,args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    return Ok(Formula::Necessary(bx!(args[0].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches permitted: P P, P Q, ... it is the possibility of deontic logics
+                id: TokenTypeID::Permitted,
+                regex: Regex::new(r"^P$").context(codeloc!())?,
+                category: TokenCategory::UnaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |_,args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    return Ok(Formula::Possible(bx!(args[0].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches the existential restriction of description logic: ∃r.C, it is the possibility of the agent r
+                id: TokenTypeID::RoleExists,
+                regex: Regex::new(r"^∃[A-Za-z0-9_]+\.$").context(codeloc!())?,
+                category: TokenCategory::UnaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |name, args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    let role = Self::parse_role(&name);
+                    return Ok(Formula::AgentPossible(role, bx!(args[0].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches the universal restriction of description logic: ∀r.C, it is the necessity of the agent r
+                id: TokenTypeID::RoleForAll,
+                regex: Regex::new(r"^∀[A-Za-z0-9_]+\.$").context(codeloc!())?,
+                category: TokenCategory::UnaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |name, args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    let role = Self::parse_role(&name);
+                    return Ok(Formula::AgentNecessary(role, bx!(args[0].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches the intersection of concepts: C ⊓ D, it is the conjunction
+                id: TokenTypeID::ConceptIntersection,
+                regex: Regex::new(r"(⊓)").context(codeloc!())?,
+                category: TokenCategory::BinaryOperation,
+                precedence: OperatorPrecedence::High,
+                to_formula: |_,args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    return Ok(Formula::And(bx!(args[0].clone()), bx!(args[1].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches the union of concepts: C ⊔ D, it is the disjunction
+                id: TokenTypeID::ConceptUnion,
+                regex: Regex::new(r"(⊔)").context(codeloc!())?,
+                category: TokenCategory::BinaryOperation,
+                precedence: OperatorPrecedence::High,
+                to_formula: |_,args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    return Ok(Formula::Or(bx!(args[0].clone()), bx!(args[1].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches subsumption: C ⊑ D, every instance of C is an instance of D, it is the implication
+                id: TokenTypeID::Subsumption,
+                regex: Regex::new(r"(⊑)").context(codeloc!())?,
+                category: TokenCategory::BinaryOperation,
+                precedence: OperatorPrecedence::Medium,
+                to_formula: |_,args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    return Ok(Formula::Imply(bx!(args[0].clone()), bx!(args[1].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches next: X P, X Q, ...
+                id: TokenTypeID::Next,
+                regex: Regex::new(r"^X$").context(codeloc!())?,
+                category: TokenCategory::UnaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |_,args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    return Ok(Formula::Next(bx!(args[0].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches until: P U Q, binds stronger than the other binary operators
+                id: TokenTypeID::Until,
+                regex: Regex::new(r"^U$").context(codeloc!())?,
+                category: TokenCategory::BinaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |_,args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    return Ok(Formula::Until(bx!(args[0].clone()), bx!(args[1].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches since: P S Q, binds stronger than the other binary operators
+                id: TokenTypeID::Since,
+                regex: Regex::new(r"^S$").context(codeloc!())?,
+                category: TokenCategory::BinaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |_,args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    return Ok(Formula::Since(bx!(args[0].clone()), bx!(args[1].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches nominals, the names of worlds: i, j, k, i1, ...
+                id: TokenTypeID::Nominal,
+                regex: Regex::new(r"^[ijk][0-9]*$").context(codeloc!())?,
+                category: TokenCategory::Atomic,
+                precedence: OperatorPrecedence::Lowest,
+                to_formula: |name,_|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    return Ok(Formula::Nominal(name.to_smolstr(), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches satisfaction at a named world: @_i P, @_j Q, ...
+                id: TokenTypeID::At,
+                regex: Regex::new(r"^@_[A-Za-z0-9]+$").context(codeloc!())?,
+                category: TokenCategory::UnaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |name, args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    let nominal = Self::parse_agent(&name);
+                    return Ok(Formula::At(nominal, bx!(args[0].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches the binder, that names the current world: ↓_i P, ...
+                id: TokenTypeID::Bind,
+                regex: Regex::new(r"^↓_[A-Za-z0-9]+$").context(codeloc!())?,
+                category: TokenCategory::UnaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |name, args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    let nominal = Self::parse_agent(&name);
+                    return Ok(Formula::Bind(nominal, bx!(args[0].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches necessity after running a program: [a]P, [a;b]P, [a∪b]P, [P?]Q, [a*]P, ...
+                id: TokenTypeID::ProgramNecessary,
+                regex: Regex::new(r"^\[.+\]$").context(codeloc!())?,
+                category: TokenCategory::UnaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |name, args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    let program = ProgramParser::parse(&name).context(codeloc!())?;
+                    return Ok(Formula::ProgramNecessary(program, bx!(args[0].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches possibility after running a program: ⟨a⟩P, ⟨a;b⟩P, ...
+                id: TokenTypeID::ProgramPossible,
+                regex: Regex::new(r"^⟨.+⟩$").context(codeloc!())?,
+                category: TokenCategory::UnaryOperation,
+                precedence: OperatorPrecedence::Higher,
+                to_formula: |name, args|
+                {
+                    let formula_extras = FormulaExtras::empty();
+                    let program = ProgramParser::parse(&name).context(codeloc!())?;
+                    return Ok(Formula::ProgramPossible(program, bx!(args[0].clone()), formula_extras));
+                }
+            },
+
+            TokenType
+            {
+                //matches atomic formulas with args: P[x,y], P[f(x),a], ...
                 id: TokenTypeID::AtomicWithArgs,
-                regex: Regex::new(r"[A-Za-z_]+\[[A-Za-zαβγ0-9_,:]+\]").context(codeloc!())?,
+                regex: Regex::new(r"[A-Za-z_]+\[[A-Za-zαβγ0-9_,:()]+\]").context(codeloc!())?,
                 category: TokenCategory::Atomic,
                 precedence: OperatorPrecedence::Lowest,
                 to_formula: |name, _args|
@@ -302,6 +605,23 @@ impl TokenType
         ]);
     }
 
+    fn parse_agent(input : &String) -> SmolStr
+    {
+        return input.split('_').nth(1).unwrap_or_default().to_smolstr();
+    }
+
+    fn parse_role(input : &String) -> SmolStr
+    {
+        return input.trim_start_matches(['∃', '∀']).trim_end_matches('.').to_smolstr();
+    }
+
+    fn parse_group_of_agents(input : &String) -> Vec<SmolStr>
+    {
+        let agents = input.substring(input.find('{').unwrap_or_default()+1, input.len()-1);
+        return agents.split(',').map(|agent| agent.to_smolstr())
+            .collect::<BTreeSet<SmolStr>>().into_iter().collect();
+    }
+
     fn parse_predicate_arguments(input : &String) -> PredicateArguments
     {
         if let Some(index_of_open_bracket) = input.find('[')
@@ -328,8 +648,132 @@ impl TokenType
             return Self::parse_predicate_arguments(&new_input);
         }
 
-        return PredicateArguments::new(input.split(",")
-            .map(|token| PredicateArgument::new(token.trim().to_smolstr()))
+        return PredicateArguments::new(PredicateArgument::split_names(input)
+            .into_iter().map(PredicateArgument::new)
             .collect());
     }
 }
+
+//the grammar of programs, from the lowest to the highest precedence:
+//choice := sequence (∪ sequence)*, sequence := iteration (; iteration)*, iteration := primary *...,
+//primary := (choice) | atomic program | formula?
+struct ProgramParser
+{
+    characters : Vec<char>,
+    current_index : usize,
+}
+
+impl ProgramParser
+{
+    fn parse(name : &String) -> Result<Program>
+    {
+        let characters = name.chars().collect::<Vec<char>>();
+        let mut parser = ProgramParser { characters: characters[1..characters.len()-1].to_vec(), current_index:0 };
+
+        let program = parser.next_choice().context(codeloc!())?;
+        if parser.current_index < parser.characters.len()
+        {
+            return Err(anyhow!("Invalid syntax: unexpected {} in program {}", parser.characters[parser.current_index], name));
+        }
+
+        return Ok(program);
+    }
+
+    fn next_choice(&mut self) -> Result<Program>
+    {
+        let mut program = self.next_sequence().context(codeloc!())?;
+        while self.current_character() == Some('∪')
+        {
+            self.current_index += 1;
+            program = Program::Choice(bx!(program), bx!(self.next_sequence().context(codeloc!())?));
+        }
+
+        return Ok(program);
+    }
+
+    fn next_sequence(&mut self) -> Result<Program>
+    {
+        let mut program = self.next_iteration().context(codeloc!())?;
+        while self.current_character() == Some(';')
+        {
+            self.current_index += 1;
+            program = Program::Sequence(bx!(program), bx!(self.next_iteration().context(codeloc!())?));
+        }
+
+        return Ok(program);
+    }
+
+    fn next_iteration(&mut self) -> Result<Program>
+    {
+        let mut program = self.next_primary().context(codeloc!())?;
+        while self.current_character() == Some('*')
+        {
+            self.current_index += 1;
+            program = Program::Iteration(bx!(program));
+        }
+
+        return Ok(program);
+    }
+
+    fn next_primary(&mut self) -> Result<Program>
+    {
+        //tests end with ?, eg: p?, (p∧q)?, [a]p?
+        let end_index = self.find_end_of_primary();
+        let text = self.characters[self.current_index..end_index].iter().collect::<String>();
+        if text.is_empty()
+        {
+            return Err(anyhow!("Invalid syntax: missing program in {}", self.characters.iter().collect::<String>()));
+        }
+
+        if self.characters.get(end_index) == Some(&'?')
+        {
+            self.current_index = end_index+1;
+            let logic : Rc<dyn Logic> = Rc::new(PropositionalDynamicLogic {});
+            let formula = LogicalExpressionParser::parse(&logic, &text).context(codeloc!())?;
+            return Ok(Program::Test(bx!(formula)));
+        }
+
+        if self.current_character() == Some('(')
+        {
+            self.current_index += 1;
+            let program = self.next_choice().context(codeloc!())?;
+            if self.current_character() != Some(')')
+            {
+                return Err(anyhow!("Invalid syntax: missing ) in program {}", self.characters.iter().collect::<String>()));
+            }
+
+            self.current_index += 1;
+            return Ok(program);
+        }
+
+        if !text.chars().all(|character| character.is_alphanumeric() || character == '_')
+        {
+            return Err(anyhow!("Invalid syntax: {} is not an atomic program", text));
+        }
+
+        self.current_index = end_index;
+        return Ok(Program::AtomicProgram(text.to_smolstr()));
+    }
+
+    fn find_end_of_primary(&self) -> usize
+    {
+        let mut depth = 0;
+        for index in self.current_index..self.characters.len()
+        {
+            match self.characters[index]
+            {
+                '(' | '[' | '⟨' => { depth += 1 }
+                ')' | ']' | '⟩' if depth > 0 => { depth -= 1 }
+                ')' | ';' | '∪' | '*' | '?' if depth == 0 => { return index }
+                _ => {}
+            }
+        }
+
+        return self.characters.len();
+    }
+
+    fn current_character(&self) -> Option<char>
+    {
+        return self.characters.get(self.current_index).cloned();
+    }
+}
@@ -3,8 +3,10 @@ pub mod json;
 
 use std::collections::BTreeSet;
 use std::rc::Rc;
+use serde::{Deserialize, Serialize};
 use crate::formula::{Formula, PredicateArgument};
 use crate::logic::Logic;
+use crate::proof::cancellation::ProofCancellationToken;
 use crate::proof::ProofAlgorithm;
 use crate::tree::ProofTree;
 
@@ -23,6 +25,7 @@ pub struct ProblemFlags
 {
     pub should_skip_contradiction_check : bool,
     pub non_rigid_designators : BTreeSet<PredicateArgument>,
+    pub limits : ProofLimits,
 }
 
 impl Default for ProblemFlags
@@ -33,15 +36,42 @@ impl Default for ProblemFlags
         {
             should_skip_contradiction_check: false,
             non_rigid_designators: BTreeSet::new(),
+            limits: ProofLimits::default(),
         };
     }
 }
 
+//the proof stops with a timeout once a limit is reached, a missing limit falls back to the default limit of the logic
+#[derive(Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProofLimits
+{
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_number_of_possible_worlds : Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_number_of_tree_nodes : Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_number_of_rule_applications : Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tree_depth : Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_term_depth : Option<usize>,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ProofLimit
+{
+    MaxNumberOfPossibleWorlds,
+    MaxNumberOfTreeNodes,
+    MaxNumberOfRuleApplications,
+    MaxTreeDepth,
+    MaxTermDepth,
+}
+
 impl Problem
 {
-    pub fn prove(self) -> ProofTree
+    pub fn prove(self, cancellation_token : ProofCancellationToken) -> ProofTree
     {
-        let algorithm = ProofAlgorithm::initialize(self);
+        let algorithm = ProofAlgorithm::initialize(self, cancellation_token);
         let proof_tree = algorithm.prove();
         return proof_tree;
     }
@@ -61,9 +61,14 @@ impl ProblemJSON
 {
     fn with_operator_notations(self, operator_notations : OperatorNotations) -> ProblemJSON
     {
+        let problem = self.to_problem().unwrap();
+
         let mut formula_format_options = FormulaFormatOptions::default();
         formula_format_options.notations = operator_notations;
+        let recommended_formula_format_options = FormulaFormatOptions::recommended_for(&problem.logic);
+        formula_format_options.should_show_deontic_operators = recommended_formula_format_options.should_show_deontic_operators;
+        formula_format_options.should_show_description_logic_operators = recommended_formula_format_options.should_show_description_logic_operators;
 
-        return self.to_problem().unwrap().to_json(&formula_format_options);
+        return problem.to_json(&formula_format_options);
     }
 }
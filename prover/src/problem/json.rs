@@ -5,7 +5,7 @@ use crate::formula::Formula;
 use crate::formula::to_string::FormulaFormatOptions;
 use crate::logic::LogicFactory;
 use crate::parser::algorithm::LogicalExpressionParser;
-use crate::problem::{Problem, ProblemFlags};
+use crate::problem::{Problem, ProblemFlags, ProofLimits};
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct BookChapterJSON
@@ -22,6 +22,8 @@ pub struct ProblemJSON
     pub expected : String,
     pub premises : Vec<String>,
     pub conclusion : String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits : Option<ProofLimits>,
 }
 
 impl ProblemJSON
@@ -47,6 +49,7 @@ impl Problem
             expected: String::new(),
             premises: premises_as_strings,
             conclusion: self.conclusion.to_string_with_options(options),
+            limits: if self.flags.limits == ProofLimits::default() { None } else { Some(self.flags.limits) },
         };
     }
 
@@ -63,6 +66,9 @@ impl Problem
             premises.push(premise);
         }
 
-        return Ok(Problem { id:json.id.clone(), logic:logic, premises:premises, conclusion:conclusion, flags:ProblemFlags::default() });
+        let mut flags = ProblemFlags::default();
+        flags.limits = json.limits.unwrap_or_default();
+
+        return Ok(Problem { id:json.id.clone(), logic:logic, premises:premises, conclusion:conclusion, flags:flags });
     }
 }
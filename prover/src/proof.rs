@@ -1,22 +1,31 @@
+use std::collections::BTreeSet;
 use crate::formula::to_string::FormulaFormatOptions;
 use crate::graph::Graph;
 use crate::logic::{LogicName, LogicRuleCollection, LogicRuleResult};
 use crate::logic::rule_apply_factory::RuleApplyFactory;
-use crate::problem::ProblemFlags;
+use crate::problem::{ProblemFlags, ProofLimit};
+use crate::proof::cancellation::ProofCancellationToken;
 use crate::proof::decomposition_queue::DecompositionPriorityQueue;
 use crate::proof::execution_log::{ExecutionLog, ExecutionLogHelperData};
 use crate::tree::node::ProofTreeNode;
-use crate::tree::node_factory::ProofTreeNodeFactory;
+use crate::tree::node_factory::{ProofTreeNodeFactory, ProofTreeNodeID};
 use crate::tree::ProofTree;
 use crate::utils::measure_total_number_of_allocated_bytes;
 
+pub mod cancellation;
 pub mod decomposition_queue;
 pub mod execution_log;
 mod initialize;
 
+//possible worlds are indexed by an u8, thus no limit can go past it
+const MAX_NUMBER_OF_POSSIBLE_WORLDS : usize = u8::MAX as usize;
+
+//default limits, overridden by the limits of the problem
 const MAX_NUMBER_OF_POSSIBLE_WORLDS_ON_MODAL_LOGIC : usize = 25;
 const MAX_NUMBER_OF_TREE_NODES_ON_FIRST_ORDER_LOGIC : usize = 250;
 const MAX_NUMBER_OF_TREE_NODES_ON_INTUITIONISTIC_LOGIC : usize = 1000;
+const MAX_NUMBER_OF_TREE_NODES_ON_LINEAR_TEMPORAL_LOGIC : usize = 1000;
+const MAX_NUMBER_OF_TREE_NODES_ON_TRUTH_MATRIX_LOGIC : usize = 1000;
 
 pub struct ProofAlgorithm
 {
@@ -27,6 +36,10 @@ pub struct ProofAlgorithm
     node_factory : ProofTreeNodeFactory,
     modality_graph : Graph,
     problem_flags : ProblemFlags,
+    number_of_rule_applications : usize,
+    number_of_tree_nodes_on_last_recheck : usize,
+    limit_reached_by_rules : Option<ProofLimit>,
+    cancellation_token : ProofCancellationToken,
 }
 
 impl ProofAlgorithm
@@ -41,11 +54,18 @@ impl ProofAlgorithm
 
         let formula_format_options = FormulaFormatOptions::recommended_for(&self.proof_tree.problem.logic);
 
-        while !self.decomposition_queue.is_empty() && !self.proof_tree.is_proof_correct && !self.reached_timeout()
+        while !self.proof_tree.is_proof_correct && !self.reached_timeout() && self.has_nodes_to_decompose()
         {
+            if let Some(cancellation) = self.cancellation_token.get_cancellation()
+            {
+                self.proof_tree.cancellation = Some(cancellation);
+                break;
+            }
+
             let ram_consumption = measure_total_number_of_allocated_bytes(||
             {
                 let (box node, mut result) = self.consume_next_queue_node().unwrap();
+                if !result.is_empty() { self.number_of_rule_applications += 1; }
 
                 ExecutionLog::log(format!("Apply: <{}> {}\nResult: {}", node.id,
                     node.formula.to_string_with_options(&formula_format_options),
@@ -68,12 +88,51 @@ impl ProofAlgorithm
             ExecutionLog::log(format!("{}B ({:.4}MB)", ram_consumption, ram_consumption/1024.0/1024.0));
         }
 
-        self.proof_tree.has_timeout = self.reached_timeout();
+        self.proof_tree.reached_limit = self.get_reached_limit();
+        if self.proof_tree.reached_limit.is_none() && !self.proof_tree.is_proof_correct && self.proof_tree.cancellation.is_none()
+        {
+            //the tree was not closed, but some rules skipped work, thus it is not a countermodel either
+            self.proof_tree.reached_limit = self.limit_reached_by_rules;
+        }
+
+        self.proof_tree.has_timeout = self.proof_tree.reached_limit.is_some();
         self.proof_tree.modality_graph = self.modality_graph;
+        self.proof_tree.cancellation_token = self.cancellation_token;
 
         return self.proof_tree;
     }
 
+    fn has_nodes_to_decompose(&mut self) -> bool
+    {
+        if self.decomposition_queue.is_empty()
+        {
+            self.push_blocked_possibilities_again();
+        }
+
+        return !self.decomposition_queue.is_empty();
+    }
+
+    //blocked worlds might have received new formulas after being blocked, thus blocked possibilities
+    //are checked again once everything else was decomposed, until the tree stops growing
+    fn push_blocked_possibilities_again(&mut self)
+    {
+        let number_of_tree_nodes = self.proof_tree.get_total_number_of_nodes();
+        if number_of_tree_nodes == self.number_of_tree_nodes_on_last_recheck { return }
+        self.number_of_tree_nodes_on_last_recheck = number_of_tree_nodes;
+
+        let blocked_node_ids = self.modality_graph.take_blocked_possibilities().into_iter()
+            .map(|blocked_possibility| blocked_possibility.node_id)
+            .collect::<BTreeSet<ProofTreeNodeID>>();
+
+        for blocked_node_id in blocked_node_ids
+        {
+            if let Some(blocked_node) = self.proof_tree.get_node_with_id(blocked_node_id)
+            {
+                self.decomposition_queue.push_consumed_node_again(blocked_node.to_tree_node());
+            }
+        }
+    }
+
     fn consume_next_queue_node(&mut self) -> Option<(Box<ProofTreeNode>, LogicRuleResult)>
     {
         let mut factory = RuleApplyFactory
@@ -82,6 +141,7 @@ impl ProofAlgorithm
             tree_node_factory: &mut self.node_factory,
             modality_graph: &mut self.modality_graph,
             problem_flags: &self.problem_flags,
+            limit_reached_by_rules: &mut self.limit_reached_by_rules,
         };
 
         if let Some(node) = self.decomposition_queue.pop()
@@ -102,18 +162,50 @@ impl ProofAlgorithm
 
     fn reached_timeout(&self) -> bool
     {
-        let proof_tree_is_too_large =
-            if self.logic_name.is_intuitionistic_logic()
-                { self.proof_tree.get_total_number_of_nodes() >= MAX_NUMBER_OF_TREE_NODES_ON_INTUITIONISTIC_LOGIC }
-            else if self.logic_name.is_first_order_logic()
-                { self.proof_tree.get_total_number_of_nodes() >= MAX_NUMBER_OF_TREE_NODES_ON_FIRST_ORDER_LOGIC }
-            else { false };
-
-        let modality_graph_is_too_large =
-            if self.logic_name.is_modal_logic()
-                { self.modality_graph.nodes().len() >= MAX_NUMBER_OF_POSSIBLE_WORLDS_ON_MODAL_LOGIC }
-            else { false };
-
-        return proof_tree_is_too_large || modality_graph_is_too_large;
+        return self.get_reached_limit().is_some();
+    }
+
+    fn get_reached_limit(&self) -> Option<ProofLimit>
+    {
+        let limits = &self.problem_flags.limits;
+
+        let default_max_number_of_tree_nodes =
+            if self.logic_name.is_intuitionistic_logic() { Some(MAX_NUMBER_OF_TREE_NODES_ON_INTUITIONISTIC_LOGIC) }
+            else if self.logic_name.is_first_order_logic() { Some(MAX_NUMBER_OF_TREE_NODES_ON_FIRST_ORDER_LOGIC) }
+            else if self.logic_name.is_linear_temporal_logic() { Some(MAX_NUMBER_OF_TREE_NODES_ON_LINEAR_TEMPORAL_LOGIC) }
+            else if self.logic_name.is_truth_matrix_logic() { Some(MAX_NUMBER_OF_TREE_NODES_ON_TRUTH_MATRIX_LOGIC) }
+            else { None };
+
+        if let Some(max_number_of_tree_nodes) = limits.max_number_of_tree_nodes.or(default_max_number_of_tree_nodes) &&
+            self.proof_tree.get_total_number_of_nodes() >= max_number_of_tree_nodes
+        {
+            return Some(ProofLimit::MaxNumberOfTreeNodes);
+        }
+
+        let default_max_number_of_possible_worlds =
+            if self.logic_name.is_modal_logic() { Some(MAX_NUMBER_OF_POSSIBLE_WORLDS_ON_MODAL_LOGIC) }
+            else { None };
+
+        let max_number_of_possible_worlds = limits.max_number_of_possible_worlds.or(default_max_number_of_possible_worlds)
+            .map_or(MAX_NUMBER_OF_POSSIBLE_WORLDS, |max_number_of_possible_worlds| max_number_of_possible_worlds.min(MAX_NUMBER_OF_POSSIBLE_WORLDS));
+
+        if self.modality_graph.nodes().len() >= max_number_of_possible_worlds
+        {
+            return Some(ProofLimit::MaxNumberOfPossibleWorlds);
+        }
+
+        if let Some(max_number_of_rule_applications) = limits.max_number_of_rule_applications &&
+            self.number_of_rule_applications >= max_number_of_rule_applications
+        {
+            return Some(ProofLimit::MaxNumberOfRuleApplications);
+        }
+
+        if let Some(max_tree_depth) = limits.max_tree_depth &&
+            self.proof_tree.get_depth() >= max_tree_depth
+        {
+            return Some(ProofLimit::MaxTreeDepth);
+        }
+
+        return None;
     }
 }
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::utils::{get_config_value, get_current_time_in_millis, CONFIG_KEY_PROOF_DEADLINE_IN_SECONDS};
+
+//a handle shared between the caller and the proof, the proof stops once the handle is cancelled or its deadline passes
+#[derive(Clone, Default)]
+pub struct ProofCancellationToken
+{
+    is_cancelled : Arc<AtomicBool>,
+    deadline_in_millis : Option<f64>,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ProofCancellation
+{
+    Cancelled,
+    DeadlineExceeded,
+}
+
+impl ProofCancellationToken
+{
+    pub fn new() -> ProofCancellationToken
+    {
+        return ProofCancellationToken::default();
+    }
+
+    pub fn with_deadline_after(duration : Duration) -> ProofCancellationToken
+    {
+        return ProofCancellationToken
+        {
+            is_cancelled: Arc::new(AtomicBool::new(false)),
+            deadline_in_millis: Some(get_current_time_in_millis() + duration.as_millis() as f64),
+        };
+    }
+
+    //eg: proof_deadline_in_seconds:10 as a command line argument or ?proof_deadline_in_seconds=10 as an URL argument
+    pub fn from_config(default_deadline : Option<Duration>) -> ProofCancellationToken
+    {
+        let deadline = get_config_value::<u64>(CONFIG_KEY_PROOF_DEADLINE_IN_SECONDS)
+            .map(Duration::from_secs).or(default_deadline);
+
+        return match deadline
+        {
+            Some(deadline) => ProofCancellationToken::with_deadline_after(deadline),
+            None => ProofCancellationToken::new(),
+        };
+    }
+
+    pub fn cancel(&self)
+    {
+        self.is_cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn get_cancellation(&self) -> Option<ProofCancellation>
+    {
+        if self.is_cancelled.load(Ordering::Relaxed)
+        {
+            return Some(ProofCancellation::Cancelled);
+        }
+
+        if let Some(deadline_in_millis) = self.deadline_in_millis && get_current_time_in_millis() >= deadline_in_millis
+        {
+            return Some(ProofCancellation::DeadlineExceeded);
+        }
+
+        return None;
+    }
+
+    pub fn is_cancelled(&self) -> bool
+    {
+        return self.get_cancellation().is_some();
+    }
+}
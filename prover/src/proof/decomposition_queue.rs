@@ -1,7 +1,8 @@
 use std::rc::Rc;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
-use crate::formula::Formula::{And, Atomic, BiImply, Conditional, Equals, Exists, ForAll, Imply, Non, Or, Possible, StrictImply};
+use crate::formula::Formula::{And, Atomic, BiImply, Comment, CommonKnowledge, Conditional, Equals, Exists, ForAll, Imply, Next, Nominal, Non, Or, Possible, ProgramPossible, StrictImply};
+use crate::formula::Program;
 use crate::formula::Sign::{Minus, Plus};
 use crate::logic::Logic;
 use crate::tree::node::ProofTreeNode;
@@ -50,11 +51,12 @@ impl DecompositionPriorityQueue
         return self.consumable_nodes.is_empty() && self.reusable_nodes.is_empty();
     }
 
-    pub fn push_tree_node(&mut self, node : Box<ProofTreeNode>)
+    //nodes are queued without their children, which are queued on their own
+    pub fn push_tree_node(&mut self, mut node : Box<ProofTreeNode>)
     {
-        if let Some(left) = &node.left { self.push_tree_node(left.clone()); }
-        if let Some(middle) = &node.middle { self.push_tree_node(middle.clone()); }
-        if let Some(right) = &node.right { self.push_tree_node(right.clone()); }
+        if let Some(left) = node.left.take() { self.push_tree_node(left); }
+        if let Some(middle) = node.middle.take() { self.push_tree_node(middle); }
+        if let Some(right) = node.right.take() { self.push_tree_node(right); }
 
         if !self.previously_queued_node_ids.contains(&node.id)
         {
@@ -63,6 +65,12 @@ impl DecompositionPriorityQueue
         }
     }
 
+    //a node that was already consumed is decomposed again, eg: a blocked possibility, whose world might have been unblocked
+    pub fn push_consumed_node_again(&mut self, node : ProofTreeNode)
+    {
+        self.consumable_nodes.push(Box::new(node));
+    }
+
     pub fn pop(&mut self) -> Option<Box<ProofTreeNode>>
     {
         if self.consumable_nodes.is_empty() && !self.reusable_nodes.is_empty()
@@ -103,12 +111,15 @@ impl DecompositionPriorityQueue
 
     fn get_node_priority(&self, node : &Box<ProofTreeNode>) -> Priority
     {
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             //atomics needs to be applied last after all
             Atomic(..) => Priority::UnimportantMinus5,
             Non(box Atomic(..), ..) => Priority::UnimportantMinus5,
 
+            //frame rules written on comments may split the tree, so they are applied last
+            Comment(..) => Priority::UnimportantMinus5,
+
             //forall needs to be applied after all instantiations
             ForAll(..) => Priority::UnimportantMinus4,
 
@@ -119,10 +130,20 @@ impl DecompositionPriorityQueue
             Non(box StrictImply(..), ..) | Possible(..)
             if self.logic.get_name().is_non_normal_modal_logic() => Priority::UnimportantMinus2,
 
+            //the eventualities of common knowledge and of iteration are checked for loops, after the world is fully decomposed
+            Non(box CommonKnowledge(..), ..) => Priority::UnimportantMinus2,
+            ProgramPossible(Program::Iteration(..), ..) => Priority::UnimportantMinus2,
+
+            //next moves to the next instant, after the current instant is fully decomposed
+            Next(..) => Priority::UnimportantMinus2,
+
             //tree-splitting operations needs to be applied after non-tree-splitting operations
             BiImply(..) | Non(box BiImply(..), ..) => Priority::UnimportantMinus1,
             Or(..) | Non(box And(..), ..) | Imply(..) => Priority::UnimportantMinus1,
 
+            //worlds are named before satisfaction operators look them up
+            Nominal(..) => Priority::Important,
+
             //equals and non-equals needs to be applied before all else
             Non(box Equals(..), ..) => Priority::Important,
             Equals(..) => Priority::MostImportant,
@@ -135,7 +156,7 @@ impl DecompositionPriorityQueue
     {
         if self.banned_reusable_nodes.contains(node) { return false };
 
-        return match &node.formula
+        return match node.formula.as_ref()
         {
             ForAll(_x, _p, extras) if extras.sign == Plus => true,
             Exists(_x, _p, extras) if extras.sign == Minus => true,
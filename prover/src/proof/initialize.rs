@@ -1,5 +1,6 @@
 use crate::graph::Graph;
 use crate::problem::Problem;
+use crate::proof::cancellation::ProofCancellationToken;
 use crate::proof::decomposition_queue::DecompositionPriorityQueue;
 use crate::proof::ProofAlgorithm;
 use crate::tree::node::ProofTreeNode;
@@ -9,7 +10,7 @@ use crate::tree::subtree::ProofSubtree;
 
 impl ProofAlgorithm
 {
-    pub fn initialize(problem : Problem) -> ProofAlgorithm
+    pub fn initialize(problem : Problem, cancellation_token : ProofCancellationToken) -> ProofAlgorithm
     {
         let logic = problem.logic.clone();
         let mut problem_flags = problem.flags.clone();
@@ -32,7 +33,9 @@ impl ProofAlgorithm
                 proof_tree: proof_tree, decomposition_queue: decomposition_queue,
                 logic_name: logic.get_name(), logic_rules: logic.get_rules(),
                 node_factory: node_factory, modality_graph: Graph::new(),
-                problem_flags: problem_flags,
+                problem_flags: problem_flags, number_of_rule_applications: 0,
+                number_of_tree_nodes_on_last_recheck: 0, limit_reached_by_rules: None,
+                cancellation_token: cancellation_token,
             };
         }
 
@@ -60,7 +63,9 @@ impl ProofAlgorithm
             proof_tree: proof_tree, decomposition_queue: decomposition_queue,
             logic_name: logic.get_name(), logic_rules: logic.get_rules(),
             node_factory: node_factory, modality_graph: Graph::new(),
-            problem_flags: problem_flags,
+            problem_flags: problem_flags, number_of_rule_applications: 0,
+            number_of_tree_nodes_on_last_recheck: 0, limit_reached_by_rules: None,
+            cancellation_token: cancellation_token,
         };
     }
 }
@@ -1,15 +1,28 @@
 pub mod binary_logic_semantics;
 pub mod many_valued_logic_semantics;
 pub mod fuzzy_logic_semantics;
+pub mod truth_matrix_logic_semantics;
 
 use crate::formula::Formula;
+use crate::tree::node_factory::ProofTreeNodeID;
 use crate::tree::path::ProofTreePath;
 
 pub trait Semantics
 {
     fn number_of_truth_values(&self) -> u8;
 
+    //logics evaluating negation on a star world are classical inside each world
+    fn number_of_truth_values_on_each_world(&self) -> u8 { self.number_of_truth_values() }
+
     fn reductio_ad_absurdum(&self, formula : &Formula) -> Formula;
 
     fn are_formulas_contradictory(&self, path : &ProofTreePath, p : &Formula, q : &Formula) -> bool;
+
+    //a pair of formulas is contradictory regardless of the other formulas on the path,
+    //thus a path extended with new formulas needs checking only the pairs involving them
+    fn are_contradictions_between_pairs_of_formulas(&self) -> bool { true }
+
+    //semantics that don't close a path by pairs of formulas check the whole path at once,
+    //returning the contradictory node and the node it contradicts
+    fn get_path_contradictory_node_ids(&self, _path : &ProofTreePath) -> Option<(ProofTreeNodeID, ProofTreeNodeID)> { None }
 }
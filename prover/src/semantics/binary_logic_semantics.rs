@@ -1,6 +1,6 @@
 use box_macro::bx;
 use crate::formula::{Formula, FormulaExtras};
-use crate::formula::Formula::{Atomic, Non, BiImply, Equals, DefinitelyExists};
+use crate::formula::Formula::{Atomic, Non, BiImply, Equals, DefinitelyExists, Nominal};
 use crate::semantics::Semantics;
 use crate::tree::path::ProofTreePath;
 
@@ -49,6 +49,13 @@ impl Semantics for BinaryLogicSemantics
                 p.get_possible_world() == q.get_possible_world()
             }
 
+            (Nominal(i, _), Non(box Nominal(j, _), _)) |
+            (Non(box Nominal(i, _), _), Nominal(j, _))
+            =>
+            {
+                i == j && p.get_possible_world() == q.get_possible_world()
+            }
+
             (DefinitelyExists(x, _), Non(box DefinitelyExists(y, _), _)) |
             (Non(box DefinitelyExists(x, _), _), DefinitelyExists(y, _))
             =>
@@ -60,3 +67,34 @@ impl Semantics for BinaryLogicSemantics
         }
     }
 }
+
+//the rules of these logics close a branch where an eventuality loops forever with its negation, eg: C_G P on ¬C_G P,
+//¬⟨α*⟩P on ⟨α*⟩P or ¬(P U Q) on P U Q, thus these formulas contradict their negation only on these logics
+pub struct EventualityLogicSemantics
+{
+    pub is_eventuality_closed_by_negation : fn(&Formula) -> bool,
+}
+
+impl Semantics for EventualityLogicSemantics
+{
+    fn number_of_truth_values(&self) -> u8 { 2 }
+
+    fn reductio_ad_absurdum(&self, formula : &Formula) -> Formula
+    {
+        return BinaryLogicSemantics {}.reductio_ad_absurdum(formula);
+    }
+
+    fn are_formulas_contradictory(&self, path : &ProofTreePath, p : &Formula, q : &Formula) -> bool
+    {
+        return match (p, q)
+        {
+            (r1, Non(box r2, _)) | (Non(box r2, _), r1) if (self.is_eventuality_closed_by_negation)(r1) =>
+            {
+                r1.with_stripped_extras() == r2.with_stripped_extras() &&
+                p.get_possible_world() == q.get_possible_world()
+            }
+
+            _ => BinaryLogicSemantics {}.are_formulas_contradictory(path, p, q)
+        }
+    }
+}
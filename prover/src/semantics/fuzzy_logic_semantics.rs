@@ -1,14 +1,14 @@
-use crate::logic::first_order_logic::FirstOrderLogicDomainType::VariableDomain;
-use crate::formula::Formula::{GreaterOrEqualThan, LessThan};
+use crate::formula::Formula::{DefinitelyExists, Equals, GreaterOrEqualThan, LessThan};
 use crate::formula::Sign::{Minus, Plus};
 use crate::formula::{Formula, FuzzyTag, FuzzyTags};
 use crate::semantics::Semantics;
 use minilp::{ComparisonOp, OptimizationDirection, Problem as LinearProgram, Variable};
 use std::collections::BTreeMap;
 use itertools::Itertools;
+use crate::tree::node_factory::ProofTreeNodeID;
 use crate::tree::path::ProofTreePath;
 
-const EPSILON : f64 = 0.001;
+const EPSILON : f64 = 0.000001;
 
 pub struct FuzzyLogicSemantics {}
 
@@ -23,92 +23,98 @@ impl Semantics for FuzzyLogicSemantics
         return formula.with_sign(Minus).with_fuzzy_tags(initial_tags);
     }
 
-    fn are_formulas_contradictory(&self, path : &ProofTreePath, p : &Formula, _ : &Formula) -> bool
+    //inequalities are solved together, as a linear program built from the whole path
+    fn are_contradictions_between_pairs_of_formulas(&self) -> bool { false }
+
+    fn are_formulas_contradictory(&self, _ : &ProofTreePath, p : &Formula, q : &Formula) -> bool
+    {
+        //existence and identity are crisp, their fuzzy tags don't matter
+        if matches!(p, DefinitelyExists(..) | Equals(..)) && p.get_sign() != q.get_sign()
+        {
+            let (crisp_p, crisp_q) = (p.with_sign(Plus).with_fuzzy_tags(FuzzyTags::empty()), q.with_sign(Plus).with_fuzzy_tags(FuzzyTags::empty()));
+            return crisp_p == crisp_q;
+        }
+
+        return false;
+    }
+
+    fn get_path_contradictory_node_ids(&self, path : &ProofTreePath) -> Option<(ProofTreeNodeID, ProofTreeNodeID)>
     {
-        //don't check for contradictions on formulas other than < and >=
-        if !matches!(p, LessThan(..) | GreaterOrEqualThan(..)) { return false };
+        //don't check for contradictions on paths without < and >=
+        let last_inequality_node = path.nodes.iter().rev()
+            .find(|node| matches!(node.formula.as_ref(), LessThan(..) | GreaterOrEqualThan(..)))?;
 
+        if self.has_solution(path) { return None };
+
+        return Some((last_inequality_node.id, path.nodes[0].id));
+    }
+}
+
+impl FuzzyLogicSemantics
+{
+    //x < y is written as x - y + t <= 0, maximizing t: the inequalities have a solution iff max(t) > 0
+    fn has_solution(&self, path : &ProofTreePath) -> bool
+    {
         let mut linear_program = LinearProgram::new(OptimizationDirection::Maximize);
 
         let variables = path.nodes.iter()
-            .flat_map(|node| node.formula.get_fuzzy_tags().into_iter())
+            .flat_map(|node| match node.formula.as_ref()
+            {
+                LessThan(left, right, _) | GreaterOrEqualThan(left, right, _) => left.iter().chain(right.iter()).cloned().collect(),
+                _ => vec![]
+            })
             .unique().map(|fuzzy_tag| (fuzzy_tag.get_variable_range(), fuzzy_tag.abs()))
-            .map(|(range, fuzzy_tag)| (fuzzy_tag, linear_program.add_var(1.0, range)))
+            .map(|(range, fuzzy_tag)| (fuzzy_tag, linear_program.add_var(0.0, range)))
             .collect::<BTreeMap<FuzzyTag, Variable>>();
-        if variables.is_empty() { return false };
+        let slack_variable = linear_program.add_var(1.0, (0.0, 1.0));
 
-        let mut number_of_constraints = 0usize;
-        let mut has_non_strict_constraints = false;
-        for formula in path.nodes.iter().map(|node| &node.formula)
+        let mut has_strict_constraints = false;
+        for formula in path.nodes.iter().map(|node| node.formula.as_ref())
         {
             if let LessThan(left, right, _) = formula
             {
-                let vector = self.create_linear_program_constraint_vector(&variables, left, right);
-                linear_program.add_constraint(vector, ComparisonOp::Le, -EPSILON);
-                has_non_strict_constraints = true;
-                number_of_constraints += 1;
-
+                let mut vector = self.create_linear_program_constraint_vector(&variables, left, right).into_vec();
+                vector.push((slack_variable, 1.0));
+                linear_program.add_constraint(vector.as_slice(), ComparisonOp::Le, 0.0);
+                has_strict_constraints = true;
             }
             else if let GreaterOrEqualThan(left, right, _) = formula
             {
                 let vector = self.create_linear_program_constraint_vector(&variables, left, right);
                 linear_program.add_constraint(vector, ComparisonOp::Ge, 0.0);
-                number_of_constraints += 1;
             }
         }
 
-        //we should have at least two inequalities
-        if number_of_constraints < 2 { return false };
-
-        /* todo hack: Linear programming technique does not work well with non-strict (<) inequalities.
-            And the minilp library does not provide support for < inequalities, only <= inequalities.
-            That's why x < 0 inequalities are implemented as x <= -EPSILON inequalities.
-            However, this adds some ambiguity: sometimes, the program cannot distinguish between
-            actual solutions and insignificant solutions. Is 1 - EPSILON a legit solution or not? */
-        if matches!(path.domain_type, VariableDomain(..)) && has_non_strict_constraints
+        return match linear_program.solve()
         {
-            variables.iter()
-                .filter(|(tag, _)| *tag != &FuzzyTag::zero() && *tag != &FuzzyTag::one())
-                .map(|(_, variable)| ((*variable, 1.0), 1.0 - 2.0 * EPSILON))
-                .for_each(|(x, y)| linear_program.add_constraint(&[x], ComparisonOp::Le, y));
-        }
-
-        if let Ok(solution) = linear_program.solve()
-        {
-            //linear program has at least one significant solution => contradiction!
-            return variables.iter().map(|(_, variable)| solution[*variable])
-                .any(|value| 1.0 - EPSILON <= value && value < 1.0);
-        }
-
-        //linear program has no solutions => contradiction!
-        return true;
+            Ok(solution) => !has_strict_constraints || solution[slack_variable] > EPSILON,
+            Err(_) => false,
+        };
     }
-}
 
-impl FuzzyLogicSemantics
-{
     fn create_linear_program_constraint_vector(&self,
         variables : &BTreeMap<FuzzyTag, Variable>,
         left_side_of_the_inequality : &FuzzyTags,
         right_side_of_the_inequality : &FuzzyTags,
     ) -> Box<[(Variable, f64)]>
     {
-        let mut vector: Vec<(Variable, f64)> = Vec::new();
+        //the same tag may appear on both sides, so the coefficients are summed up
+        let mut coefficients : BTreeMap<Variable, f64> = BTreeMap::new();
 
         for fuzzy_tag in left_side_of_the_inequality.iter()
         {
             let variable = variables[&fuzzy_tag.abs()];
             let coefficient = if fuzzy_tag.sign == Plus { 1.0 } else { -1.0 };
-            vector.push((variable, coefficient));
+            *coefficients.entry(variable).or_default() += coefficient;
         }
 
         for fuzzy_tag in right_side_of_the_inequality.iter()
         {
             let variable = variables[&fuzzy_tag.abs()];
             let coefficient = if fuzzy_tag.sign == Plus { -1.0 } else { 1.0 };
-            vector.push((variable, coefficient));
+            *coefficients.entry(variable).or_default() += coefficient;
         }
 
-        return vector.into_boxed_slice();
+        return coefficients.into_iter().collect::<Vec<(Variable, f64)>>().into_boxed_slice();
     }
 }
@@ -10,7 +10,7 @@ pub struct ManyValuedLogicSemantics
     contradiction_behaviours : Vec<ManyValuedContradictionBehaviour>,
 }
 
-enum TruthValueSetCardinality { Three, Four }
+enum TruthValueSetCardinality { Three, Four, FourWithRoutleyStar }
 
 #[derive(Eq, PartialEq)]
 pub enum ManyValuedContradictionBehaviour
@@ -45,6 +45,19 @@ impl ManyValuedLogicSemantics
             ]
         };
     }
+
+    pub fn with_routley_star() -> ManyValuedLogicSemantics
+    {
+        return ManyValuedLogicSemantics
+        {
+            truth_value_set_cardinality: TruthValueSetCardinality::FourWithRoutleyStar,
+            contradiction_behaviours: vec!
+            [
+                //negations are evaluated on star worlds, so A+ and A- on the same world is the only closure rule
+                ManyValuedContradictionBehaviour::FormulaPlusWithFormulaMinus
+            ]
+        };
+    }
 }
 
 impl Semantics for ManyValuedLogicSemantics
@@ -58,6 +71,18 @@ impl Semantics for ManyValuedLogicSemantics
 
             //4-valued logic: true, false, neither true nor false, both true and false
             TruthValueSetCardinality::Four => 4,
+
+            //4-valued logic, each value being a pair of classical values on worlds x and x*
+            TruthValueSetCardinality::FourWithRoutleyStar => 4,
+        };
+    }
+
+    fn number_of_truth_values_on_each_world(&self) -> u8
+    {
+        return match self.truth_value_set_cardinality
+        {
+            TruthValueSetCardinality::FourWithRoutleyStar => 2,
+            _ => self.number_of_truth_values(),
         };
     }
 
@@ -0,0 +1,66 @@
+use std::collections::BTreeSet;
+use std::rc::Rc;
+use crate::formula::Formula;
+use crate::formula::Formula::Atomic;
+use crate::logic::truth_matrix_logic::TruthMatrix;
+use crate::semantics::Semantics;
+use crate::tree::node_factory::ProofTreeNodeID;
+use crate::tree::path::ProofTreePath;
+
+pub struct TruthMatrixLogicSemantics
+{
+    pub matrix : Rc<TruthMatrix>
+}
+
+impl Semantics for TruthMatrixLogicSemantics
+{
+    fn number_of_truth_values(&self) -> u8
+    {
+        //matrices have at most MAX_NUMBER_OF_TRUTH_VALUES values
+        return u8::try_from(self.matrix.number_of_truth_values()).unwrap();
+    }
+
+    fn reductio_ad_absurdum(&self, formula : &Formula) -> Formula
+    {
+        let non_designated_values = self.matrix.non_designated_values();
+        return formula.with_fuzzy_tags(self.matrix.to_fuzzy_tags(&non_designated_values));
+    }
+
+    //the values of an atomic formula are intersected along the whole path
+    fn are_contradictions_between_pairs_of_formulas(&self) -> bool { false }
+
+    fn are_formulas_contradictory(&self, _ : &ProofTreePath, _ : &Formula, _ : &Formula) -> bool { false }
+
+    fn get_path_contradictory_node_ids(&self, path : &ProofTreePath) -> Option<(ProofTreeNodeID, ProofTreeNodeID)>
+    {
+        return path.nodes.iter().rev()
+            .find(|node| self.is_formula_contradictory(path, &node.formula))
+            .map(|node| (node.id, path.nodes[0].id));
+    }
+}
+
+impl TruthMatrixLogicSemantics
+{
+    fn is_formula_contradictory(&self, path : &ProofTreePath, p : &Formula) -> bool
+    {
+        //the formula cannot take any of its values, eg: ¬p tagged with a value that is not a negation of anything
+        if self.matrix.get_values(p).is_disjoint(&self.matrix.get_range(p)) { return true };
+
+        //the values of the same atomic formula on the path have nothing in common
+        if let Atomic(p_name, p_extras) = p
+        {
+            let mut common_values = self.matrix.get_values(p);
+            for formula in path.nodes.iter().map(|node| node.formula.as_ref())
+            {
+                if let Atomic(q_name, q_extras) = formula && p_name == q_name && p_extras.predicate_args == q_extras.predicate_args
+                {
+                    common_values = common_values.intersection(&self.matrix.get_values(formula)).cloned().collect::<BTreeSet<usize>>();
+                }
+            }
+
+            return common_values.is_empty();
+        }
+
+        return false;
+    }
+}
@@ -1,8 +1,14 @@
+use std::collections::BTreeMap;
+use std::rc::Rc;
 use rand::prelude::IteratorRandom;
 use crate::graph::Graph;
-use crate::problem::Problem;
+use crate::problem::{Problem, ProofLimit};
+use crate::proof::cancellation::{ProofCancellation, ProofCancellationToken};
 use crate::proof::execution_log::ExecutionLogHelperData;
+use crate::semantics::Semantics;
 use crate::tree::node::ProofTreeNode;
+use crate::tree::arena::{ProofTreeArena, ProofTreeArenaNode};
+use crate::tree::literal_index::PathLiteralIndex;
 use crate::tree::node_factory::{ProofTreeNodeFactory, ProofTreeNodeID};
 use crate::tree::path::ProofTreePath;
 
@@ -12,15 +18,21 @@ mod to_string;
 mod to_json;
 pub mod subtree;
 pub mod node_factory;
+pub mod literal_index;
+pub mod arena;
 
 pub struct ProofTree
 {
     pub problem : Problem,
-    pub root_node : ProofTreeNode,
+    arena : ProofTreeArena,
     pub node_factory : ProofTreeNodeFactory,
     pub modality_graph : Graph,
     pub is_proof_correct : bool,
     pub has_timeout : bool,
+    pub reached_limit : Option<ProofLimit>,
+    pub cancellation : Option<ProofCancellation>,
+    pub cancellation_token : ProofCancellationToken,
+    path_literal_indexes : BTreeMap<ProofTreeNodeID, PathLiteralIndex>,
 }
 
 impl ProofTree
@@ -29,15 +41,21 @@ impl ProofTree
     {
         return ProofTree
         {
-            problem, root_node, node_factory,
+            problem, node_factory,
+            arena: ProofTreeArena::new(root_node),
             modality_graph: Graph::new(),
-            is_proof_correct:false, has_timeout:false,
+            is_proof_correct:false, has_timeout:false, reached_limit:None,
+            cancellation:None, cancellation_token:ProofCancellationToken::new(),
+            path_literal_indexes:BTreeMap::new(),
         }
     }
 
     pub fn get_all_paths(&self) -> Vec<ProofTreePath>
     {
-        return self.root_node.get_all_paths();
+        let root_node_id = self.arena.get_root_node().id;
+        return self.arena.get_leaf_node_ids_below(root_node_id).into_iter()
+            .map(|leaf_node_id| self.arena.get_path_to_leaf(leaf_node_id))
+            .collect();
     }
 
     pub fn get_path_that_goes_through_node(&self, node : &ProofTreeNode) -> ProofTreePath
@@ -48,52 +66,98 @@ impl ProofTree
 
     pub fn get_paths_that_goes_through_node(&self, node : &ProofTreeNode) -> Vec<ProofTreePath>
     {
-        return self.get_all_paths().into_iter().filter(|path| path.contains(node)).collect();
+        //only the paths to the leaves below the node are built
+        return self.arena.get_leaf_node_ids_below(node.id).into_iter()
+            .map(|leaf_node_id| self.arena.get_path_to_leaf(leaf_node_id))
+            .collect();
     }
 
-    pub fn get_node_with_id(&self, node_id : ProofTreeNodeID) -> Option<&ProofTreeNode>
+    pub fn get_node_with_id(&self, node_id : ProofTreeNodeID) -> Option<&ProofTreeArenaNode>
     {
-        return self.root_node.get_node_with_id(node_id);
+        return self.arena.get_node_with_id(node_id);
     }
 
     pub fn get_total_number_of_nodes(&self) -> usize
     {
-        return 1 + self.root_node.get_total_number_of_nodes();
+        return self.arena.get_total_number_of_nodes();
+    }
+
+    pub fn get_depth(&self) -> usize
+    {
+        return self.arena.get_depth();
     }
 
     pub fn check_for_contradictions(&mut self)
     {
-        let mut number_of_contradictory_paths = 0usize;
+        let mut are_all_paths_contradictory = true;
+        let semantics = self.problem.logic.get_semantics();
 
-        let paths = self.get_all_paths();
-        for path in &paths
+        //nodes are appended only on leaves, thus only the paths below the previously checked leaves have new nodes
+        let previous_leaf_node_ids_and_path_literal_indexes = match std::mem::take(&mut self.path_literal_indexes)
         {
-            let contradictory_node_ids = path.get_contradictory_node_ids(&self.problem.logic);
-            if let Some((contradictory_node_id, contrarian_node_id)) = contradictory_node_ids
+            path_literal_indexes if path_literal_indexes.is_empty() => vec![(self.arena.get_root_node().id, None)],
+            path_literal_indexes => path_literal_indexes.into_iter()
+                .map(|(leaf_node_id, path_literal_index)| (leaf_node_id, Some(path_literal_index)))
+                .collect(),
+        };
+
+        for (previous_leaf_node_id, previous_path_literal_index) in previous_leaf_node_ids_and_path_literal_indexes
+        {
+            let is_previous_leaf_still_a_leaf = self.arena.get_node_with_id(previous_leaf_node_id).is_some_and(|node| node.is_leaf());
+            if is_previous_leaf_still_a_leaf && let Some(path_literal_index) = previous_path_literal_index
             {
-                self.root_node.mark_child_node_as_contradictory(contradictory_node_id, contrarian_node_id);
+                are_all_paths_contradictory &= path_literal_index.get_contradictory_node_ids().is_some();
+                self.path_literal_indexes.insert(previous_leaf_node_id, path_literal_index);
+                continue;
             }
 
-            ExecutionLogHelperData::with(|mut helper_data|
+            //a path that didn't branch takes over the index of its previous leaf, the paths of a branch share it instead of copying it
+            let leaf_node_ids = self.arena.get_leaf_node_ids_below(previous_leaf_node_id);
+            let previous_path_literal_index = previous_path_literal_index.unwrap_or_default();
+            if let [leaf_node_id] = leaf_node_ids[..]
             {
-                if let Some(contradictory_node_id_pair) = contradictory_node_ids
-                {
-                    if !helper_data.old_contradictions.contains(&contradictory_node_id_pair)
-                    {
-                        helper_data.new_contradictions.insert(contradictory_node_id_pair.clone());
-                    }
-                }
-            });
+                are_all_paths_contradictory &= self.check_path_for_contradictions(leaf_node_id, previous_path_literal_index, semantics.as_ref());
+                continue;
+            }
 
-            if contradictory_node_ids.is_some()
+            let shared_path_literal_index = Rc::new(previous_path_literal_index);
+            for leaf_node_id in leaf_node_ids
             {
-                number_of_contradictory_paths += 1;
+                let path_literal_index = PathLiteralIndex::continuing(shared_path_literal_index.clone());
+                are_all_paths_contradictory &= self.check_path_for_contradictions(leaf_node_id, path_literal_index, semantics.as_ref());
             }
         }
 
-        if number_of_contradictory_paths == paths.len()
+        if are_all_paths_contradictory
         {
             self.is_proof_correct = true;
         }
     }
+
+    fn check_path_for_contradictions(&mut self, leaf_node_id : ProofTreeNodeID, mut path_literal_index : PathLiteralIndex, semantics : &dyn Semantics) -> bool
+    {
+        //paths are built one at a time
+        let path = self.arena.get_path_to_leaf(leaf_node_id);
+        path_literal_index.index_new_nodes(&path, &self.problem.logic, semantics);
+        let contradictory_node_ids = path_literal_index.get_contradictory_node_ids();
+        self.path_literal_indexes.insert(leaf_node_id, path_literal_index);
+
+        if let Some((contradictory_node_id, contrarian_node_id)) = contradictory_node_ids
+        {
+            self.arena.mark_node_as_contradictory(contradictory_node_id, contrarian_node_id);
+        }
+
+        ExecutionLogHelperData::with(|mut helper_data|
+        {
+            if let Some(contradictory_node_id_pair) = contradictory_node_ids
+            {
+                if !helper_data.old_contradictions.contains(&contradictory_node_id_pair)
+                {
+                    helper_data.new_contradictions.insert(contradictory_node_id_pair.clone());
+                }
+            }
+        });
+
+        return contradictory_node_ids.is_some();
+    }
 }
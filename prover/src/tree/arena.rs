@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::formula::Formula;
+use crate::logic::first_order_logic::FirstOrderLogicDomainType;
+use crate::tree::node::ProofTreeNode;
+use crate::tree::node_factory::ProofTreeNodeID;
+use crate::tree::path::{ProofTreePath, ProofTreePathNodeData};
+use crate::tree::subtree::ProofSubtree;
+
+type ProofTreeArenaIndex = usize;
+
+//the nodes of the proof tree, stored flat and linked by their indices. Formulas are shared with the paths going through them,
+//with the appended subtrees and with the decomposition queue
+pub struct ProofTreeArena
+{
+    nodes : Vec<ProofTreeArenaNode>,
+    node_indices_by_id : HashMap<ProofTreeNodeID, ProofTreeArenaIndex>,
+    depth : usize,
+}
+
+pub struct ProofTreeArenaNode
+{
+    pub id : ProofTreeNodeID,
+    pub formula : Rc<Formula>,
+    pub domain_type : FirstOrderLogicDomainType,
+    pub spawner_node_id : Option<ProofTreeNodeID>,
+    pub contrarian_node_id : Option<ProofTreeNodeID>,
+    pub is_contradictory : bool,
+    parent : Option<ProofTreeArenaIndex>,
+    left : Option<ProofTreeArenaIndex>,
+    middle : Option<ProofTreeArenaIndex>,
+    right : Option<ProofTreeArenaIndex>,
+    depth : usize,
+}
+
+impl ProofTreeArenaNode
+{
+    //a copy of the node, without its children
+    pub fn to_tree_node(&self) -> ProofTreeNode
+    {
+        return ProofTreeNode
+        {
+            id: self.id,
+            formula: self.formula.clone(),
+            left: None, middle: None, right: None,
+            domain_type: self.domain_type,
+            spawner_node_id: self.spawner_node_id,
+            contrarian_node_id: self.contrarian_node_id,
+            is_contradictory: self.is_contradictory,
+        };
+    }
+
+    pub fn is_leaf(&self) -> bool
+    {
+        return self.left.is_none() && self.middle.is_none() && self.right.is_none();
+    }
+}
+
+impl ProofTreeArena
+{
+    pub fn new(root_node : ProofTreeNode) -> ProofTreeArena
+    {
+        let mut arena = ProofTreeArena { nodes:vec![], node_indices_by_id:HashMap::new(), depth:0 };
+        arena.insert(&root_node, None);
+        return arena;
+    }
+
+    fn insert(&mut self, node : &ProofTreeNode, parent : Option<ProofTreeArenaIndex>) -> ProofTreeArenaIndex
+    {
+        let index = self.nodes.len();
+        let depth = parent.map(|parent| self.nodes[parent].depth + 1).unwrap_or(1);
+        self.depth = self.depth.max(depth);
+
+        self.node_indices_by_id.insert(node.id, index);
+        self.nodes.push(ProofTreeArenaNode
+        {
+            id: node.id,
+            formula: node.formula.clone(),
+            domain_type: node.domain_type,
+            spawner_node_id: node.spawner_node_id,
+            contrarian_node_id: node.contrarian_node_id,
+            is_contradictory: node.is_contradictory,
+            parent, left: None, middle: None, right: None, depth,
+        });
+
+        self.nodes[index].left = node.left.as_ref().map(|left| self.insert(left, Some(index)));
+        self.nodes[index].middle = node.middle.as_ref().map(|middle| self.insert(middle, Some(index)));
+        self.nodes[index].right = node.right.as_ref().map(|right| self.insert(right, Some(index)));
+
+        return index;
+    }
+
+    pub fn append_subtree_on_leaf(&mut self, subtree : &ProofSubtree, leaf_node_id : ProofTreeNodeID)
+    {
+        let Some(leaf_index) = self.node_indices_by_id.get(&leaf_node_id).cloned() else { return };
+
+        self.nodes[leaf_index].left = subtree.left.as_ref().map(|left| self.insert(left, Some(leaf_index)));
+        self.nodes[leaf_index].middle = subtree.middle.as_ref().map(|middle| self.insert(middle, Some(leaf_index)));
+        self.nodes[leaf_index].right = subtree.right.as_ref().map(|right| self.insert(right, Some(leaf_index)));
+    }
+
+    pub fn get_node_with_id(&self, node_id : ProofTreeNodeID) -> Option<&ProofTreeArenaNode>
+    {
+        return self.node_indices_by_id.get(&node_id).map(|index| &self.nodes[*index]);
+    }
+
+    pub fn get_root_node(&self) -> &ProofTreeArenaNode
+    {
+        return &self.nodes[0];
+    }
+
+    //left, middle and right, only the existing ones
+    pub fn get_children(&self, node : &ProofTreeArenaNode) -> impl Iterator<Item = &ProofTreeArenaNode>
+    {
+        return [node.left, node.middle, node.right].into_iter().flatten().map(|index| &self.nodes[index]);
+    }
+
+    pub fn get_left_middle_right_children(&self, node : &ProofTreeArenaNode) -> [Option<&ProofTreeArenaNode>; 3]
+    {
+        return [node.left, node.middle, node.right].map(|index| index.map(|index| &self.nodes[index]));
+    }
+
+    pub fn mark_node_as_contradictory(&mut self, node_id : ProofTreeNodeID, contrarian_node_id : ProofTreeNodeID)
+    {
+        if let Some(index) = self.node_indices_by_id.get(&node_id)
+        {
+            let node = &mut self.nodes[*index];
+            node.is_contradictory = true;
+            node.contrarian_node_id = Some(contrarian_node_id);
+        }
+    }
+
+    pub fn get_total_number_of_nodes(&self) -> usize
+    {
+        return self.nodes.len();
+    }
+
+    pub fn get_depth(&self) -> usize
+    {
+        return self.depth;
+    }
+
+    //the leaves below the given node, in the order of the paths: left, middle, right
+    pub fn get_leaf_node_ids_below(&self, node_id : ProofTreeNodeID) -> Vec<ProofTreeNodeID>
+    {
+        let Some(index) = self.node_indices_by_id.get(&node_id).cloned() else { return vec![] };
+
+        let mut leaf_node_ids : Vec<ProofTreeNodeID> = vec![];
+        let mut indices_to_visit = vec![index];
+        while let Some(index) = indices_to_visit.pop()
+        {
+            let node = &self.nodes[index];
+            if node.is_leaf() { leaf_node_ids.push(node.id) }
+            indices_to_visit.extend([node.right, node.middle, node.left].into_iter().flatten());
+        }
+
+        return leaf_node_ids;
+    }
+
+    pub fn get_path_to_leaf(&self, leaf_node_id : ProofTreeNodeID) -> ProofTreePath
+    {
+        let mut path_nodes : Vec<ProofTreePathNodeData> = vec![];
+        let mut index_option = self.node_indices_by_id.get(&leaf_node_id).cloned();
+        while let Some(index) = index_option
+        {
+            let node = &self.nodes[index];
+            path_nodes.push(ProofTreePathNodeData { id:node.id, is_contradictory:node.is_contradictory, formula:node.formula.clone() });
+            index_option = node.parent;
+        }
+
+        path_nodes.reverse();
+        return ProofTreePath { nodes:path_nodes, domain_type:self.get_root_node().domain_type };
+    }
+
+    pub fn is_path_to_leaf_contradictory(&self, leaf_node_id : ProofTreeNodeID) -> bool
+    {
+        let mut index_option = self.node_indices_by_id.get(&leaf_node_id).cloned();
+        while let Some(index) = index_option
+        {
+            if self.nodes[index].is_contradictory { return true }
+            index_option = self.nodes[index].parent;
+        }
+
+        return false;
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::mem::Discriminant;
+use std::rc::Rc;
+use smol_str::SmolStr;
+use crate::formula::{Formula, PossibleWorld};
+use crate::formula::Formula::{Atomic, Comment, Equals, Nominal, Non};
+use crate::logic::Logic;
+use crate::semantics::Semantics;
+use crate::tree::node_factory::ProofTreeNodeID;
+use crate::tree::path::ProofTreePath;
+
+//formulas that could contradict each other share the same key: the same world, the same kind of formula under negations
+//and, for atomic formulas and nominals, the same name. Both signs share a key, since which signs clash depends on the semantics
+#[derive(Eq, PartialEq, Hash, Clone)]
+struct LiteralKey
+{
+    possible_world : PossibleWorld,
+    kind : Discriminant<Formula>,
+    name : Option<SmolStr>,
+}
+
+impl LiteralKey
+{
+    fn of(formula : &Formula) -> Option<LiteralKey>
+    {
+        let mut kernel = formula;
+        while let Non(box p, _) = kernel { kernel = p }
+
+        let name = match kernel
+        {
+            Atomic(name, _) | Nominal(name, _) => Some(name.clone()),
+            Comment(..) => { return None }
+            _ => None
+        };
+
+        return Some(LiteralKey { possible_world:formula.get_possible_world(), kind:std::mem::discriminant(kernel), name });
+    }
+}
+
+//the formulas of a path grouped by literal key, such that the nodes appended to the path
+//are checked only against the nodes they could contradict, instead of rechecking every pair.
+//The paths of a branch share the index of the path before the branch and only hold their own nodes
+#[derive(Default)]
+pub struct PathLiteralIndex
+{
+    parent : Option<Rc<PathLiteralIndex>>,
+    number_of_indexed_nodes : usize,
+    node_indices_by_key : HashMap<LiteralKey, Vec<usize>>,
+    has_equalities : bool,
+    contradictory_node_ids : Option<(ProofTreeNodeID, ProofTreeNodeID)>,
+}
+
+impl PathLiteralIndex
+{
+    pub fn continuing(parent : Rc<PathLiteralIndex>) -> PathLiteralIndex
+    {
+        return PathLiteralIndex
+        {
+            number_of_indexed_nodes: parent.number_of_indexed_nodes,
+            node_indices_by_key: HashMap::new(),
+            has_equalities: parent.has_equalities,
+            contradictory_node_ids: parent.contradictory_node_ids,
+            parent: Some(parent),
+        };
+    }
+
+    //from the root of the path to this index
+    fn get_ancestors_and_self(&self) -> Vec<&PathLiteralIndex>
+    {
+        let mut indexes = vec![self];
+        while let Some(parent) = &indexes.last().unwrap().parent
+        {
+            indexes.push(parent.as_ref());
+        }
+
+        indexes.reverse();
+        return indexes;
+    }
+
+    pub fn get_contradictory_node_ids(&self) -> Option<(ProofTreeNodeID, ProofTreeNodeID)>
+    {
+        return self.contradictory_node_ids;
+    }
+
+    //the path must extend the previously indexed path. The result is the same as ProofTreePath::get_contradictory_node_ids
+    pub fn index_new_nodes(&mut self, path : &ProofTreePath, logic : &Rc<dyn Logic>, semantics : &dyn Semantics)
+    {
+        let first_new_node_index = self.number_of_indexed_nodes;
+        if first_new_node_index >= path.nodes.len() { return }
+
+        for index in first_new_node_index..path.nodes.len()
+        {
+            let formula = path.nodes[index].formula.as_ref();
+            self.has_equalities |= matches!(formula, Equals(..) | Non(box Equals(..), _));
+
+            if let Some(key) = LiteralKey::of(formula)
+            {
+                self.node_indices_by_key.entry(key).or_default().push(index);
+            }
+        }
+
+        self.number_of_indexed_nodes = path.nodes.len();
+
+        //equalities change the arguments of the atomic formulas checked before them, thus the whole path is checked again
+        if self.has_equalities
+        {
+            self.contradictory_node_ids = path.get_contradictory_node_ids(logic);
+            return;
+        }
+
+        //the semantics checks the whole path at once, after the pairs of formulas
+        if !semantics.are_contradictions_between_pairs_of_formulas()
+        {
+            self.contradictory_node_ids = path.get_contradictory_pair_node_ids(semantics)
+                .or_else(|| semantics.get_path_contradictory_node_ids(path));
+            return;
+        }
+
+        //same order as ProofTreePath::get_contradictory_node_ids, older contradictions are found only if new nodes bring none
+        let indexes = self.get_ancestors_and_self();
+        for i in (first_new_node_index..path.nodes.len()).rev()
+        {
+            let Some(key) = LiteralKey::of(&path.nodes[i].formula) else { continue };
+
+            let node_indices = indexes.iter().filter_map(|index| index.node_indices_by_key.get(&key)).flatten();
+            for j in node_indices.take_while(|j| **j < i)
+            {
+                if semantics.are_formulas_contradictory(path, &path.nodes[i].formula, &path.nodes[*j].formula)
+                {
+                    self.contradictory_node_ids = Some((path.nodes[i].id, path.nodes[*j].id));
+                    return;
+                }
+            }
+        }
+    }
+}
@@ -95,7 +95,7 @@ impl ProofTreeNodeFactoryImpl
         return ProofTreeNode
         {
             id: self.node_id_sequence.next(),
-            formula: formula,
+            formula: Rc::new(formula),
             left: None, middle: None, right: None,
             domain_type: self.domain_type,
             spawner_node_id: self.spawner_node_id,
@@ -109,7 +109,7 @@ impl ProofTreeNodeFactoryImpl
         return ProofTreeNode
         {
             id: self.node_id_sequence.next(),
-            formula: formula,
+            formula: Rc::new(formula),
             left: None, right: None,
             middle: Some(Box::new(child)),
             domain_type: self.domain_type,
@@ -3,6 +3,7 @@ use std::rc::Rc;
 use crate::formula::Formula;
 use crate::logic::first_order_logic::FirstOrderLogicDomainType;
 use crate::logic::Logic;
+use crate::semantics::Semantics;
 use crate::tree::node::ProofTreeNode;
 use crate::tree::node_factory::ProofTreeNodeID;
 
@@ -18,7 +19,8 @@ pub struct ProofTreePathNodeData
 {
     pub id : ProofTreeNodeID,
     pub is_contradictory : bool,
-    pub formula : Formula,
+    //shared with the proof tree node, paths are built often and don't need their own copy
+    pub formula : Rc<Formula>,
 }
 
 impl ProofTreePathNodeData
@@ -75,12 +77,18 @@ impl ProofTreePath
     }
 
     pub fn get_contradictory_node_ids(&self, logic : &Rc<dyn Logic>) -> Option<(ProofTreeNodeID, ProofTreeNodeID)>
+    {
+        let semantics = logic.get_semantics();
+        return self.get_contradictory_pair_node_ids(semantics.as_ref())
+            .or_else(|| semantics.get_path_contradictory_node_ids(&self));
+    }
+
+    pub fn get_contradictory_pair_node_ids(&self, semantics : &dyn Semantics) -> Option<(ProofTreeNodeID, ProofTreeNodeID)>
     {
         for i in (0..self.nodes.len()).rev()
         {
             for j in 0..i
             {
-                let semantics = logic.get_semantics();
                 if semantics.are_formulas_contradictory(&self, &self.nodes[i].formula, &self.nodes[j].formula)
                 {
                     return Some((self.nodes[i].id, self.nodes[j].id));
@@ -1,3 +1,4 @@
+use std::rc::Rc;
 use crate::proof::decomposition_queue::DecompositionPriorityQueue;
 use crate::tree::node::ProofTreeNode;
 use crate::tree::node_factory::{ProofTreeNodeFactory, ProofTreeNodeID};
@@ -77,6 +78,27 @@ impl ProofSubtree
         return ProofSubtree::new(Some(Box::new(root_left)), None, Some(Box::new(root_right)));
     }
 
+    pub fn with_nested_left_right_nodes(nodes : Vec<(ProofTreeNode, ProofTreeNode)>, node_factory : &mut ProofTreeNodeFactory) -> ProofSubtree
+    {
+        //every pair branches again below both nodes of the previous pair
+        let mut subtree = ProofSubtree::empty();
+        for (mut left, mut right) in nodes.into_iter().rev()
+        {
+            if !subtree.is_empty()
+            {
+                let mut subtree_with_new_ids = subtree.clone();
+                subtree_with_new_ids.attach_new_ids(node_factory);
+
+                (left.left, left.right) = (subtree.left, subtree.right);
+                (right.left, right.right) = (subtree_with_new_ids.left, subtree_with_new_ids.right);
+            }
+
+            subtree = ProofSubtree::with_left_right_nodes(left, right);
+        }
+
+        return subtree;
+    }
+
     pub fn with_left_right_nodes(left : ProofTreeNode, right : ProofTreeNode) -> ProofSubtree
     {
         return ProofSubtree::new(Some(Box::new(left)), None, Some(Box::new(right)));
@@ -128,23 +150,21 @@ impl ProofTree
 
         let mut should_clone_subtree_with_new_ids = false;
 
-        let paths = self.get_all_paths();
-        for path in &paths
+        let leaf_node_ids = self.arena.get_leaf_node_ids_below(target_node_id);
+        for leaf_node_id in leaf_node_ids
         {
-            if path.nodes.iter().all(|node| !node.is_contradictory) &&
-                path.nodes.iter().any(|node| node.id == target_node_id)
+            if !self.arena.is_path_to_leaf_contradictory(leaf_node_id)
             {
-                let leaf = path.nodes.last().unwrap();
                 if !should_clone_subtree_with_new_ids
                 {
-                    self.root_node.append_subtree_on_leaf(&another_subtree, leaf.id);
+                    self.arena.append_subtree_on_leaf(&another_subtree, leaf_node_id);
                     should_clone_subtree_with_new_ids = true;
                 }
                 else
                 {
                     let mut subtree_with_new_ids = another_subtree.clone();
                     subtree_with_new_ids.attach_new_ids(&mut self.node_factory);
-                    self.root_node.append_subtree_on_leaf(&subtree_with_new_ids, leaf.id);
+                    self.arena.append_subtree_on_leaf(&subtree_with_new_ids, leaf_node_id);
                     another_subtree.cloned_subtrees_with_new_ids.push(Box::new(subtree_with_new_ids));
                 }
             }
@@ -184,7 +204,7 @@ impl ProofTreeNode
 
     fn hide_all_nodes(&mut self)
     {
-        self.formula = self.formula.with_is_hidden(true);
+        self.formula = Rc::new(self.formula.with_is_hidden(true));
         if let Some(left) = &mut self.left { left.hide_all_nodes(); }
         if let Some(middle) = &mut self.middle { middle.hide_all_nodes(); }
         if let Some(right) = &mut self.right { right.hide_all_nodes(); }
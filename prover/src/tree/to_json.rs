@@ -5,8 +5,10 @@ use crate::countermodel::CountermodelGraph;
 use crate::formula::to_string::FormulaFormatOptions;
 use crate::graph::to_json::GraphJSON;
 use crate::problem::json::ProblemJSON;
+use crate::problem::ProofLimit;
+use crate::proof::cancellation::ProofCancellation;
 use crate::proof::execution_log::ExecutionLog;
-use crate::tree::node::ProofTreeNode;
+use crate::tree::arena::ProofTreeArenaNode;
 use crate::tree::node_factory::ProofTreeNodeID;
 use crate::tree::ProofTree;
 
@@ -16,12 +18,22 @@ struct ProofTreeJSON
     problem : ProblemJSON,
     was_proved : bool,
     has_timeout : bool,
+    reached_limit : Option<ProofLimit>,
+    cancellation : Option<ProofCancellation>,
     root_node : ProofTreeNodeJSON,
     modality_graph : GraphJSON,
+    branch_modality_graphs : Vec<ProofTreeBranchGraphJSON>,
     countermodel : Option<CountermodelGraph>,
     execution_log : Vec<String>,
 }
 
+#[derive(Serialize, Deserialize)]
+struct ProofTreeBranchGraphJSON
+{
+    leaf_node_id : ProofTreeNodeID,
+    modality_graph : GraphJSON,
+}
+
 #[derive(Serialize, Deserialize)]
 struct ProofTreeNodeJSON
 {
@@ -44,33 +56,49 @@ impl ProofTree
             problem: self.problem.to_json(options),
             was_proved: self.is_proof_correct,
             has_timeout: self.has_timeout,
-            root_node: self.root_node.to_json(options),
+            reached_limit: self.reached_limit,
+            cancellation: self.cancellation,
+            root_node: self.node_to_json(self.arena.get_root_node(), options),
             modality_graph: self.modality_graph.to_json(),
+            branch_modality_graphs: self.get_branch_modality_graphs_json(),
             countermodel: self.find_countermodel(),
             execution_log: ExecutionLog::clear(),
         };
 
         return serde_json::to_string_pretty(&json).context(codeloc!());
     }
+
+    fn get_branch_modality_graphs_json(&self) -> Vec<ProofTreeBranchGraphJSON>
+    {
+        if self.modality_graph.is_empty() { return vec![] };
+
+        //each open branch might have its own graph, if branching frame rules were applied
+        return self.get_all_paths().iter()
+            .filter(|path| !path.is_contradictory(&self.problem.logic))
+            .map(|path| ProofTreeBranchGraphJSON
+            {
+                leaf_node_id: path.get_leaf_node_id(),
+                modality_graph: self.modality_graph.to_json_on_path(path),
+            })
+            .collect();
+    }
 }
 
-impl ProofTreeNode
+impl ProofTree
 {
-    fn to_json(&self, options : &FormulaFormatOptions) -> ProofTreeNodeJSON
+    fn node_to_json(&self, node : &ProofTreeArenaNode, options : &FormulaFormatOptions) -> ProofTreeNodeJSON
     {
+        let [left, middle, right] = self.arena.get_left_middle_right_children(node)
+            .map(|child| child.map(|child| Box::new(self.node_to_json(child, options))));
+
         return ProofTreeNodeJSON
         {
-            id: self.id,
-            formula: self.formula.to_string_with_options(options),
-            is_contradictory: self.is_contradictory,
-            spawner_node_id: self.spawner_node_id,
-            contrarian_node_id: self.contrarian_node_id,
-            left: if let Some(left) = &self.left
-                  { Some(Box::new(left.to_json(options))) } else { None },
-            middle: if let Some(middle) = &self.middle
-                    { Some(Box::new(middle.to_json(options))) } else { None },
-            right: if let Some(right) = &self.right
-                   { Some(Box::new(right.to_json(options))) } else { None },
+            id: node.id,
+            formula: node.formula.to_string_with_options(options),
+            is_contradictory: node.is_contradictory,
+            spawner_node_id: node.spawner_node_id,
+            contrarian_node_id: node.contrarian_node_id,
+            left, middle, right,
         };
     }
 }
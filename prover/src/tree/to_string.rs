@@ -1,5 +1,8 @@
 use std::fmt::{Display, Formatter};
+use crate::formula::Formula;
 use crate::formula::to_string::FormulaFormatOptions;
+use crate::tree::arena::ProofTreeArenaNode;
+use crate::tree::node_factory::ProofTreeNodeID;
 use crate::tree::node::ProofTreeNode;
 use crate::tree::ProofTree;
 use crate::tree::subtree::ProofSubtree;
@@ -19,14 +22,26 @@ impl ProofTree
     {
         let mut output_string = String::new();
 
-        if self.has_timeout { output_string.push_str("TIMEOUT!\n"); }
+        if let Some(reached_limit) = self.reached_limit { output_string.push_str(format!("TIMEOUT! Reached {:?}\n", reached_limit).as_str()); }
+        else if self.has_timeout { output_string.push_str("TIMEOUT!\n"); }
+        else if let Some(cancellation) = self.cancellation { output_string.push_str(format!("STOPPED! {:?}\n", cancellation).as_str()); }
         else if self.is_proof_correct { output_string.push_str("PROVED!\n"); }
         else { output_string.push_str("NOT PROVED!\n"); }
 
-        self.root_node.print_to_string(options, &mut output_string, 0);
+        self.print_node_to_string(self.arena.get_root_node(), options, &mut output_string, 0);
 
         return output_string;
     }
+
+    fn print_node_to_string(&self, node : &ProofTreeArenaNode, options : &FormulaFormatOptions, out_string : &mut String, indent : usize)
+    {
+        print_node_line_to_string(node.id, &node.formula, node.is_contradictory, options, out_string, indent);
+
+        for child in self.arena.get_children(node)
+        {
+            self.print_node_to_string(child, options, out_string, indent+1);
+        }
+    }
 }
 
 impl Display for ProofSubtree
@@ -58,32 +73,7 @@ impl ProofTreeNode
 {
     fn print_to_string(&self, options : &FormulaFormatOptions, out_string : &mut String, indent : usize)
     {
-        if indent>0
-        {
-            //append tree glyphs
-            out_string.push('├');
-            for _ in 0..indent { out_string.push_str("──"); }
-            out_string.push(' ');
-        }
-
-        //append node ID
-        out_string.push('<');
-        out_string.push_str(self.id.to_string().as_str());
-        out_string.push('>');
-        out_string.push(' ');
-
-        //append formula
-        let formula_as_string = self.formula.to_string_with_options(options);
-        out_string.push_str(formula_as_string.replace("\n", " ").as_str());
-
-        if self.is_contradictory
-        {
-            //append contradiction sign
-            out_string.push(' ');
-            out_string.push('X');
-        }
-
-        out_string.push('\n');
+        print_node_line_to_string(self.id, &self.formula, self.is_contradictory, options, out_string, indent);
 
         if let Some(left) = &self.left
         {
@@ -101,3 +91,33 @@ impl ProofTreeNode
         }
     }
 }
+
+fn print_node_line_to_string(id : ProofTreeNodeID, formula : &Formula, is_contradictory : bool, options : &FormulaFormatOptions, out_string : &mut String, indent : usize)
+{
+    if indent>0
+    {
+        //append tree glyphs
+        out_string.push('├');
+        for _ in 0..indent { out_string.push_str("──"); }
+        out_string.push(' ');
+    }
+
+    //append node ID
+    out_string.push('<');
+    out_string.push_str(id.to_string().as_str());
+    out_string.push('>');
+    out_string.push(' ');
+
+    //append formula
+    let formula_as_string = formula.to_string_with_options(options);
+    out_string.push_str(formula_as_string.replace("\n", " ").as_str());
+
+    if is_contradictory
+    {
+        //append contradiction sign
+        out_string.push(' ');
+        out_string.push('X');
+    }
+
+    out_string.push('\n');
+}
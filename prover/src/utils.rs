@@ -19,6 +19,7 @@ pub const CONFIG_KEY_MAX_COUNTERMODEL_GRAPH_NODES : &str = "max_countermodel_gra
 pub const CONFIG_KEY_MIN_COUNTERMODEL_DOMAIN_SIZE : &str = "min_countermodel_domain_size";
 pub const CONFIG_KEY_MAX_COUNTERMODEL_DOMAIN_SIZE : &str = "max_countermodel_domain_size";
 pub const CONFIG_KEY_SHOULD_SHUFFLE_COUNTERMODEL_GRAPHS : &str = "should_shuffle_countermodel_graphs";
+pub const CONFIG_KEY_PROOF_DEADLINE_IN_SECONDS : &str = "proof_deadline_in_seconds";
 
 #[cfg(not(any(target_arch = "wasm32", target_arch = "wasm64")))]
 pub fn get_config_value<R>(key : &str) -> Option<R> where R : FromStr, R : Default, R : Display
@@ -42,6 +43,28 @@ pub fn get_config_value<R>(key : &str) -> Option<R> where R : FromStr, R : Defau
     return R::from_str(url_arg.as_str()).ok();
 }
 
+//milliseconds elapsed since an arbitrary, but fixed, moment
+#[cfg(not(any(target_arch = "wasm32", target_arch = "wasm64")))]
+pub fn get_current_time_in_millis() -> f64
+{
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    static START_INSTANT : OnceLock<Instant> = OnceLock::new();
+    let start_instant = START_INSTANT.get_or_init(Instant::now);
+    return start_instant.elapsed().as_secs_f64() * 1000.0;
+}
+
+//std::time::Instant is not available on the web, thus the browser clock is used
+#[cfg(any(target_arch = "wasm32", target_arch = "wasm64"))]
+pub fn get_current_time_in_millis() -> f64
+{
+    return web_sys::window()
+        .and_then(|window| window.performance())
+        .map(|performance| performance.now())
+        .unwrap_or_default();
+}
+
 #[inline(always)]
 #[cfg(is_benchmark_mode_enabled)]
 pub fn measure_total_number_of_allocated_bytes<F>(function : F) -> f64 where F : FnOnce() -> ()
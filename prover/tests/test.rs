@@ -1,6 +1,13 @@
-use anyhow::Result;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use anyhow::{Context, Result};
+use prover::countermodel::{CountermodelGraph, CountermodelGraphNode, CountermodelGraphVertex};
+use prover::formula::PossibleWorld;
+use prover::logic::LogicFactory;
 use prover::problem::catalog::get_demo_problem_catalog;
 use prover::problem::json::ProblemJSON;
+use prover::problem::{ProofLimit, ProofLimits};
+use prover::proof::cancellation::{ProofCancellation, ProofCancellationToken};
 use prover::utils::{parallel_for_each_problem, setup_panicking_from_all_future_threads};
 
 const EXPECTED_TIMEOUT : &str = "timeout";
@@ -19,7 +26,7 @@ fn test_proof_status() -> Result<()>
     {
         let problem_id = &problem_json.id;
         let problem = problem_json.to_problem().unwrap();
-        let proof_tree = problem.prove();
+        let proof_tree = problem.prove(ProofCancellationToken::new());
 
         if problem_json.expected == EXPECTED_TIMEOUT && !proof_tree.has_timeout
         {
@@ -55,3 +62,80 @@ fn test_proof_status() -> Result<()>
         return Ok(());
     });
 }
+
+#[test]
+fn test_proof_cancellation() -> Result<()>
+{
+    //a valid formula, which times out after two rule applications
+    let problem_json = get_demo_problem_catalog()?.into_iter()
+        .flat_map(|book_chapter| book_chapter.problems)
+        .find(|problem_json| problem_json.id == "3.10.9.Limits.f")
+        .context("Problem 3.10.9.Limits.f is missing!")?;
+
+    let cancellation_token = ProofCancellationToken::new();
+    cancellation_token.cancel();
+    let proof_tree = problem_json.to_problem()?.prove(cancellation_token);
+    assert_eq!(proof_tree.cancellation, Some(ProofCancellation::Cancelled));
+    assert!(!proof_tree.is_proof_correct && !proof_tree.has_timeout);
+
+    let proof_tree = problem_json.to_problem()?.prove(ProofCancellationToken::with_deadline_after(Duration::ZERO));
+    assert_eq!(proof_tree.cancellation, Some(ProofCancellation::DeadlineExceeded));
+    assert!(!proof_tree.is_proof_correct && !proof_tree.has_timeout);
+
+    //there is no countermodel, thus the countermodel search would try every graph, unless stopped by the deadline
+    let started_at = Instant::now();
+    let proof_tree = problem_json.to_problem()?.prove(ProofCancellationToken::with_deadline_after(Duration::from_secs(3)));
+    assert!(proof_tree.has_timeout && proof_tree.cancellation.is_none());
+    assert!(proof_tree.find_countermodel().is_none());
+    assert!(started_at.elapsed() < Duration::from_secs(15));
+
+    return Ok(());
+}
+
+#[test]
+fn test_proof_limits_set_on_problem() -> Result<()>
+{
+    //a formula that needs 261 worlds, with limits set on the problem instead of its JSON
+    let problem_json = get_demo_problem_catalog()?.into_iter()
+        .flat_map(|book_chapter| book_chapter.problems)
+        .find(|problem_json| problem_json.id == "3.10.9.Limits.e")
+        .context("Problem 3.10.9.Limits.e is missing!")?;
+
+    let mut problem = problem_json.to_problem()?;
+    problem.flags.limits = ProofLimits { max_number_of_possible_worlds:Some(1000), ..ProofLimits::default() };
+
+    let proof_tree = problem.prove(ProofCancellationToken::new());
+    assert_eq!(proof_tree.reached_limit, Some(ProofLimit::MaxNumberOfPossibleWorlds));
+    assert_eq!(proof_tree.modality_graph.nodes().count(), u8::MAX as usize);
+
+    return Ok(());
+}
+
+#[test]
+fn test_countermodel_graph_transitivity() -> Result<()>
+{
+    let logic = LogicFactory::get_logic_by_name(&String::from("K4ModalLogic"))?;
+
+    let mut graph = CountermodelGraph::new();
+    for index in 0..3
+    {
+        let possible_world = PossibleWorld { index };
+        graph.nodes.insert(CountermodelGraphNode { possible_world, is_normal_world:true, atomics:BTreeMap::new() });
+    }
+
+    //w0 → w1 → w2 is not transitive without w0 → w2
+    for (from, to) in [(0, 1), (1, 2)]
+    {
+        graph.vertices.insert(CountermodelGraphVertex { from:PossibleWorld { index:from }, to:PossibleWorld { index:to }, tags:vec![] });
+    }
+
+    assert!(!graph.is_transitive());
+    assert!(graph.validate(&logic).is_err());
+
+    graph.vertices.insert(CountermodelGraphVertex { from:PossibleWorld { index:0 }, to:PossibleWorld { index:2 }, tags:vec![] });
+
+    assert!(graph.is_transitive());
+    assert!(graph.validate(&logic).is_ok());
+
+    return Ok(());
+}
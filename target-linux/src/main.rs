@@ -16,6 +16,7 @@ use prover::parser::algorithm::LogicalExpressionParser;
 use prover::problem::catalog::get_demo_problem_catalog;
 use prover::problem::json::ProblemJSON;
 use prover::problem::{Problem, ProblemFlags};
+use prover::proof::cancellation::ProofCancellationToken;
 use prover::utils::{parallel_for_each_problem, setup_panicking_from_all_future_threads};
 
 const OUTPUT_DIR_PATH : &str = "./target/html";
@@ -94,7 +95,7 @@ fn prove_problems_from_the_book() -> Result<()>
 
         let formula_format_options = FormulaFormatOptions::recommended_for(&logic);
 
-        let proof_tree = problem.prove();
+        let proof_tree = problem.prove(ProofCancellationToken::from_config(None));
         let proof_tree_json = proof_tree.to_json(&formula_format_options).context(codeloc!())?;
 
         let template = mustache::compile_str(TEMPLATE).context(codeloc!())?;
@@ -134,7 +135,7 @@ fn prove_problem(proof_file_path : &str, problem : Problem) -> Result<()>
 
     let formula_format_options = FormulaFormatOptions::recommended_for(&problem.logic);
 
-    let proof_tree = problem.prove();
+    let proof_tree = problem.prove(ProofCancellationToken::from_config(None));
     let proof_tree_json = proof_tree.to_json(&formula_format_options).context(codeloc!())?;
 
     let template = mustache::compile_str(TEMPLATE).context(codeloc!())?;
@@ -368,6 +368,10 @@ function update_problem_input_area(problem, proof_tree)
         proof_status_label.innerText = '';
     else if (proof_tree.has_timeout)
         proof_status_label.innerText = 'TIMEOUT!';
+    else if (proof_tree.cancellation === 'DeadlineExceeded')
+        proof_status_label.innerText = 'DEADLINE EXCEEDED!';
+    else if (proof_tree.cancellation)
+        proof_status_label.innerText = 'CANCELLED!';
     else if (proof_tree.was_proved)
         proof_status_label.innerText = 'PROVED!';
     else proof_status_label.innerText = 'DISPROVED!';
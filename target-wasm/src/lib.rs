@@ -9,12 +9,17 @@ use prover::logic::LogicFactory;
 use prover::parser::token_types::TokenTypeID;
 use prover::problem::catalog::get_demo_problem_catalog;
 use prover::problem::json::ProblemJSON;
+use prover::proof::cancellation::ProofCancellationToken;
+use std::time::Duration;
 use strum::IntoEnumIterator;
 use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::JsError;
 
 pub const PROPOSITIONAL_LOGIC_CATEGORY_NAME : &str = "PropositionalLogic";
 
+//the proof runs on the UI thread, it must not freeze the browser tab
+const DEFAULT_PROOF_DEADLINE : Duration = Duration::from_secs(30);
+
 #[wasm_bindgen]
 pub fn setup_console_error_panic_hook()
 {
@@ -54,8 +59,10 @@ pub fn get_logics_categories() -> Vec<String>
     let domain_types =
     [
         ConstantDomain,
-        VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:false }),
-        VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:true }),
+        VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:false, has_negativity_constraint:true }),
+        VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:true, has_negativity_constraint:true }),
+        VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:false, has_negativity_constraint:false }),
+        VariableDomain(VariableDomainFlags { has_domain_increasing_constraint:true, has_negativity_constraint:false }),
     ];
 
     for domain_type in domain_types
@@ -79,8 +86,7 @@ pub fn get_default_logic_category() -> String
 #[wasm_bindgen]
 pub fn get_operator_symbols(logic_name : String) -> Vec<String>
 {
-    if let Some(logic) = LogicFactory::get_logic_theories().into_iter()
-        .find(|logic| logic.get_name().to_string() == logic_name)
+    if let Ok(logic) = LogicFactory::get_logic_by_name(&logic_name)
     {
         return get_operator_symbols_impl(logic.get_parser_syntax());
     }
@@ -118,7 +124,7 @@ pub fn solve_problem(problem_raw_json : String) -> Result<String, JsError>
 
     let formula_format_options = FormulaFormatOptions::recommended_for(&problem.logic);
 
-    let proof_tree = problem.prove();
+    let proof_tree = problem.prove(ProofCancellationToken::from_config(Some(DEFAULT_PROOF_DEADLINE)));
     let proof_tree_json = proof_tree.to_json(&formula_format_options)
         .map_err(|err| JsError::new(err.chain().last().unwrap().to_string().as_str()))?;
 
@@ -128,9 +134,8 @@ pub fn solve_problem(problem_raw_json : String) -> Result<String, JsError>
 #[wasm_bindgen]
 pub fn should_skip_rendering_modality_graph(logic_name_raw : String) -> bool
 {
-    let logic_name = LogicFactory::get_logic_theories().iter()
+    let logic_name = LogicFactory::get_logic_by_name(&logic_name_raw)
         .map(|logic| logic.get_name())
-        .find(|logic_name| logic_name.to_string() == logic_name_raw)
         .unwrap_or(PropositionalLogic{}.get_name());
 
     return !logic_name.is_modal_logic();